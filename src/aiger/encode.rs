@@ -0,0 +1,124 @@
+use super::{
+    Aig,
+    AigLit,
+    Reset,
+};
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use core::iter;
+use std::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+/// The solver literals of all variables of an and-inverter graph in one time frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// The solver literal of every used variable, the one of variable `0` is `false`.
+    lits: BTreeMap<u32, Lit>,
+}
+
+impl Frame {
+    /// Returns the solver literal of the literal of the graph.
+    ///
+    /// # Panics
+    ///
+    /// If the variable of the literal is not used by the graph.
+    pub fn lit(&self, lit: AigLit) -> Lit {
+        let var_lit = self.lits[&lit.var()];
+        if lit.is_negated() { !var_lit } else { var_lit }
+    }
+
+    /// Returns the solver literals of the given literals of the graph.
+    pub fn lits<'a>(&'a self, lits: &'a [AigLit]) -> impl Iterator<Item = Lit> + 'a {
+        lits.iter().map(move |&lit| self.lit(lit))
+    }
+}
+
+impl Aig {
+    /// Adds the Tseitin encoding of one time frame of the graph to the solver.
+    ///
+    /// Inputs, latches and and gates get fresh solver variables except for the latches
+    /// which use the given current state if any. The constant `false` gets a fresh
+    /// variable that is forced to be `false`. Variables that are used but never
+    /// defined get unconstrained fresh variables and unused variables get none.
+    /// Every and gate `a = b & c` adds the clauses `!a | b`, `!a | c` and `a | !b | !c`.
+    ///
+    /// # Panics
+    ///
+    /// If the given state does not have one literal per latch.
+    pub fn encode<S>(&self, solver: &mut S, vars: &mut VarManager, state: Option<&[Lit]>) -> Frame
+    where
+        S: IpasirSolver,
+    {
+        let mut lits = BTreeMap::new();
+        let false_lit = vars.fresh_lit();
+        solver.add_clause(Some(!false_lit));
+        lits.insert(0, false_lit);
+        match state {
+            Some(state) => {
+                assert_eq!(state.len(), self.latches.len(), "the state must have one literal per latch");
+                for (latch, &lit) in self.latches.iter().zip(state) {
+                    lits.insert(latch.lit.var(), lit);
+                }
+            },
+            None => {
+                for latch in &self.latches {
+                    lits.insert(latch.lit.var(), vars.fresh_lit());
+                }
+            },
+        }
+        let defined = self.inputs.iter().chain(self.ands.iter().map(|and| &and.lhs));
+        for lit in defined {
+            lits.insert(lit.var(), vars.fresh_lit());
+        }
+        let used = self
+            .ands
+            .iter()
+            .flat_map(|and| iter::once(and.rhs0).chain(iter::once(and.rhs1)))
+            .chain(self.latches.iter().map(|latch| latch.next))
+            .chain(self.outputs.iter().copied())
+            .chain(self.bad.iter().copied())
+            .chain(self.constraints.iter().copied())
+            .chain(self.justice.iter().flatten().copied())
+            .chain(self.fairness.iter().copied());
+        for lit in used {
+            lits.entry(lit.var()).or_insert_with(|| vars.fresh_lit());
+        }
+        let frame = Frame { lits };
+        for and in &self.ands {
+            let (lhs, rhs0, rhs1) = (frame.lit(and.lhs), frame.lit(and.rhs0), frame.lit(and.rhs1));
+            solver.add_clause(vec![!lhs, rhs0]);
+            solver.add_clause(vec![!lhs, rhs1]);
+            solver.add_clause(vec![lhs, !rhs0, !rhs1]);
+        }
+        frame
+    }
+
+    /// Adds unit clauses forcing the latches of the frame to their initial values.
+    ///
+    /// Uninitialized latches remain unconstrained.
+    pub fn encode_reset<S>(&self, solver: &mut S, frame: &Frame)
+    where
+        S: IpasirSolver,
+    {
+        for latch in &self.latches {
+            let lit = frame.lit(latch.lit);
+            match latch.reset {
+                Reset::Zero => solver.add_clause(Some(!lit)),
+                Reset::One => solver.add_clause(Some(lit)),
+                Reset::Undefined => (),
+            }
+        }
+    }
+
+    /// Returns the solver literals of the next state of the latches in the frame.
+    ///
+    /// Pass them as state to `encode` to unroll the transition relation.
+    pub fn next_state(&self, frame: &Frame) -> Vec<Lit> {
+        self.latches.iter().map(|latch| frame.lit(latch.next)).collect()
+    }
+}
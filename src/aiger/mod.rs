@@ -0,0 +1,238 @@
+//! Reading and writing and-inverter graphs in the AIGER format.
+//!
+//! Both the ASCII format with header `aag M I L O A [B C J F]` and the binary
+//! format with header `aig M I L O A [B C J F]` of AIGER 1.9 are supported,
+//! including latches with reset values, bad-state properties, invariant
+//! constraints, justice and fairness properties, the symbol table and comments.
+//!
+//! An AIGER literal is twice its variable plus one if it is negated, the
+//! variable `0` is the constant `false`. `Aig::encode` translates one time
+//! frame of the graph into clauses over solver literals and `Witness`
+//! writes counterexamples in the AIGER witness format.
+
+mod parser;
+mod writer;
+mod encode;
+mod witness;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    parser::{
+        AigerErrorKind,
+        read_aiger,
+    },
+    writer::write_aiger,
+    encode::Frame,
+    witness::{
+        Property,
+        Witness,
+        write_witness,
+    },
+};
+use core::ops::Not;
+use std::{
+    string::String,
+    vec::Vec,
+};
+
+/// The variant of the AIGER format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// The ASCII format with header `aag`.
+    Ascii,
+    /// The binary format with header `aig`.
+    Binary,
+}
+
+/// A literal of an and-inverter graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AigLit(u32);
+
+impl AigLit {
+    /// The constant `false` literal.
+    pub const FALSE: Self = AigLit(0);
+
+    /// The constant `true` literal.
+    pub const TRUE: Self = AigLit(1);
+
+    /// Creates the literal of the variable with the given polarity.
+    ///
+    /// # Panics
+    ///
+    /// If the literal does not fit into `u32`.
+    pub fn new(var: u32, negated: bool) -> Self {
+        let raw = var.checked_mul(2).and_then(|raw| raw.checked_add(negated as u32));
+        AigLit(raw.expect("AIGER variable out of range"))
+    }
+
+    /// Creates a literal from its raw AIGER value.
+    pub fn from_raw(raw: u32) -> Self {
+        AigLit(raw)
+    }
+
+    /// Returns the raw AIGER value of the literal.
+    pub fn to_raw(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the variable of the literal.
+    pub fn var(self) -> u32 {
+        self.0 >> 1
+    }
+
+    /// Returns `true` if the literal is negated.
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    /// Returns `true` if the literal is one of the constants.
+    pub fn is_constant(self) -> bool {
+        self.var() == 0
+    }
+}
+
+impl Not for AigLit {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        AigLit(self.0 ^ 1)
+    }
+}
+
+/// The initial value of a latch.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Reset {
+    /// The latch is initialized to `false`.
+    Zero,
+    /// The latch is initialized to `true`.
+    One,
+    /// The latch is uninitialized.
+    Undefined,
+}
+
+/// A latch of an and-inverter graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Latch {
+    /// The non-negated literal of the current state.
+    pub lit: AigLit,
+    /// The literal of the next state.
+    pub next: AigLit,
+    /// The initial value.
+    pub reset: Reset,
+}
+
+/// An and gate of an and-inverter graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct And {
+    /// The non-negated output literal.
+    pub lhs: AigLit,
+    /// The first input literal.
+    pub rhs0: AigLit,
+    /// The second input literal.
+    pub rhs1: AigLit,
+}
+
+/// The kind of the entity a symbol names.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// An input with prefix `i`.
+    Input,
+    /// A latch with prefix `l`.
+    Latch,
+    /// An output with prefix `o`.
+    Output,
+    /// A bad-state property with prefix `b`.
+    Bad,
+    /// An invariant constraint with prefix `c`.
+    Constraint,
+    /// A justice property with prefix `j`.
+    Justice,
+    /// A fairness constraint with prefix `f`.
+    Fairness,
+}
+
+impl SymbolKind {
+    /// Returns the prefix of the symbol kind in the symbol table.
+    pub(crate) fn prefix(self) -> char {
+        match self {
+            SymbolKind::Input => 'i',
+            SymbolKind::Latch => 'l',
+            SymbolKind::Output => 'o',
+            SymbolKind::Bad => 'b',
+            SymbolKind::Constraint => 'c',
+            SymbolKind::Justice => 'j',
+            SymbolKind::Fairness => 'f',
+        }
+    }
+}
+
+/// A name of an entity of an and-inverter graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    /// The kind of the named entity.
+    pub kind: SymbolKind,
+    /// The zero-based position of the entity among those of its kind.
+    pub index: usize,
+    /// The name.
+    pub name: String,
+}
+
+/// A sequential and-inverter graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aig {
+    /// The greatest variable.
+    pub max_var: u32,
+    /// The non-negated literals of all inputs.
+    pub inputs: Vec<AigLit>,
+    /// The latches.
+    pub latches: Vec<Latch>,
+    /// The outputs.
+    pub outputs: Vec<AigLit>,
+    /// The bad-state properties.
+    pub bad: Vec<AigLit>,
+    /// The invariant constraints.
+    pub constraints: Vec<AigLit>,
+    /// The justice properties, each a set of literals that must hold infinitely often.
+    pub justice: Vec<Vec<AigLit>>,
+    /// The fairness constraints.
+    pub fairness: Vec<AigLit>,
+    /// The and gates.
+    pub ands: Vec<And>,
+    /// The symbol table.
+    pub symbols: Vec<Symbol>,
+    /// The lines of the comment section.
+    pub comments: Vec<String>,
+}
+
+impl Aig {
+    /// Creates a new empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an input with a fresh variable and returns its literal.
+    pub fn add_input(&mut self) -> AigLit {
+        self.max_var += 1;
+        let lit = AigLit::new(self.max_var, false);
+        self.inputs.push(lit);
+        lit
+    }
+
+    /// Adds an and gate with a fresh variable over the inputs and returns its output literal.
+    pub fn add_and(&mut self, rhs0: AigLit, rhs1: AigLit) -> AigLit {
+        self.max_var += 1;
+        let lhs = AigLit::new(self.max_var, false);
+        self.ands.push(And { lhs, rhs0, rhs1 });
+        lhs
+    }
+
+    /// Returns the name of the entity if the symbol table has one.
+    pub fn symbol(&self, kind: SymbolKind, index: usize) -> Option<&str> {
+        self.symbols
+            .iter()
+            .find(|symbol| symbol.kind == kind && symbol.index == index)
+            .map(|symbol| symbol.name.as_str())
+    }
+}
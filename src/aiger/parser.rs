@@ -0,0 +1,392 @@
+use super::{
+    Aig,
+    AigLit,
+    And,
+    Latch,
+    Reset,
+    Symbol,
+    SymbolKind,
+};
+use crate::dimacs::{
+    Lexer,
+    ParseError,
+    ParseErrorKind,
+};
+use std::{
+    boxed::Box,
+    collections::HashSet,
+    error::Error,
+    fmt,
+    io::Read,
+    string::String,
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to AIGER graphs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AigerErrorKind {
+    /// A literal, delta, reset value, symbol index or size is out of its allowed range.
+    InvalidValue(i128),
+    /// A variable is defined more than once.
+    DuplicateDefinition(u32),
+}
+
+impl fmt::Display for AigerErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AigerErrorKind::InvalidValue(value) => write!(f, "invalid value {}", value),
+            AigerErrorKind::DuplicateDefinition(var) => write!(f, "variable {} is defined twice", var),
+        }
+    }
+}
+
+impl Error for AigerErrorKind {}
+
+impl From<AigerErrorKind> for ParseErrorKind {
+    fn from(kind: AigerErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// The counts of the header `M I L O A B C J F`.
+#[derive(Debug, Copy, Clone, Default)]
+struct Header {
+    max_var: u32,
+    inputs: usize,
+    latches: usize,
+    outputs: usize,
+    ands: usize,
+    bad: usize,
+    constraints: usize,
+    justice: usize,
+    fairness: usize,
+}
+
+/// Parses AIGER input.
+struct Parser<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+    /// The counts of the header.
+    header: Header,
+    /// `true` for the binary format.
+    binary: bool,
+    /// The variables that have been defined.
+    defined: HashSet<u32>,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Parses the header line.
+    fn parse_header(&mut self) -> Result<()> {
+        let start = self.lexer.position();
+        self.binary = match &self.lexer.read_word()?[..] {
+            b"aag" => false,
+            b"aig" => true,
+            _ => return self.lexer.error_at(start, ParseErrorKind::InvalidHeader),
+        };
+        let mut counts = Vec::new();
+        loop {
+            self.lexer.skip_blanks()?;
+            match self.lexer.peek()? {
+                None | Some(b'\n') => break,
+                Some(_) => match self.lexer.read_usize()? {
+                    Some(count) => counts.push(count),
+                    None => return self.lexer.error_at(start, ParseErrorKind::InvalidHeader),
+                },
+            }
+        }
+        self.lexer.skip_line()?;
+        if counts.len() < 5 || counts.len() > 9 || counts[0] > (u32::MAX >> 1) as usize {
+            return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+        }
+        counts.resize(9, 0);
+        self.header = Header {
+            max_var: counts[0] as u32,
+            inputs: counts[1],
+            latches: counts[2],
+            outputs: counts[3],
+            ands: counts[4],
+            bad: counts[5],
+            constraints: counts[6],
+            justice: counts[7],
+            fairness: counts[8],
+        };
+        let defined = counts[1].checked_add(counts[2]).and_then(|sum| sum.checked_add(counts[4]));
+        if defined.map(|defined| defined > counts[0]).unwrap_or(true) {
+            return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+        }
+        Ok(())
+    }
+
+    /// Consumes the rest of the current line which must be blank.
+    fn end_line(&mut self) -> Result<()> {
+        self.lexer.finish_line()?;
+        self.lexer.skip_line()
+    }
+
+    /// Reads an integer on the current line.
+    fn read_value(&mut self) -> Result<(i128, (usize, usize))> {
+        self.lexer.skip_blanks()?;
+        let start = self.lexer.position();
+        match self.lexer.read_int()? {
+            Some(value) => Ok((value, start)),
+            None => self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Reads a literal that is at most `2M + 1`.
+    fn read_lit(&mut self) -> Result<AigLit> {
+        let (value, start) = self.read_value()?;
+        if !(0..=2 * i128::from(self.header.max_var) + 1).contains(&value) {
+            return self.lexer.error_at(start, AigerErrorKind::InvalidValue(value))
+        }
+        Ok(AigLit::from_raw(value as u32))
+    }
+
+    /// Reads a literal on a line of its own.
+    fn read_lit_line(&mut self) -> Result<AigLit> {
+        let lit = self.read_lit()?;
+        self.end_line()?;
+        Ok(lit)
+    }
+
+    /// Reads a literal that defines a variable.
+    fn read_definition(&mut self) -> Result<AigLit> {
+        self.lexer.skip_blanks()?;
+        let start = self.lexer.position();
+        let lit = self.read_lit()?;
+        self.define(lit, start)?;
+        Ok(lit)
+    }
+
+    /// Marks the variable of the literal as defined.
+    fn define(&mut self, lit: AigLit, start: (usize, usize)) -> Result<()> {
+        if lit.is_negated() || lit.is_constant() {
+            return self.lexer.error_at(start, AigerErrorKind::InvalidValue(i128::from(lit.to_raw())))
+        }
+        if !self.defined.insert(lit.var()) {
+            return self.lexer.error_at(start, AigerErrorKind::DuplicateDefinition(lit.var()))
+        }
+        Ok(())
+    }
+
+    /// Reads a latch whose literal is given in the binary format and read otherwise.
+    fn read_latch(&mut self, lit: Option<AigLit>) -> Result<Latch> {
+        let start = self.lexer.position();
+        let lit = match lit {
+            Some(lit) => {
+                self.define(lit, start)?;
+                lit
+            }
+            None => self.read_definition()?,
+        };
+        let next = self.read_lit()?;
+        self.lexer.skip_blanks()?;
+        let reset = match self.lexer.peek()? {
+            None | Some(b'\n') => Reset::Zero,
+            Some(_) => match self.read_value()? {
+                (0, _) => Reset::Zero,
+                (1, _) => Reset::One,
+                (value, _) if value == i128::from(lit.to_raw()) => Reset::Undefined,
+                (value, start) => return self.lexer.error_at(start, AigerErrorKind::InvalidValue(value)),
+            },
+        };
+        self.end_line()?;
+        Ok(Latch { lit, next, reset })
+    }
+
+    /// Reads an unsigned integer in the variable-length binary encoding.
+    fn read_encoded(&mut self) -> Result<u32> {
+        let start = self.lexer.position();
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = match self.lexer.peek()? {
+                Some(byte) => byte,
+                None => return self.lexer.error(ParseErrorKind::UnexpectedEof),
+            };
+            self.lexer.bump(byte);
+            if shift > 28 {
+                return self.lexer.error_at(start, AigerErrorKind::InvalidValue(i128::from(value)))
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break
+            }
+            shift += 7;
+        }
+        if value > u64::from(u32::MAX) {
+            return self.lexer.error_at(start, AigerErrorKind::InvalidValue(i128::from(value)))
+        }
+        Ok(value as u32)
+    }
+
+    /// Reads a binary and gate with the given output literal.
+    fn read_binary_and(&mut self, lhs: AigLit) -> Result<And> {
+        let start = self.lexer.position();
+        self.define(lhs, start)?;
+        let delta0 = self.read_encoded()?;
+        let delta1 = self.read_encoded()?;
+        let rhs0 = lhs.to_raw().checked_sub(delta0).filter(|_| delta0 > 0);
+        let rhs1 = rhs0.and_then(|rhs0| rhs0.checked_sub(delta1));
+        match (rhs0, rhs1) {
+            (Some(rhs0), Some(rhs1)) => Ok(And {
+                lhs,
+                rhs0: AigLit::from_raw(rhs0),
+                rhs1: AigLit::from_raw(rhs1),
+            }),
+            _ => {
+                let delta = if rhs0.is_none() { delta0 } else { delta1 };
+                self.lexer.error_at(start, AigerErrorKind::InvalidValue(i128::from(delta)))
+            }
+        }
+    }
+
+    /// Parses the symbol table and the comment section.
+    fn parse_symbols(&mut self, aig: &mut Aig) -> Result<()> {
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            let (kind, count) = match self.lexer.peek()? {
+                None => return Ok(()),
+                Some(b'i') => (SymbolKind::Input, self.header.inputs),
+                Some(b'l') => (SymbolKind::Latch, self.header.latches),
+                Some(b'o') => (SymbolKind::Output, self.header.outputs),
+                Some(b'b') => (SymbolKind::Bad, self.header.bad),
+                Some(b'c') => (SymbolKind::Constraint, self.header.constraints),
+                Some(b'j') => (SymbolKind::Justice, self.header.justice),
+                Some(b'f') => (SymbolKind::Fairness, self.header.fairness),
+                Some(_) => return self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+            };
+            self.lexer.bump(kind.prefix() as u8);
+            if kind == SymbolKind::Constraint {
+                if let None | Some(b'\n') = self.lexer.peek()? {
+                    return self.parse_comments(aig)
+                }
+            }
+            let index = match self.lexer.read_int()? {
+                Some(index) if (0..count as i128).contains(&index) => index as usize,
+                Some(index) => return self.lexer.error_at(start, AigerErrorKind::InvalidValue(index)),
+                None => return self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+            };
+            if self.lexer.peek()? != Some(b' ') {
+                return self.lexer.unexpected(ParseErrorKind::UnexpectedEof)
+            }
+            self.lexer.bump(b' ');
+            let name = String::from_utf8_lossy(&self.lexer.read_rest_of_line()?).into_owned();
+            aig.symbols.push(Symbol { kind, index, name });
+        }
+    }
+
+    /// Parses the comment lines after the line `c`.
+    fn parse_comments(&mut self, aig: &mut Aig) -> Result<()> {
+        self.lexer.skip_line()?;
+        while self.lexer.peek()?.is_some() {
+            let line = self.lexer.read_rest_of_line()?;
+            aig.comments.push(String::from_utf8_lossy(&line).into_owned());
+            self.lexer.skip_line()?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses an and-inverter graph in the ASCII or binary AIGER format.
+///
+/// The format is detected from the header.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::aiger::read_aiger;
+///
+/// let input = "aag 3 2 0 1 1\n2\n4\n6\n6 2 4\n";
+/// let aig = read_aiger(input.as_bytes()).unwrap();
+/// assert_eq!(aig.inputs.len(), 2);
+/// assert_eq!(aig.ands.len(), 1);
+/// ```
+pub fn read_aiger<R>(reader: R) -> Result<Aig>
+where
+    R: Read,
+{
+    let mut parser = Parser {
+        lexer: Lexer::new(reader),
+        header: Header::default(),
+        binary: false,
+        defined: HashSet::new(),
+    };
+    parser.parse_header()?;
+    let header = parser.header;
+    let mut aig = Aig {
+        max_var: header.max_var,
+        ..Aig::new()
+    };
+    for index in 0..header.inputs {
+        let input = if parser.binary {
+            let lit = AigLit::new(index as u32 + 1, false);
+            let start = parser.lexer.position();
+            parser.define(lit, start)?;
+            lit
+        } else {
+            let lit = parser.read_definition()?;
+            parser.end_line()?;
+            lit
+        };
+        aig.inputs.push(input);
+    }
+    for index in 0..header.latches {
+        let lit = if parser.binary {
+            Some(AigLit::new((header.inputs + index) as u32 + 1, false))
+        } else {
+            None
+        };
+        aig.latches.push(parser.read_latch(lit)?);
+    }
+    for _ in 0..header.outputs {
+        aig.outputs.push(parser.read_lit_line()?);
+    }
+    for _ in 0..header.bad {
+        aig.bad.push(parser.read_lit_line()?);
+    }
+    for _ in 0..header.constraints {
+        aig.constraints.push(parser.read_lit_line()?);
+    }
+    let mut sizes = Vec::new();
+    for _ in 0..header.justice {
+        match parser.read_value()? {
+            (size, _) if size > 0 => sizes.push(size as usize),
+            (size, start) => return parser.lexer.error_at(start, AigerErrorKind::InvalidValue(size)),
+        }
+        parser.end_line()?;
+    }
+    for size in sizes {
+        let mut lits = Vec::new();
+        for _ in 0..size {
+            lits.push(parser.read_lit_line()?);
+        }
+        aig.justice.push(lits);
+    }
+    for _ in 0..header.fairness {
+        aig.fairness.push(parser.read_lit_line()?);
+    }
+    for index in 0..header.ands {
+        let and = if parser.binary {
+            let lhs = AigLit::new((header.inputs + header.latches + index) as u32 + 1, false);
+            parser.read_binary_and(lhs)?
+        } else {
+            let lhs = parser.read_definition()?;
+            let rhs0 = parser.read_lit()?;
+            let rhs1 = parser.read_lit()?;
+            parser.end_line()?;
+            And { lhs, rhs0, rhs1 }
+        };
+        aig.ands.push(and);
+    }
+    parser.parse_symbols(&mut aig)?;
+    Ok(aig)
+}
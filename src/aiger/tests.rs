@@ -0,0 +1,122 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    SolveResponse,
+    VarManager,
+    tests::TestSolver,
+};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+const FULL: &str = "\
+aag 7 2 1 1 2 1 1 1 1
+2
+4
+6 13 6
+12
+12
+2
+2
+6
+13
+4
+12 2 4
+14 12 7
+i0 request
+l0 state
+b0 never both
+c
+made by hand
+";
+
+/// Writes the graph in the given format.
+fn write(aig: &Aig, format: Format) -> Vec<u8> {
+    let mut output = Vec::new();
+    write_aiger(&mut output, aig, format).unwrap();
+    output
+}
+
+/// Returns the position and message of the parse error of the input.
+fn error(input: &str) -> (usize, usize, String) {
+    let err = read_aiger(input.as_bytes()).unwrap_err();
+    (err.line(), err.column(), err.kind().to_string())
+}
+
+#[test]
+fn ascii_round_trip() {
+    let aig = read_aiger(FULL.as_bytes()).unwrap();
+    assert_eq!(aig.latches, vec![Latch { lit: AigLit::new(3, false), next: AigLit::from_raw(13), reset: Reset::Undefined }]);
+    assert_eq!(aig.justice, vec![vec![AigLit::from_raw(6), AigLit::from_raw(13)]]);
+    assert_eq!(aig.symbol(SymbolKind::Bad, 0), Some("never both"));
+    assert_eq!(aig.comments, vec!["made by hand".to_string()]);
+    assert_eq!(String::from_utf8(write(&aig, Format::Ascii)).unwrap(), FULL);
+}
+
+#[test]
+fn binary_renumbers_variables() {
+    let aig = read_aiger(FULL.as_bytes()).unwrap();
+    let binary = write(&aig, Format::Binary);
+    assert!(binary.starts_with(b"aig 5 2 1 1 2 1 1 1 1\n9 6\n"));
+    let renumbered = read_aiger(&binary[..]).unwrap();
+    let expected = "\
+aag 5 2 1 1 2 1 1 1 1\n2\n4\n6 9 6\n8\n8\n2\n2\n6\n9\n4\n8 4 2\n10 8 7\n\
+i0 request\nl0 state\nb0 never both\nc\nmade by hand\n";
+    assert_eq!(String::from_utf8(write(&renumbered, Format::Ascii)).unwrap(), expected);
+    assert_eq!(write(&renumbered, Format::Binary), binary);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(error("aag 2 1 0 0 1\n2\n2 2 2\n"), (3, 1, "variable 1 is defined twice".to_string()));
+    assert_eq!(error("aag 1 1 0 1 0\n2\n4\n"), (3, 1, "invalid value 4".to_string()));
+    let err = read_aiger("aag 2 1 0 0 1\n2\n2 2 2\n".as_bytes()).unwrap_err();
+    assert_eq!(err.kind().format_kind(), Some(&AigerErrorKind::DuplicateDefinition(1)));
+    assert_eq!(error("aag 1 2 0 0 0\n"), (1, 1, "invalid header".to_string()));
+    assert_eq!(error("aig 1 0 0 0 1\n\x02"), (2, 2, "unexpected end of input".to_string()));
+}
+
+#[test]
+fn sparse_variables() {
+    // The header admits far more variables than are defined.
+    let input = "aag 2147483647 1 0 1 1\n2\n4294967294\n4294967294 2 3\n";
+    let aig = read_aiger(input.as_bytes()).unwrap();
+    assert_eq!(aig.max_var, 2147483647);
+    assert_eq!(String::from_utf8(write(&aig, Format::Ascii)).unwrap(), input);
+    assert!(write(&aig, Format::Binary).starts_with(b"aig 2 1 0 1 1\n"));
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let frame = aig.encode(&mut solver, &mut vars, None);
+    assert_eq!(vars.num_vars(), 3);
+    solver.assume(frame.lit(aig.outputs[0]));
+    assert_eq!(solver.solve().unwrap(), SolveResponse::Unsat);
+}
+
+#[test]
+fn bounded_model_checking() {
+    // A shift register of two latches fed by an input, bad if both latches are set.
+    let aig = read_aiger("aag 4 1 2 0 1 1\n2\n4 2\n6 4\n8\n8 4 6\n".as_bytes()).unwrap();
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let mut frames: Vec<Frame> = Vec::new();
+    let depth = loop {
+        let state = frames.last().map(|frame| aig.next_state(frame));
+        let frame = aig.encode(&mut solver, &mut vars, state.as_deref());
+        if frames.is_empty() {
+            aig.encode_reset(&mut solver, &frame);
+        }
+        solver.assume(frame.lit(aig.bad[0]));
+        frames.push(frame);
+        if solver.solve().unwrap() == SolveResponse::Sat {
+            break frames.len() - 1
+        }
+    };
+    assert_eq!(depth, 2);
+    let witness = Witness::from_frames(&mut solver, &aig, &frames, vec![Property::Bad(0)]).unwrap();
+    let mut output = Vec::new();
+    write_witness(&mut output, &witness).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("1\nb0\n00\n1\n1\n"), "{}", output);
+    assert!(output.ends_with("\n.\n"));
+}
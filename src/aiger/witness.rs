@@ -0,0 +1,110 @@
+use super::{
+    Aig,
+    Frame,
+};
+use crate::{
+    LitValue,
+    IpasirSolver,
+    Result,
+};
+use std::{
+    io::{
+        self,
+        Write,
+    },
+    vec::Vec,
+};
+
+/// A property of an and-inverter graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Property {
+    /// The bad-state property with the given index, written as `b<index>`.
+    Bad(usize),
+    /// The justice property with the given index, written as `j<index>`.
+    Justice(usize),
+}
+
+/// A counterexample trace in the AIGER witness format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Witness {
+    /// The violated properties.
+    pub properties: Vec<Property>,
+    /// The initial values of all latches.
+    pub latches: Vec<LitValue>,
+    /// The values of all inputs in every time frame.
+    pub inputs: Vec<Vec<LitValue>>,
+}
+
+impl Witness {
+    /// Creates a witness from the model of the solver for the unrolled time frames.
+    ///
+    /// The initial latch values are taken from the first frame.
+    ///
+    /// # States
+    ///
+    /// - **Required:** SAT
+    /// - **After:** SAT
+    pub fn from_frames<S>(solver: &mut S, aig: &Aig, frames: &[Frame], properties: Vec<Property>) -> Result<Self>
+    where
+        S: IpasirSolver,
+    {
+        let mut latches = Vec::with_capacity(aig.latches.len());
+        if let Some(first) = frames.first() {
+            for latch in &aig.latches {
+                latches.push(solver.val(first.lit(latch.lit))?);
+            }
+        }
+        let mut inputs = Vec::with_capacity(frames.len());
+        for frame in frames {
+            let mut values = Vec::with_capacity(aig.inputs.len());
+            for lit in frame.lits(&aig.inputs) {
+                values.push(solver.val(lit)?);
+            }
+            inputs.push(values);
+        }
+        Ok(Self { properties, latches, inputs })
+    }
+}
+
+/// Writes a line of values as `1`, `0` or `x` for don't-care.
+fn write_values<W>(writer: &mut W, values: &[LitValue]) -> io::Result<()>
+where
+    W: Write,
+{
+    for value in values {
+        let c = match value {
+            LitValue::True => '1',
+            LitValue::False => '0',
+            LitValue::DontCare => 'x',
+        };
+        write!(writer, "{}", c)?;
+    }
+    writeln!(writer)
+}
+
+/// Writes the witness in the AIGER witness format.
+///
+/// The witness starts with the status line `1`, followed by the violated
+/// properties, the initial latch values and the input values of each time
+/// frame, and ends with a line `.`.
+pub fn write_witness<W>(writer: &mut W, witness: &Witness) -> io::Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "1")?;
+    for (n, property) in witness.properties.iter().enumerate() {
+        if n > 0 {
+            write!(writer, " ")?;
+        }
+        match property {
+            Property::Bad(index) => write!(writer, "b{}", index)?,
+            Property::Justice(index) => write!(writer, "j{}", index)?,
+        }
+    }
+    writeln!(writer)?;
+    write_values(writer, &witness.latches)?;
+    for inputs in &witness.inputs {
+        write_values(writer, inputs)?;
+    }
+    writeln!(writer, ".")
+}
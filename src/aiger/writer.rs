@@ -0,0 +1,215 @@
+use super::{
+    Aig,
+    AigLit,
+    And,
+    Format,
+    Reset,
+};
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    io::{
+        self,
+        Write,
+    },
+    vec::Vec,
+};
+
+/// Returns an `InvalidInput` error with the given message.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// The variables of a graph renumbered for the binary format.
+///
+/// Inputs come first, then latches and then the and gates in topological order.
+struct Renumbering {
+    /// The new variable of every old variable that is defined.
+    vars: HashMap<u32, u32>,
+    /// The and gates in topological order.
+    ands: Vec<And>,
+}
+
+impl Renumbering {
+    /// Renumbers the variables of the graph.
+    fn new(aig: &Aig) -> io::Result<Self> {
+        let mut gates = HashMap::new();
+        for (index, and) in aig.ands.iter().enumerate() {
+            if and.lhs.var() > aig.max_var {
+                return Err(invalid("variable exceeds maximum"))
+            }
+            gates.insert(and.lhs.var(), index);
+        }
+        let mut vars = HashMap::new();
+        vars.insert(0, 0);
+        let mut next = 1;
+        let defined = aig.inputs.iter().chain(aig.latches.iter().map(|latch| &latch.lit));
+        for lit in defined {
+            if lit.var() > aig.max_var {
+                return Err(invalid("variable exceeds maximum"))
+            }
+            vars.insert(lit.var(), next);
+            next += 1;
+        }
+        let mut ands = Vec::with_capacity(aig.ands.len());
+        let mut visited = HashSet::new();
+        let mut stack = Vec::new();
+        for root in 0..aig.ands.len() {
+            stack.push((root, false));
+            while let Some((index, expanded)) = stack.pop() {
+                let and = aig.ands[index];
+                let var = and.lhs.var();
+                if vars.contains_key(&var) {
+                    continue
+                }
+                if expanded {
+                    vars.insert(var, next);
+                    next += 1;
+                    ands.push(and);
+                    continue
+                }
+                if !visited.insert(var) {
+                    return Err(invalid("and gates form a cycle"))
+                }
+                stack.push((index, true));
+                for rhs in &[and.rhs0, and.rhs1] {
+                    if rhs.var() > aig.max_var {
+                        return Err(invalid("variable exceeds maximum"))
+                    }
+                    if !vars.contains_key(&rhs.var()) {
+                        match gates.get(&rhs.var()) {
+                            Some(&gate) => stack.push((gate, false)),
+                            None => return Err(invalid("variable is undefined")),
+                        }
+                    }
+                }
+            }
+        }
+        Ok(Self { vars, ands })
+    }
+
+    /// Returns the renumbered literal.
+    fn lit(&self, lit: AigLit) -> io::Result<AigLit> {
+        match self.vars.get(&lit.var()) {
+            Some(&var) => Ok(AigLit::new(var, lit.is_negated())),
+            _ => Err(invalid("variable is undefined")),
+        }
+    }
+}
+
+/// Writes an unsigned integer in the variable-length binary encoding.
+fn write_encoded<W>(writer: &mut W, mut value: u32) -> io::Result<()>
+where
+    W: Write,
+{
+    while value >= 0x80 {
+        writer.write_all(&[(value & 0x7f) as u8 | 0x80])?;
+        value >>= 7;
+    }
+    writer.write_all(&[value as u8])
+}
+
+/// Writes the literal on a line of its own.
+fn write_lit<W>(writer: &mut W, lit: AigLit) -> io::Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "{}", lit.to_raw())
+}
+
+/// Writes the and-inverter graph in the given AIGER format.
+///
+/// The optional counts of the header are only written as far as needed.
+/// The binary format requires variables to be numbered in a fixed order,
+/// so they are renumbered with inputs, latches and the symbol table
+/// keeping their order.
+///
+/// # Errors
+///
+/// Returns an `InvalidInput` error for the binary format if an and gate
+/// or latch uses an undefined variable or the and gates form a cycle.
+pub fn write_aiger<W>(writer: &mut W, aig: &Aig, format: Format) -> io::Result<()>
+where
+    W: Write,
+{
+    let renumbering = match format {
+        Format::Ascii => None,
+        Format::Binary => Some(Renumbering::new(aig)?),
+    };
+    let lit = |lit: AigLit| match &renumbering {
+        Some(renumbering) => renumbering.lit(lit),
+        None => Ok(lit),
+    };
+    let (keyword, max_var) = match format {
+        Format::Ascii => ("aag", aig.max_var),
+        Format::Binary => ("aig", (aig.inputs.len() + aig.latches.len() + aig.ands.len()) as u32),
+    };
+    write!(
+        writer, "{} {} {} {} {} {}",
+        keyword, max_var, aig.inputs.len(), aig.latches.len(), aig.outputs.len(), aig.ands.len()
+    )?;
+    let optional = [aig.bad.len(), aig.constraints.len(), aig.justice.len(), aig.fairness.len()];
+    let needed = optional.iter().rposition(|&count| count > 0).map(|last| last + 1).unwrap_or(0);
+    for count in &optional[..needed] {
+        write!(writer, " {}", count)?;
+    }
+    writeln!(writer)?;
+    if format == Format::Ascii {
+        for &input in &aig.inputs {
+            write_lit(writer, input)?;
+        }
+    }
+    for latch in &aig.latches {
+        if format == Format::Ascii {
+            write!(writer, "{} ", latch.lit.to_raw())?;
+        }
+        write!(writer, "{}", lit(latch.next)?.to_raw())?;
+        match latch.reset {
+            Reset::Zero => (),
+            Reset::One => write!(writer, " 1")?,
+            Reset::Undefined => write!(writer, " {}", lit(latch.lit)?.to_raw())?,
+        }
+        writeln!(writer)?;
+    }
+    for &output in aig.outputs.iter().chain(&aig.bad).chain(&aig.constraints) {
+        write_lit(writer, lit(output)?)?;
+    }
+    for justice in &aig.justice {
+        writeln!(writer, "{}", justice.len())?;
+    }
+    for &justice in aig.justice.iter().flatten() {
+        write_lit(writer, lit(justice)?)?;
+    }
+    for &fairness in &aig.fairness {
+        write_lit(writer, lit(fairness)?)?;
+    }
+    match &renumbering {
+        None => {
+            for and in &aig.ands {
+                writeln!(writer, "{} {} {}", and.lhs.to_raw(), and.rhs0.to_raw(), and.rhs1.to_raw())?;
+            }
+        }
+        Some(renumbering) => {
+            for and in &renumbering.ands {
+                let lhs = renumbering.lit(and.lhs)?.to_raw();
+                let rhs0 = renumbering.lit(and.rhs0)?.to_raw();
+                let rhs1 = renumbering.lit(and.rhs1)?.to_raw();
+                let (rhs0, rhs1) = if rhs0 >= rhs1 { (rhs0, rhs1) } else { (rhs1, rhs0) };
+                write_encoded(writer, lhs - rhs0)?;
+                write_encoded(writer, rhs0 - rhs1)?;
+            }
+        }
+    }
+    for symbol in &aig.symbols {
+        writeln!(writer, "{}{} {}", symbol.kind.prefix(), symbol.index, symbol.name)?;
+    }
+    if !aig.comments.is_empty() {
+        writeln!(writer, "c")?;
+        for comment in &aig.comments {
+            writeln!(writer, "{}", comment)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use crate::{
+    Var,
+    Lit,
+    Clause,
+    OwnedClause,
+    IpasirSolver,
+};
+use alloc::vec::Vec;
+use core::{
+    iter,
+    slice,
+};
+
+/// A formula in conjunctive normal form that owns its clauses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cnf {
+    /// The clauses of the formula.
+    clauses: Vec<OwnedClause>,
+    /// The greatest variable of all clauses.
+    max_var: Option<Var>,
+}
+
+impl Cnf {
+    /// Creates a new empty formula.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of clauses of the formula.
+    pub fn len(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Returns `true` if the formula has no clauses.
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// Returns the greatest variable of all clauses if any.
+    pub fn max_var(&self) -> Option<Var> {
+        self.max_var
+    }
+
+    /// Returns the number of variables of the formula.
+    ///
+    /// # Note
+    ///
+    /// This is the number of the greatest variable
+    /// as is the convention for the DIMACS format.
+    pub fn num_vars(&self) -> usize {
+        self.max_var.map(|var| var.index() + 1).unwrap_or(0)
+    }
+
+    /// Adds a clause to the formula.
+    pub fn add_clause<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        self.push(lits.into_iter().collect())
+    }
+
+    /// Adds an owned clause to the formula.
+    pub fn push(&mut self, clause: OwnedClause) {
+        for lit in &clause {
+            self.max_var = self.max_var.max(Some(lit.var()));
+        }
+        self.clauses.push(clause);
+    }
+
+    /// Returns an iterator over the clauses of the formula.
+    pub fn iter(&self) -> slice::Iter<'_, OwnedClause> {
+        self.clauses.iter()
+    }
+
+    /// Returns an iterator over borrowed views of the clauses of the formula.
+    pub fn clauses(&self) -> impl Iterator<Item = Clause<'_>> {
+        self.clauses.iter().map(OwnedClause::as_clause)
+    }
+
+    /// Adds all clauses of the formula to the given solver.
+    pub fn add_to<S>(&self, solver: &mut S)
+    where
+        S: IpasirSolver,
+    {
+        for clause in &self.clauses {
+            solver.add_clause(clause);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Cnf {
+    type Item = &'a OwnedClause;
+    type IntoIter = slice::Iter<'a, OwnedClause>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl iter::Extend<OwnedClause> for Cnf {
+    fn extend<I>(&mut self, clauses: I)
+    where
+        I: IntoIterator<Item = OwnedClause>,
+    {
+        for clause in clauses {
+            self.push(clause);
+        }
+    }
+}
+
+impl iter::FromIterator<OwnedClause> for Cnf {
+    fn from_iter<I>(clauses: I) -> Self
+    where
+        I: IntoIterator<Item = OwnedClause>,
+    {
+        let mut cnf = Self::new();
+        cnf.extend(clauses);
+        cnf
+    }
+}
+
+impl From<Cnf> for Vec<OwnedClause> {
+    fn from(cnf: Cnf) -> Self {
+        cnf.clauses
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    Var,
+    InvalidLitVal,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io,
+};
+
+/// A kind of a DIMACS parse error.
+#[derive(Debug)]
+pub enum ParseErrorKind {
+    /// Reading from the underlying reader failed.
+    Io(io::Error),
+    /// Encountered a character that is not allowed at its position.
+    UnexpectedChar(char),
+    /// The header line is malformed.
+    InvalidHeader,
+    /// Encountered a second header or a header after clauses.
+    UnexpectedHeader,
+    /// The input has no header.
+    MissingHeader,
+    /// A literal has an invalid value.
+    InvalidLit(InvalidLitVal),
+    /// A variable is greater than declared by the header.
+    VarOutOfRange {
+        /// The offending variable.
+        var: Var,
+        /// The declared number of variables.
+        num_vars: usize,
+    },
+    /// The number of clauses does not match the header.
+    ClauseCountMismatch {
+        /// The declared number of clauses.
+        expected: usize,
+        /// The number of clauses found so far.
+        found: usize,
+    },
+    /// The last clause is not terminated by `0`.
+    UnterminatedClause,
+    /// The input ended unexpectedly.
+    UnexpectedEof,
+    /// An error specific to a format other than DIMACS CNF.
+    ///
+    /// Each format defines its own error kind, see `format_kind`.
+    Format(Box<dyn Error + Send + Sync>),
+}
+
+impl ParseErrorKind {
+    /// Returns the format specific error kind if it has the given type.
+    pub fn format_kind<K>(&self) -> Option<&K>
+    where
+        K: Error + 'static,
+    {
+        match self {
+            ParseErrorKind::Format(kind) => kind.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::Io(err) => err.fmt(f),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::InvalidHeader => write!(f, "invalid header"),
+            ParseErrorKind::UnexpectedHeader => write!(f, "unexpected header"),
+            ParseErrorKind::MissingHeader => write!(f, "missing header"),
+            ParseErrorKind::InvalidLit(err) => err.fmt(f),
+            ParseErrorKind::VarOutOfRange { var, num_vars } => write!(
+                f, "variable {} exceeds the declared {} variables", var.to_raw(), num_vars
+            ),
+            ParseErrorKind::ClauseCountMismatch { expected, found } => write!(
+                f, "found {} clauses but {} are declared", found, expected
+            ),
+            ParseErrorKind::UnterminatedClause => write!(f, "last clause is not terminated by 0"),
+            ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseErrorKind::Format(kind) => kind.fmt(f),
+        }
+    }
+}
+
+/// An error encountered while parsing DIMACS input.
+#[derive(Debug)]
+pub struct ParseError {
+    /// The one-based line of the error.
+    line: usize,
+    /// The one-based column of the error.
+    column: usize,
+    /// The kind of the error.
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Creates a new parse error at the given position.
+    pub(crate) fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// Returns the one-based line of the error.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the one-based column of the error.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::Io(err) => Some(err),
+            ParseErrorKind::InvalidLit(err) => Some(err),
+            ParseErrorKind::Format(kind) => Some(&**kind),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use super::{
+    ParseError,
+    ParseErrorKind,
+};
+use std::{
+    io::{
+        self,
+        BufRead,
+        BufReader,
+        Read,
+    },
+    vec::Vec,
+};
+
+/// Type alias that has a `ParseError` as error variant.
+pub(crate) type Result<T> = core::result::Result<T, ParseError>;
+
+/// A byte-wise lexer over buffered input that tracks positions.
+///
+/// Shared by the parsers of all DIMACS-like formats.
+pub(crate) struct Lexer<R> {
+    /// The buffered input.
+    reader: BufReader<R>,
+    /// The one-based line of the next character.
+    line: usize,
+    /// The one-based column of the next character.
+    column: usize,
+    /// `true` if the current line has no tokens so far.
+    line_start: bool,
+}
+
+impl<R> Lexer<R>
+where
+    R: Read,
+{
+    /// Creates a new lexer over the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            line: 1,
+            column: 1,
+            line_start: true,
+        }
+    }
+
+    /// Returns the position of the next character.
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// Returns an error of the given kind at the given position.
+    pub fn error_at<T, K>(&self, (line, column): (usize, usize), kind: K) -> Result<T>
+    where
+        K: Into<ParseErrorKind>,
+    {
+        Err(ParseError::new(line, column, kind.into()))
+    }
+
+    /// Returns an error of the given kind at the current position.
+    pub fn error<T, K>(&self, kind: K) -> Result<T>
+    where
+        K: Into<ParseErrorKind>,
+    {
+        self.error_at(self.position(), kind)
+    }
+
+    /// Returns `true` if no token has been read on the current line
+    /// and marks the current line as having tokens.
+    pub fn take_line_start(&mut self) -> bool {
+        let line_start = self.line_start;
+        self.line_start = false;
+        line_start
+    }
+
+    /// Returns the buffered input without consuming it.
+    ///
+    /// The buffer is only empty at the end of input.
+    pub fn peek_buf(&mut self) -> Result<&[u8]> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(_) => return Ok(self.reader.buffer()),
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return self.error(ParseErrorKind::Io(err)),
+            }
+        }
+    }
+
+    /// Returns the underlying buffered reader for binary input.
+    pub fn into_inner(self) -> BufReader<R> {
+        self.reader
+    }
+
+    /// Returns the next byte without consuming it.
+    pub fn peek(&mut self) -> Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().cloned()),
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return self.error(ParseErrorKind::Io(err)),
+            }
+        }
+    }
+
+    /// Consumes the next byte which must have been peeked before.
+    pub fn bump(&mut self, byte: u8) {
+        self.reader.consume(1);
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+            self.line_start = true;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    /// Skips all whitespace including line breaks.
+    pub fn skip_whitespace(&mut self) -> Result<()> {
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_whitespace() {
+                break
+            }
+            self.bump(byte);
+        }
+        Ok(())
+    }
+
+    /// Skips all whitespace except line breaks.
+    pub fn skip_blanks(&mut self) -> Result<()> {
+        while let Some(byte) = self.peek()? {
+            if byte == b'\n' || !byte.is_ascii_whitespace() {
+                break
+            }
+            self.bump(byte);
+        }
+        Ok(())
+    }
+
+    /// Skips the rest of the current line including the line break.
+    pub fn skip_line(&mut self) -> Result<()> {
+        while let Some(byte) = self.peek()? {
+            self.bump(byte);
+            if byte == b'\n' {
+                break
+            }
+        }
+        Ok(())
+    }
+
+    /// Skips all input.
+    pub fn skip_all(&mut self) -> Result<()> {
+        while self.peek()?.is_some() {
+            self.skip_line()?;
+        }
+        Ok(())
+    }
+
+    /// Returns an error for the next character or the end of input.
+    ///
+    /// The end of input is reported with the given error kind.
+    pub fn unexpected<T, K>(&mut self, eof: K) -> Result<T>
+    where
+        K: Into<ParseErrorKind>,
+    {
+        match self.peek()? {
+            Some(byte) => self.error(ParseErrorKind::UnexpectedChar(char::from(byte))),
+            None => self.error(eof),
+        }
+    }
+
+    /// Checks that only blanks are left on the current line.
+    ///
+    /// Returns an error of the given kind at the given position otherwise.
+    pub fn expect_line_end(&mut self, start: (usize, usize), kind: ParseErrorKind) -> Result<()> {
+        self.skip_blanks()?;
+        match self.peek()? {
+            None | Some(b'\n') => Ok(()),
+            Some(_) => self.error_at(start, kind),
+        }
+    }
+
+    /// Checks that only blanks are left on the current line.
+    ///
+    /// Returns an error for the first unexpected character otherwise.
+    pub fn finish_line(&mut self) -> Result<()> {
+        self.skip_blanks()?;
+        match self.peek()? {
+            None | Some(b'\n') => Ok(()),
+            Some(_) => self.unexpected(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Reads a word of ASCII letters.
+    pub fn read_word(&mut self) -> Result<Vec<u8>> {
+        let mut word = Vec::new();
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_alphabetic() {
+                break
+            }
+            self.bump(byte);
+            word.push(byte);
+        }
+        Ok(word)
+    }
+
+    /// Reads the rest of the current line without the line break and trailing blanks.
+    pub fn read_rest_of_line(&mut self) -> Result<Vec<u8>> {
+        let mut text = Vec::new();
+        while let Some(byte) = self.peek()? {
+            if byte == b'\n' {
+                break
+            }
+            self.bump(byte);
+            text.push(byte);
+        }
+        while text.last().map(u8::is_ascii_whitespace).unwrap_or(false) {
+            text.pop();
+        }
+        Ok(text)
+    }
+
+    /// Reads an optionally negative decimal integer that must be
+    /// followed by whitespace or the end of input.
+    ///
+    /// Returns `None` if the next character does not start an integer.
+    /// Values exceeding the range of `i128` saturate.
+    pub fn read_int(&mut self) -> Result<Option<i128>> {
+        let negative = self.peek()? == Some(b'-');
+        if negative {
+            self.bump(b'-');
+        }
+        let mut value: Option<i128> = None;
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_digit() {
+                break
+            }
+            self.bump(byte);
+            let digit = i128::from(byte - b'0');
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+        match (value, self.peek()?) {
+            (None, _) if !negative => Ok(None),
+            (Some(value), None) => Ok(Some(if negative { -value } else { value })),
+            (Some(value), Some(byte)) if byte.is_ascii_whitespace() => {
+                Ok(Some(if negative { -value } else { value }))
+            }
+            _ => self.unexpected(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Reads a non-negative integer that fits into `usize`.
+    ///
+    /// Returns `None` if there is no such integer.
+    pub fn read_usize(&mut self) -> Result<Option<usize>> {
+        match self.read_int()? {
+            Some(value) if (0..=usize::MAX as i128).contains(&value) => Ok(Some(value as usize)),
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Reading and writing the DIMACS CNF format.
+//!
+//! The format consists of an optional number of comment lines starting with `c`,
+//! a header line `p cnf <num_vars> <num_clauses>` and the clauses as sequences of
+//! non-zero literals terminated by `0`.
+//! Clauses may span multiple lines or share a line.
+//! The parser optionally accepts XOR constraints on lines starting with `x`.
+
+mod error;
+mod lexer;
+mod parser;
+mod writer;
+
+pub(crate) use self::lexer::Lexer;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::error::{
+    ParseError,
+    ParseErrorKind,
+};
+pub use self::parser::{
+    Header,
+    Mode,
+    Parser,
+    load_dimacs,
+    read_cnf,
+};
+pub use self::writer::{
+    write_clause,
+    write_cnf,
+    write_header,
+};
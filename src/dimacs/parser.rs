@@ -0,0 +1,403 @@
+use super::{
+    lexer::{Lexer, Result},
+    ParseError,
+    ParseErrorKind,
+};
+use crate::{
+    Var,
+    Lit,
+    Clause,
+    OwnedClause,
+    Cnf,
+    IpasirSolver,
+    VarManager,
+    encodings::xor::XorConstraint,
+};
+use core::convert::TryFrom;
+use std::{
+    io::Read,
+    vec::Vec,
+};
+
+/// The header of a DIMACS CNF file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// The declared number of variables.
+    pub num_vars: usize,
+    /// The declared number of clauses.
+    pub num_clauses: usize,
+}
+
+/// How strictly the input is checked against the DIMACS specification.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Requires a header before all clauses whose counts match the clauses,
+    /// and requires the last clause to be terminated by `0`.
+    #[default]
+    Strict,
+    /// Allows a missing header, mismatching counts, an unterminated last clause
+    /// and ends the input at a line starting with `%` as found in some
+    /// benchmark archives.
+    Lenient,
+}
+
+/// A streaming parser for DIMACS CNF input.
+///
+/// Yields the clauses one at a time as views into an internal buffer
+/// without materializing the whole formula.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::dimacs::Parser;
+///
+/// let input = "p cnf 2 2\n1 -2 0\n2 0\n";
+/// let mut parser = Parser::new(input.as_bytes());
+/// while let Some(clause) = parser.next_clause() {
+///     assert!(clause.unwrap().len() <= 2);
+/// }
+/// assert_eq!(parser.header().unwrap().num_clauses, 2);
+/// ```
+///
+/// # XOR constraints
+///
+/// With `with_xors` the parser accepts lines `x <lits> 0` that constrain
+/// the XOR of their literals to be `true` as used by CryptoMiniSat.
+/// They count as clauses against the header and are translated into clauses
+/// over fresh variables after the last clause of the input.
+pub struct Parser<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+    /// How strictly the input is checked.
+    mode: Mode,
+    /// The header if already parsed.
+    header: Option<Header>,
+    /// The literals of the current clause.
+    lits: Vec<Lit>,
+    /// The number of clauses parsed so far.
+    num_clauses: usize,
+    /// `true` if the input is exhausted or an error occured.
+    done: bool,
+    /// The cutting length of XOR constraints if they are accepted.
+    xor_cutting_length: Option<usize>,
+    /// The XOR constraints that have not been translated yet.
+    xors: Vec<XorConstraint>,
+    /// The translated clauses of all XOR constraints in reverse order.
+    translated: Vec<OwnedClause>,
+    /// The variables of the input and of translated XOR constraints.
+    vars: VarManager,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Creates a new strict parser over the given reader.
+    pub fn new(reader: R) -> Self {
+        Self::with_mode(reader, Mode::default())
+    }
+
+    /// Creates a new parser over the given reader with the given mode.
+    pub fn with_mode(reader: R, mode: Mode) -> Self {
+        Self {
+            lexer: Lexer::new(reader),
+            mode,
+            header: None,
+            lits: Vec::new(),
+            num_clauses: 0,
+            done: false,
+            xor_cutting_length: None,
+            xors: Vec::new(),
+            translated: Vec::new(),
+            vars: VarManager::new(),
+        }
+    }
+
+    /// Makes the parser accept XOR constraints and translate them
+    /// with the given cutting length.
+    ///
+    /// See `encodings::xor::XorConstraint::encode` for the translation.
+    ///
+    /// # Panics
+    ///
+    /// If the cutting length is less than `3` or greater than `20`.
+    pub fn with_xors(mut self, cutting_length: usize) -> Self {
+        assert!((3..=20).contains(&cutting_length), "the cutting length must be between 3 and 20");
+        self.xor_cutting_length = Some(cutting_length);
+        self
+    }
+
+    /// Returns the cutting length of XOR constraints if they are accepted.
+    pub fn xor_cutting_length(&self) -> Option<usize> {
+        self.xor_cutting_length
+    }
+
+    /// Returns the variable manager of all variables in use.
+    ///
+    /// These are the declared variables, the variables of all clauses and XOR
+    /// constraints parsed so far, and the fresh variables of translated XOR constraints.
+    pub fn vars(&self) -> VarManager {
+        self.vars
+    }
+
+    /// Returns the mode of the parser.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns the header if it has been parsed already.
+    pub fn header(&self) -> Option<Header> {
+        self.header
+    }
+
+    /// Returns the number of clauses parsed so far.
+    pub fn num_clauses(&self) -> usize {
+        self.num_clauses
+    }
+
+    /// Parses the header line after its leading `p`.
+    fn parse_header(&mut self, start: (usize, usize)) -> Result<()> {
+        if self.header.is_some() || self.num_clauses > 0 || !self.lits.is_empty() {
+            return self.lexer.error_at(start, ParseErrorKind::UnexpectedHeader)
+        }
+        self.lexer.skip_blanks()?;
+        if self.lexer.read_word()? != b"cnf" {
+            return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+        }
+        let mut counts = [0; 2];
+        for count in &mut counts {
+            self.lexer.skip_blanks()?;
+            match self.lexer.read_usize()? {
+                Some(value) => *count = value,
+                None => return self.lexer.error_at(start, ParseErrorKind::InvalidHeader),
+            }
+        }
+        self.lexer.expect_line_end(start, ParseErrorKind::InvalidHeader)?;
+        if let Ok(max_var) = Var::try_from(counts[0]) {
+            self.vars.reserve(max_var);
+        }
+        self.header = Some(Header {
+            num_vars: counts[0],
+            num_clauses: counts[1],
+        });
+        Ok(())
+    }
+
+    /// Checks the literal read at the given position against the header.
+    fn check_lit(&mut self, value: i128, start: (usize, usize)) -> Result<Lit> {
+        let lit = match Lit::try_from_wide(value) {
+            Ok(lit) => lit,
+            Err(err) => return self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+        };
+        if self.mode == Mode::Strict {
+            match self.header {
+                None => return self.lexer.error_at(start, ParseErrorKind::MissingHeader),
+                Some(header) if lit.var().to_raw() as usize > header.num_vars => {
+                    let kind = ParseErrorKind::VarOutOfRange {
+                        var: lit.var(),
+                        num_vars: header.num_vars,
+                    };
+                    return self.lexer.error_at(start, kind)
+                }
+                _ => (),
+            }
+        }
+        self.vars.reserve(lit.var());
+        Ok(lit)
+    }
+
+    /// Counts a completed clause starting at the given position.
+    fn finish_clause(&mut self, start: (usize, usize)) -> Result<()> {
+        self.num_clauses += 1;
+        if let (Mode::Strict, Some(header)) = (self.mode, self.header) {
+            if self.num_clauses > header.num_clauses {
+                let kind = ParseErrorKind::ClauseCountMismatch {
+                    expected: header.num_clauses,
+                    found: self.num_clauses,
+                };
+                return self.lexer.error_at(start, kind)
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the end of the input.
+    ///
+    /// Returns `true` if an unterminated last clause has been accepted.
+    fn finish_input(&mut self) -> Result<bool> {
+        self.done = true;
+        let unterminated = !self.lits.is_empty();
+        if unterminated && self.mode == Mode::Strict {
+            return self.lexer.error(ParseErrorKind::UnterminatedClause)
+        }
+        if unterminated {
+            self.num_clauses += 1;
+        }
+        if self.mode == Mode::Strict {
+            match self.header {
+                None => return self.lexer.error(ParseErrorKind::MissingHeader),
+                Some(header) if header.num_clauses != self.num_clauses => {
+                    let kind = ParseErrorKind::ClauseCountMismatch {
+                        expected: header.num_clauses,
+                        found: self.num_clauses,
+                    };
+                    return self.lexer.error(kind)
+                }
+                _ => (),
+            }
+        }
+        Ok(unterminated)
+    }
+
+    /// Parses an XOR constraint after its leading `x`.
+    fn parse_xor(&mut self, start: (usize, usize)) -> Result<()> {
+        let mut lits = Vec::new();
+        loop {
+            self.lexer.skip_whitespace()?;
+            let lit_start = self.lexer.position();
+            match self.lexer.read_int()? {
+                Some(0) => break,
+                Some(value) => lits.push(self.check_lit(value, lit_start)?),
+                None => return self.lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+        self.finish_clause(start)?;
+        self.xors.push(XorConstraint::new(lits, true));
+        Ok(())
+    }
+
+    /// Translates all pending XOR constraints.
+    fn translate_xors(&mut self) {
+        let cutting_length = match self.xor_cutting_length {
+            Some(cutting_length) if !self.xors.is_empty() => cutting_length,
+            _ => return,
+        };
+        for xor in core::mem::take(&mut self.xors) {
+            self.translated.extend(Vec::from(xor.to_cnf(&mut self.vars, cutting_length)));
+        }
+        self.translated.reverse();
+    }
+
+    /// Parses the next clause into the literal buffer.
+    ///
+    /// Returns `false` if there are no more clauses.
+    fn parse_clause(&mut self) -> Result<bool> {
+        self.lits.clear();
+        let mut clause_start = self.lexer.position();
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            let byte = match self.lexer.peek()? {
+                Some(byte) => byte,
+                None => return self.finish_input(),
+            };
+            if self.lexer.take_line_start() {
+                match byte {
+                    b'c' => {
+                        self.lexer.skip_line()?;
+                        continue
+                    }
+                    b'p' => {
+                        self.lexer.bump(byte);
+                        self.parse_header(start)?;
+                        continue
+                    }
+                    b'%' if self.mode == Mode::Lenient => {
+                        self.lexer.skip_all()?;
+                        return self.finish_input()
+                    }
+                    b'x' if self.xor_cutting_length.is_some() && self.lits.is_empty() => {
+                        self.lexer.bump(byte);
+                        self.parse_xor(start)?;
+                        continue
+                    }
+                    _ => (),
+                }
+            }
+            if self.lits.is_empty() {
+                clause_start = start;
+            }
+            match self.lexer.read_int()? {
+                Some(0) => {
+                    self.finish_clause(clause_start)?;
+                    return Ok(true)
+                }
+                Some(value) => {
+                    let lit = self.check_lit(value, start)?;
+                    self.lits.push(lit);
+                }
+                None => return self.lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+    }
+
+    /// Returns the next clause of the input.
+    ///
+    /// Returns `None` after the last clause or after an error.
+    /// The clauses of translated XOR constraints follow the last clause.
+    ///
+    /// # Note
+    ///
+    /// The returned clause borrows the internal buffer of the parser
+    /// and is only valid until the next call.
+    pub fn next_clause(&mut self) -> Option<Result<Clause<'_>>> {
+        if !self.done {
+            match self.parse_clause() {
+                Ok(true) => return Some(Ok(Clause::from(self.lits.as_slice()))),
+                Ok(false) => (),
+                Err(err) => {
+                    self.done = true;
+                    self.xors.clear();
+                    return Some(Err(err))
+                }
+            }
+        }
+        self.translate_xors();
+        let clause = self.translated.pop()?;
+        self.lits.clear();
+        self.lits.extend(clause.iter());
+        Some(Ok(Clause::from(self.lits.as_slice())))
+    }
+
+    /// Adds all remaining clauses of the input to the solver.
+    ///
+    /// Returns the header of the input if any.
+    pub fn load_into<S>(mut self, solver: &mut S) -> Result<Option<Header>>
+    where
+        S: IpasirSolver,
+    {
+        while let Some(clause) = self.next_clause() {
+            solver.add_clause(clause?.iter());
+        }
+        Ok(self.header)
+    }
+
+    /// Collects all remaining clauses of the input into a formula.
+    pub fn into_cnf(mut self) -> Result<Cnf> {
+        let mut cnf = Cnf::new();
+        while let Some(clause) = self.next_clause() {
+            cnf.push(clause?.into());
+        }
+        Ok(cnf)
+    }
+}
+
+/// Strictly parses DIMACS CNF input and adds all its clauses to the solver.
+///
+/// Returns the header of the input.
+pub fn load_dimacs<S, R>(solver: &mut S, reader: R) -> core::result::Result<Header, ParseError>
+where
+    S: IpasirSolver,
+    R: Read,
+{
+    let header = Parser::new(reader).load_into(solver)?;
+    Ok(header.expect("strict parsing requires a header"))
+}
+
+/// Strictly parses DIMACS CNF input into a formula.
+pub fn read_cnf<R>(reader: R) -> core::result::Result<Cnf, ParseError>
+where
+    R: Read,
+{
+    Parser::new(reader).into_cnf()
+}
@@ -0,0 +1,131 @@
+use super::*;
+use crate::{
+    Cnf,
+    IpasirSolver,
+    record::Recording,
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Parses all clauses of the input with the given mode.
+fn parse(input: &str, mode: Mode) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut parser = Parser::with_mode(input.as_bytes(), mode);
+    let mut clauses = Vec::new();
+    while let Some(clause) = parser.next_clause() {
+        clauses.push(clause?.iter().map(|lit| lit.to_raw()).collect());
+    }
+    Ok(clauses)
+}
+
+/// Returns the position and message of the strict parse error of the input.
+fn strict_error(input: &str) -> (usize, usize, String) {
+    let err = parse(input, Mode::Strict).unwrap_err();
+    (err.line(), err.column(), err.kind().to_string())
+}
+
+#[test]
+fn parse_clauses_across_lines() {
+    let input = "c a comment\np cnf 3 3\n1 -2\n 3 0 -1 0\nc another comment\n2 0\n";
+    assert_eq!(
+        parse(input, Mode::Strict).unwrap(),
+        vec![vec![1, -2, 3], vec![-1], vec![2]]
+    );
+}
+
+#[test]
+fn strict_errors_have_positions() {
+    assert_eq!(
+        strict_error("p cnf 2 1\n1 3 0\n"),
+        (2, 3, "variable 3 exceeds the declared 2 variables".to_string())
+    );
+    assert_eq!(
+        strict_error("p cnf 2 2\n1 2 0\n"),
+        (3, 1, "found 1 clauses but 2 are declared".to_string())
+    );
+    assert_eq!(
+        strict_error("p cnf 2 1\n1 2 0\n-1 0\n"),
+        (3, 1, "found 2 clauses but 1 are declared".to_string())
+    );
+    assert_eq!(strict_error("1 2 0\n"), (1, 1, "missing header".to_string()));
+    assert_eq!(strict_error("p cnf 2 1\n1 x 0\n"), (2, 3, "unexpected character 'x'".to_string()));
+    assert_eq!(strict_error("p cnf 2 1\n1 2"), (2, 4, "last clause is not terminated by 0".to_string()));
+    assert_eq!(strict_error("p dnf 2 1\n"), (1, 1, "invalid header".to_string()));
+}
+
+#[test]
+fn lenient_mode_accepts_sloppy_input() {
+    assert_eq!(
+        parse("p cnf 1 5\n1 2 0\n-3 4", Mode::Lenient).unwrap(),
+        vec![vec![1, 2], vec![-3, 4]]
+    );
+    assert_eq!(
+        parse("1 0\n%\n0\n", Mode::Lenient).unwrap(),
+        vec![vec![1]]
+    );
+}
+
+#[test]
+fn translate_xor_lines() {
+    use crate::{encodings::xor::XorConstraint, Model};
+    let input = "p cnf 5 3\nx1 -2 3 4 5 0\n-1 0\nx 2 -3 0\n";
+    assert_eq!(strict_error(input), (2, 1, "unexpected character 'x'".to_string()));
+    let mut parser = Parser::new(input.as_bytes()).with_xors(3);
+    let mut solver = TestSolver::init();
+    while let Some(clause) = parser.next_clause() {
+        solver.add_clause(clause.unwrap().iter());
+    }
+    assert_eq!(parser.num_clauses(), 3);
+    assert_eq!(parser.vars().num_vars(), 7);
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    let model = Model::from_solver(&mut solver, lit(5).var()).unwrap();
+    assert!(XorConstraint::new(vec![lit(1), lit(-2), lit(3), lit(4), lit(5)], true).is_satisfied(&model));
+    assert!(XorConstraint::new(vec![lit(2), lit(-3)], true).is_satisfied(&model));
+}
+
+#[test]
+fn load_into_solver() {
+    let mut solver = TestSolver::init();
+    let header = load_dimacs(&mut solver, "p cnf 2 2\n1 2 0\n-1 0\n".as_bytes()).unwrap();
+    assert_eq!(header, Header { num_vars: 2, num_clauses: 2 });
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    assert_eq!(solver.val(lit(2)), Ok(crate::LitValue::True));
+}
+
+#[test]
+fn read_into_cnf() {
+    let cnf = read_cnf("p cnf 4 1\n4 -1 0\n".as_bytes()).unwrap();
+    let mut expected = Cnf::new();
+    expected.add_clause(vec![lit(4), lit(-1)]);
+    assert_eq!(cnf, expected);
+    assert_eq!(cnf.num_vars(), 4);
+}
+
+#[test]
+fn write_and_read_back() {
+    let mut cnf = Cnf::new();
+    cnf.add_clause(vec![lit(1), lit(-3)]);
+    cnf.add_clause(vec![lit(2)]);
+    let mut output = Vec::new();
+    write_cnf(&mut output, &cnf).unwrap();
+    assert_eq!(output, b"p cnf 3 2\n1 -3 0\n2 0\n");
+    assert_eq!(read_cnf(output.as_slice()).unwrap(), cnf);
+}
+
+#[test]
+fn record_solver_input() {
+    let mut solver = Recording::<TestSolver>::init();
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.assume(lit(-1));
+    solver.assume(lit(4));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    let mut output = Vec::new();
+    solver.write_dimacs(&mut output, false).unwrap();
+    assert_eq!(output, b"p cnf 2 1\n1 2 0\n");
+    output.clear();
+    solver.write_dimacs(&mut output, true).unwrap();
+    assert_eq!(output, b"p cnf 4 3\n1 2 0\n-1 0\n4 0\n");
+}
@@ -0,0 +1,45 @@
+use super::Header;
+use crate::{
+    Clause,
+    Cnf,
+};
+use std::io::{
+    self,
+    Write,
+};
+
+/// Writes the DIMACS CNF header line.
+pub fn write_header<W>(writer: &mut W, header: Header) -> io::Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "p cnf {} {}", header.num_vars, header.num_clauses)
+}
+
+/// Writes the clause as a DIMACS CNF clause line terminated by `0`.
+pub fn write_clause<W>(writer: &mut W, clause: Clause) -> io::Result<()>
+where
+    W: Write,
+{
+    for lit in clause.iter() {
+        write!(writer, "{} ", lit.to_raw())?;
+    }
+    writeln!(writer, "0")
+}
+
+/// Writes the formula in DIMACS CNF format.
+///
+/// The number of variables of the header is the greatest variable of the formula.
+pub fn write_cnf<W>(writer: &mut W, cnf: &Cnf) -> io::Result<()>
+where
+    W: Write,
+{
+    write_header(writer, Header {
+        num_vars: cnf.num_vars(),
+        num_clauses: cnf.len(),
+    })?;
+    for clause in cnf.clauses() {
+        write_clause(writer, clause)?;
+    }
+    Ok(())
+}
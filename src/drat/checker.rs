@@ -0,0 +1,569 @@
+use super::{
+    Parser,
+    Step,
+};
+use crate::{
+    dimacs::{
+        read_cnf,
+        ParseError,
+    },
+    Lit,
+    OwnedClause,
+    Cnf,
+    LitValue,
+    VarMap,
+    LitMap,
+    LitSet,
+    lrat,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::Read,
+    vec::Vec,
+};
+
+/// An error encountered while checking a DRAT proof.
+#[derive(Debug)]
+pub enum CheckError {
+    /// The DIMACS CNF formula is malformed.
+    Cnf(ParseError),
+    /// The proof is malformed.
+    Proof(ParseError),
+    /// A lemma needed for the refutation is neither RUP nor RAT.
+    Rejected {
+        /// The one-based number of the proof step adding the lemma.
+        step: usize,
+        /// The rejected lemma.
+        lemma: OwnedClause,
+    },
+    /// Unit propagation on the formula and all lemmas does not yield a conflict.
+    NoConflict,
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::Cnf(err) => write!(f, "formula: {}", err),
+            CheckError::Proof(err) => write!(f, "proof: {}", err),
+            CheckError::Rejected { step, lemma } => {
+                write!(f, "lemma [")?;
+                for (n, lit) in lemma.iter().enumerate() {
+                    if n > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", lit.to_raw())?;
+                }
+                write!(f, "] of proof step {} is neither RUP nor RAT", step)
+            }
+            CheckError::NoConflict => write!(f, "proof does not derive a conflict"),
+        }
+    }
+}
+
+impl Error for CheckError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckError::Cnf(err) | CheckError::Proof(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Statistics of a successfully checked proof.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The number of lemmas up to the empty clause.
+    pub lemmas: usize,
+    /// The number of lemmas needed to derive the empty clause which have been checked.
+    pub core_lemmas: usize,
+    /// The number of ignored deletions of unit clauses or of clauses that are not active.
+    pub ignored_deletions: usize,
+}
+
+/// A recorded proof step.
+#[derive(Debug, Copy, Clone)]
+enum Record {
+    /// The lemma with the given identifier was added by the proof step with the given number.
+    Add(usize, usize),
+    /// The clause with the given identifier was deleted.
+    Delete(usize),
+}
+
+/// The justification of a lemma by unit propagation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Justification {
+    /// The clauses propagating to a conflict under the negated lemma in order.
+    ///
+    /// Empty if the lemma is justified by RAT.
+    rup: Vec<usize>,
+    /// The RAT candidates containing the negated pivot and the clauses
+    /// propagating to a conflict under the negated resolvent.
+    rat: Vec<(usize, Vec<usize>)>,
+}
+
+impl Justification {
+    /// Returns the LRAT hints of the justification.
+    fn lrat_hints(&self) -> Vec<i64> {
+        let id = |id: &usize| *id as i64 + 1;
+        let mut hints: Vec<i64> = self.rup.iter().map(id).collect();
+        for (candidate, candidate_hints) in &self.rat {
+            hints.push(-id(candidate));
+            hints.extend(candidate_hints.iter().map(id));
+        }
+        hints
+    }
+}
+
+/// A backward DRAT checker over a formula.
+///
+/// Clauses are identified by their position in the formula followed by the lemmas.
+///
+/// # Note
+///
+/// - Like `drat-trim` the checker ignores deletions of unit clauses.
+/// - All steps after the first empty lemma are ignored. Without an empty lemma
+///   unit propagation on the final clauses must yield a conflict.
+/// - Every check propagates from scratch, so checking is quadratic in the worst case.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::{Cnf, Lit, drat::{Checker, Step}};
+/// use std::convert::TryFrom;
+///
+/// let lit = |val: i32| Lit::try_from(val).unwrap();
+/// let mut cnf = Cnf::new();
+/// for clause in [[1, 2], [1, -2], [-1, 2], [-1, -2]].iter() {
+///     cnf.add_clause(clause.iter().map(|&val| lit(val)));
+/// }
+/// let mut checker = Checker::new(&cnf);
+/// checker.add_step(Step::Add(vec![lit(1)].into()));
+/// checker.add_step(Step::Add(Vec::<Lit>::new().into()));
+/// assert_eq!(checker.check().unwrap().core_lemmas, 2);
+/// ```
+pub struct Checker {
+    /// The literals of all clauses with the watched literals first.
+    clauses: Vec<Vec<Lit>>,
+    /// The literals of every lemma in the order of the proof with the pivot first.
+    lemmas: Vec<Vec<Lit>>,
+    /// `true` for the clauses active at the current point of the proof.
+    active: Vec<bool>,
+    /// `true` for the clauses needed to derive the empty clause.
+    core: Vec<bool>,
+    /// The number of formula clauses.
+    num_formula: usize,
+    /// The clauses watching each literal.
+    watches: LitMap<Vec<usize>>,
+    /// All unit clauses.
+    units: Vec<usize>,
+    /// All empty clauses.
+    empty: Vec<usize>,
+    /// The active clauses by their sorted literals.
+    index: HashMap<Vec<Lit>, Vec<usize>>,
+    /// The recorded proof steps.
+    records: Vec<Record>,
+    /// The number of proof steps added so far.
+    num_steps: usize,
+    /// `true` if the empty clause has been added.
+    refuted: bool,
+    /// The number of ignored deletions.
+    ignored_deletions: usize,
+    /// Scratch set to remove duplicate literals.
+    scratch: LitSet,
+    /// The current assignment.
+    values: VarMap<LitValue>,
+    /// The clauses that propagated the assigned variables.
+    reasons: VarMap<Option<usize>>,
+    /// The assigned literals in order.
+    trail: Vec<Lit>,
+    /// Marks used by conflict analysis.
+    seen: VarMap<bool>,
+}
+
+impl Checker {
+    /// Creates a new checker over the clauses of the formula.
+    pub fn new(cnf: &Cnf) -> Self {
+        let mut checker = Self {
+            clauses: Vec::new(),
+            lemmas: Vec::new(),
+            active: Vec::new(),
+            core: Vec::new(),
+            num_formula: cnf.len(),
+            watches: LitMap::new(),
+            units: Vec::new(),
+            empty: Vec::new(),
+            index: HashMap::new(),
+            records: Vec::new(),
+            num_steps: 0,
+            refuted: false,
+            ignored_deletions: 0,
+            scratch: LitSet::new(),
+            values: VarMap::new(),
+            reasons: VarMap::new(),
+            trail: Vec::new(),
+            seen: VarMap::new(),
+        };
+        for clause in cnf.clauses() {
+            checker.insert(clause.iter());
+        }
+        checker
+    }
+
+    /// Adds the clause without duplicate literals and returns its identifier.
+    fn insert<I>(&mut self, lits: I) -> usize
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        self.scratch.clear();
+        let lits: Vec<Lit> = lits.into_iter().filter(|&lit| self.scratch.insert(lit)).collect();
+        let mut key = lits.clone();
+        key.sort();
+        let id = self.clauses.len();
+        if id >= self.num_formula {
+            self.lemmas.push(lits.clone());
+        }
+        self.clauses.push(lits);
+        self.active.push(true);
+        self.core.push(false);
+        self.index.entry(key).or_default().push(id);
+        self.attach(id);
+        id
+    }
+
+    /// Registers the clause for propagation.
+    fn attach(&mut self, id: usize) {
+        match *self.clauses[id].as_slice() {
+            [] => self.empty.push(id),
+            [_] => self.units.push(id),
+            [first, second, ..] => {
+                self.watches[first].push(id);
+                self.watches[second].push(id);
+            }
+        }
+    }
+
+    /// Adds the next step of the proof.
+    ///
+    /// Steps after the first empty lemma are ignored.
+    pub fn add_step(&mut self, step: Step) {
+        if self.refuted {
+            return
+        }
+        self.num_steps += 1;
+        match step {
+            Step::Add(lemma) => {
+                let id = self.insert(lemma.iter());
+                self.refuted = lemma.is_empty();
+                self.records.push(Record::Add(id, self.num_steps));
+            }
+            Step::Delete(clause) => {
+                let mut key: Vec<Lit> = clause.iter().collect();
+                key.sort();
+                key.dedup();
+                let id = match self.index.get_mut(&key) {
+                    Some(ids) if key.len() > 1 => ids.pop(),
+                    _ => None,
+                };
+                match id {
+                    Some(id) => {
+                        self.active[id] = false;
+                        self.records.push(Record::Delete(id));
+                    }
+                    None => self.ignored_deletions += 1,
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the literal under the current assignment.
+    fn value(&self, lit: Lit) -> LitValue {
+        lit.eval(self.values.get(lit.var()).cloned().unwrap_or_default())
+    }
+
+    /// Assigns the literal to be `true`.
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        self.values[lit.var()] = lit.eval(LitValue::True);
+        self.reasons[lit.var()] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Undoes all assignments.
+    fn reset(&mut self) {
+        for lit in self.trail.drain(..) {
+            self.values[lit.var()] = LitValue::DontCare;
+            self.reasons[lit.var()] = None;
+        }
+    }
+
+    /// Propagates all assignments on the trail and returns a conflicting clause if any.
+    fn propagate(&mut self) -> Option<usize> {
+        let mut head = 0;
+        while head < self.trail.len() {
+            let false_lit = !self.trail[head];
+            head += 1;
+            let mut watches = core::mem::take(&mut self.watches[false_lit]);
+            let mut kept = 0;
+            let mut conflict = None;
+            let mut n = 0;
+            while n < watches.len() {
+                let id = watches[n];
+                n += 1;
+                if conflict.is_some() {
+                    watches[kept] = id;
+                    kept += 1;
+                    continue
+                }
+                if !self.active[id] {
+                    continue
+                }
+                let values = &self.values;
+                let lits = &mut self.clauses[id];
+                if lits[0] == false_lit {
+                    lits.swap(0, 1);
+                }
+                if lits[1] != false_lit {
+                    // Stale watch of a clause that has been reactivated.
+                    continue
+                }
+                let first = lits[0];
+                let first_value = lit_value(values, first);
+                if first_value != LitValue::True {
+                    let replacement = (2..lits.len())
+                        .find(|&k| lit_value(values, lits[k]) != LitValue::False);
+                    if let Some(k) = replacement {
+                        lits.swap(1, k);
+                        let watched = lits[1];
+                        self.watches[watched].push(id);
+                        continue
+                    }
+                }
+                watches[kept] = id;
+                kept += 1;
+                match first_value {
+                    LitValue::True => (),
+                    LitValue::False => conflict = Some(id),
+                    LitValue::DontCare => self.assign(first, Some(id)),
+                }
+            }
+            watches.truncate(kept);
+            self.watches[false_lit] = watches;
+            if conflict.is_some() {
+                return conflict
+            }
+        }
+        None
+    }
+
+    /// Returns the clauses that propagated the assigned variables of the
+    /// conflicting clause in trail order followed by the conflicting clause.
+    fn analyze(&mut self, conflict: usize) -> Vec<usize> {
+        for lit in &self.clauses[conflict] {
+            self.seen[lit.var()] = true;
+        }
+        let mut hints = Vec::new();
+        for &lit in self.trail.iter().rev() {
+            let var = lit.var();
+            // Only the variables marked so far are covered by `seen`.
+            if !self.seen.get(var).copied().unwrap_or(false) {
+                continue
+            }
+            self.seen[var] = false;
+            if let Some(reason) = self.reasons.get(var).copied().flatten() {
+                hints.push(reason);
+                for other in &self.clauses[reason] {
+                    if other.var() != var {
+                        self.seen[other.var()] = true;
+                    }
+                }
+            }
+        }
+        hints.reverse();
+        hints.push(conflict);
+        hints
+    }
+
+    /// Propagates the assumed literals with all active clauses.
+    ///
+    /// Returns the clauses deriving a conflict in propagation order if any.
+    fn rup(&mut self, assumed: &[Lit]) -> Option<Vec<usize>> {
+        self.reset();
+        let hints = self.rup_from_scratch(assumed);
+        self.reset();
+        hints
+    }
+
+    /// Implements `rup` on an empty assignment.
+    fn rup_from_scratch(&mut self, assumed: &[Lit]) -> Option<Vec<usize>> {
+        if let Some(&id) = self.empty.iter().find(|&&id| self.active[id]) {
+            return Some(Vec::from([id]))
+        }
+        for &lit in assumed {
+            match self.value(lit) {
+                LitValue::True => (),
+                LitValue::False => return Some(Vec::new()),
+                LitValue::DontCare => self.assign(lit, None),
+            }
+        }
+        for n in 0..self.units.len() {
+            let id = self.units[n];
+            if !self.active[id] {
+                continue
+            }
+            let lit = self.clauses[id][0];
+            match self.value(lit) {
+                LitValue::True => (),
+                LitValue::False => return Some(self.analyze(id)),
+                LitValue::DontCare => self.assign(lit, Some(id)),
+            }
+        }
+        self.propagate().map(|conflict| self.analyze(conflict))
+    }
+
+    /// Returns the literals of the lemma in the order of the proof.
+    fn lemma(&self, id: usize) -> &[Lit] {
+        &self.lemmas[id - self.num_formula]
+    }
+
+    /// Checks the lemma against the active clauses.
+    ///
+    /// The lemma is RAT on its first literal in the proof if it is not RUP.
+    fn justify(&mut self, id: usize) -> Option<Justification> {
+        let lemma = self.lemma(id).to_vec();
+        let negated: Vec<Lit> = lemma.iter().map(|&lit| !lit).collect();
+        if let Some(rup) = self.rup(&negated) {
+            return Some(Justification { rup, rat: Vec::new() })
+        }
+        let pivot = *lemma.first()?;
+        let mut rat = Vec::new();
+        for candidate in 0..self.clauses.len() {
+            if !self.active[candidate] || !self.clauses[candidate].contains(&!pivot) {
+                continue
+            }
+            let mut assumed = negated.clone();
+            assumed.extend(self.clauses[candidate].iter().filter(|&&lit| lit != !pivot).map(|&lit| !lit));
+            let hints = self.rup(&assumed)?;
+            rat.push((candidate, hints));
+        }
+        Some(Justification { rup: Vec::new(), rat })
+    }
+
+    /// Marks all clauses used by the justification as needed.
+    fn mark_core(&mut self, justification: &Justification) {
+        let candidates = justification.rat.iter().map(|(candidate, _)| candidate);
+        let hints = justification.rat.iter().flat_map(|(_, hints)| hints);
+        for &id in justification.rup.iter().chain(candidates).chain(hints) {
+            self.core[id] = true;
+        }
+    }
+
+    /// Checks the proof calling the given function for every checked lemma in reverse order.
+    ///
+    /// Without an empty lemma the final conflict is visited with the next free identifier.
+    fn check_with<F>(mut self, mut visit: F) -> Result<Report, CheckError>
+    where
+        F: FnMut(&Self, usize, &Justification),
+    {
+        if !self.refuted {
+            let rup = self.rup(&[]).ok_or(CheckError::NoConflict)?;
+            let justification = Justification { rup, rat: Vec::new() };
+            self.mark_core(&justification);
+            visit(&self, self.clauses.len(), &justification);
+        }
+        if let (true, Some(Record::Add(id, _))) = (self.refuted, self.records.last()) {
+            self.core[*id] = true;
+        }
+        let mut report = Report {
+            lemmas: self.clauses.len() - self.num_formula,
+            core_lemmas: 0,
+            ignored_deletions: self.ignored_deletions,
+        };
+        for n in (0..self.records.len()).rev() {
+            match self.records[n] {
+                Record::Add(id, step) => {
+                    self.active[id] = false;
+                    if !self.core[id] {
+                        continue
+                    }
+                    let justification = match self.justify(id) {
+                        Some(justification) => justification,
+                        None => {
+                            let lemma = OwnedClause::from(self.lemma(id).to_vec());
+                            return Err(CheckError::Rejected { step, lemma })
+                        }
+                    };
+                    self.mark_core(&justification);
+                    report.core_lemmas += 1;
+                    visit(&self, id, &justification);
+                }
+                Record::Delete(id) => {
+                    self.active[id] = true;
+                    self.attach(id);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Checks the proof.
+    pub fn check(self) -> Result<Report, CheckError> {
+        self.check_with(|_, _, _| ())
+    }
+
+    /// Checks the proof and turns it into an LRAT proof.
+    ///
+    /// The formula clauses are numbered from `1` and every lemma is numbered by its
+    /// position after the formula clauses. Lemmas that are not needed to derive
+    /// the empty clause are dropped and deletions are kept for all other clauses.
+    /// Without an empty lemma the proof ends with the empty clause.
+    pub fn elaborate(self) -> Result<Vec<lrat::Step>, CheckError> {
+        let records = self.records.clone();
+        let num_formula = self.num_formula;
+        let mut lemmas = HashMap::new();
+        let mut in_proof: Vec<bool> = (0..=self.clauses.len()).map(|id| id < num_formula).collect();
+        let end = self.clauses.len();
+        self.check_with(|checker, id, justification| {
+            let lemma = checker.lemmas.get(id - num_formula).cloned().unwrap_or_default();
+            lemmas.insert(id, (lemma, justification.lrat_hints()));
+        })?;
+        let mut steps = Vec::new();
+        let mut last_id = num_formula as u64;
+        for record in records.into_iter().chain(Some(Record::Add(end, 0))) {
+            match record {
+                Record::Add(id, _) => {
+                    if let Some((lemma, hints)) = lemmas.remove(&id) {
+                        last_id = id as u64 + 1;
+                        in_proof[id] = true;
+                        steps.push(lrat::Step::Add { id: last_id, clause: OwnedClause::from(lemma), hints });
+                    }
+                }
+                Record::Delete(id) if in_proof[id] => {
+                    in_proof[id] = false;
+                    steps.push(lrat::Step::Delete { id: last_id, clauses: Vec::from([id as u64 + 1]) });
+                }
+                Record::Delete(_) => (),
+            }
+        }
+        Ok(steps)
+    }
+}
+
+/// Returns the value of the literal under the assignment.
+fn lit_value(values: &VarMap<LitValue>, lit: Lit) -> LitValue {
+    lit.eval(values.get(lit.var()).cloned().unwrap_or_default())
+}
+
+/// Checks a DRAT proof of either format against a strictly parsed DIMACS CNF formula.
+pub fn check_drat<C, P>(cnf: C, proof: P) -> Result<Report, CheckError>
+where
+    C: Read,
+    P: Read,
+{
+    let cnf = read_cnf(cnf).map_err(CheckError::Cnf)?;
+    let mut checker = Checker::new(&cnf);
+    for step in Parser::new(proof).map_err(CheckError::Proof)? {
+        checker.add_step(step.map_err(CheckError::Proof)?);
+    }
+    checker.check()
+}
@@ -0,0 +1,49 @@
+//! Reading, writing and checking DRAT proofs of unsatisfiability.
+//!
+//! A DRAT proof is a sequence of lemma additions and clause deletions.
+//! In the text format every step is a clause terminated by `0`
+//! and deletions are prefixed with `d`. Comment lines start with `c`.
+//! In the binary format every step starts with the byte `a` or `d`
+//! followed by its literals as variable-length integers terminated by `0`,
+//! where a literal `l` is encoded as `2 * |l| + (l < 0)`.
+//!
+//! The checker works backwards from the empty clause and only checks lemmas
+//! that are needed to derive it. Every such lemma must be a reverse unit
+//! propagation (RUP) or a resolution asymmetric tautology (RAT) on its
+//! first literal with respect to the clauses active before its addition.
+
+mod parser;
+mod writer;
+mod checker;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    parser::BinaryReader,
+    writer::write_encoded,
+};
+pub use self::{
+    parser::{
+        DratErrorKind,
+        Format,
+        Parser,
+    },
+    writer::write_step,
+    checker::{
+        check_drat,
+        CheckError,
+        Checker,
+        Report,
+    },
+};
+use crate::OwnedClause;
+
+/// A step of a DRAT proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// Adds the lemma to the clauses.
+    Add(OwnedClause),
+    /// Deletes the clause from the clauses.
+    Delete(OwnedClause),
+}
@@ -0,0 +1,321 @@
+use super::Step;
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    Lit,
+    OwnedClause,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::{
+        self,
+        BufRead,
+        BufReader,
+        Read,
+    },
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to DRAT proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DratErrorKind {
+    /// An encoded number of the binary format exceeds 64 bits.
+    InvalidValue(i128),
+}
+
+impl fmt::Display for DratErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DratErrorKind::InvalidValue(value) => write!(f, "invalid value {}", value),
+        }
+    }
+}
+
+impl Error for DratErrorKind {}
+
+impl From<DratErrorKind> for ParseErrorKind {
+    fn from(kind: DratErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// The number of leading bytes inspected to detect binary proofs.
+const DETECT_LEN: usize = 10;
+
+/// The encoding of a DRAT proof.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The human readable text format.
+    Text,
+    /// The compact binary format.
+    Binary,
+}
+
+impl Format {
+    /// Guesses the format from the first bytes of a proof.
+    ///
+    /// A proof is considered binary if it starts with `a` or if one of its
+    /// first ten bytes is neither printable ASCII nor whitespace.
+    pub fn detect(bytes: &[u8]) -> Self {
+        let binary = bytes.first() == Some(&b'a') || bytes
+            .iter()
+            .take(DETECT_LEN)
+            .any(|&byte| !(byte.is_ascii_graphic() || byte.is_ascii_whitespace()));
+        if binary { Format::Binary } else { Format::Text }
+    }
+}
+
+/// The input of a parser depending on the format.
+enum Source<R> {
+    /// Text input.
+    Text(Lexer<R>),
+    /// Binary input.
+    Binary(BinaryReader<R>),
+}
+
+/// A reader of the variable-length integers of binary proofs.
+///
+/// Errors are reported at line 1 with the one-based byte offset as column.
+pub(crate) struct BinaryReader<R> {
+    /// The buffered input.
+    reader: BufReader<R>,
+    /// The number of bytes consumed so far.
+    offset: usize,
+}
+
+impl<R> BinaryReader<R>
+where
+    R: Read,
+{
+    /// Creates a new binary reader over the buffered input.
+    pub fn new(reader: BufReader<R>) -> Self {
+        Self { reader, offset: 0 }
+    }
+
+    /// Returns an error of the given kind at the given byte offset.
+    pub fn error_at<T, K>(&self, offset: usize, kind: K) -> Result<T>
+    where
+        K: Into<ParseErrorKind>,
+    {
+        Err(ParseError::new(1, offset + 1, kind.into()))
+    }
+
+    /// Reads the next byte.
+    pub fn read_byte(&mut self) -> Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => {
+                    let byte = buf.first().cloned();
+                    if byte.is_some() {
+                        self.reader.consume(1);
+                        self.offset += 1;
+                    }
+                    return Ok(byte)
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return self.error_at(self.offset, ParseErrorKind::Io(err)),
+            }
+        }
+    }
+
+    /// Reads the next step marker `a` or `d`.
+    ///
+    /// Returns `Some(true)` for deletions and `None` at the end of input.
+    pub fn read_marker(&mut self) -> Result<Option<bool>> {
+        match self.read_byte()? {
+            None => Ok(None),
+            Some(b'a') => Ok(Some(false)),
+            Some(b'd') => Ok(Some(true)),
+            Some(byte) => self.error_at(self.offset - 1, ParseErrorKind::UnexpectedChar(char::from(byte))),
+        }
+    }
+
+    /// Reads a variable-length integer and returns it with its offset.
+    pub fn read_encoded(&mut self) -> Result<(u64, usize)> {
+        let start = self.offset;
+        let mut encoded = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = match self.read_byte()? {
+                Some(byte) => byte,
+                None => return self.error_at(self.offset, ParseErrorKind::UnterminatedClause),
+            };
+            if shift > 56 {
+                return self.error_at(start, DratErrorKind::InvalidValue(i128::from(encoded)))
+            }
+            encoded |= u64::from(byte & 0x7f) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Ok((encoded, start))
+            }
+        }
+    }
+
+    /// Reads a signed integer encoded as `2 * |x| + (x < 0)`.
+    ///
+    /// Returns `None` for the terminating `0`.
+    pub fn read_signed(&mut self) -> Result<Option<(i128, usize)>> {
+        let (encoded, start) = self.read_encoded()?;
+        if encoded == 0 {
+            return Ok(None)
+        }
+        let magnitude = i128::from(encoded >> 1);
+        Ok(Some((if encoded & 1 == 1 { -magnitude } else { magnitude }, start)))
+    }
+
+    /// Reads literals up to the terminating `0`.
+    pub fn read_lits(&mut self) -> Result<Vec<Lit>> {
+        let mut lits = Vec::new();
+        while let Some((value, start)) = self.read_signed()? {
+            match Lit::try_from_wide(value) {
+                Ok(lit) => lits.push(lit),
+                Err(err) => return self.error_at(start, ParseErrorKind::InvalidLit(err)),
+            }
+        }
+        Ok(lits)
+    }
+}
+
+/// A streaming parser for DRAT proofs in either format.
+///
+/// # Note
+///
+/// Errors in binary proofs are reported at line 1
+/// with the one-based byte offset as column.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::drat::{Parser, Step};
+///
+/// let mut parser = Parser::new("1 2 0\nd 1 2 3 0\n0\n".as_bytes()).unwrap();
+/// assert!(matches!(parser.next(), Some(Ok(Step::Add(_)))));
+/// assert!(matches!(parser.next(), Some(Ok(Step::Delete(_)))));
+/// assert_eq!(parser.count(), 1);
+/// ```
+pub struct Parser<R> {
+    /// The input.
+    source: Source<R>,
+    /// `true` if the input is exhausted or an error occured.
+    done: bool,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Creates a new parser detecting the format from the first bytes of the input.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut lexer = Lexer::new(reader);
+        let format = Format::detect(lexer.peek_buf()?);
+        Ok(Self::from_lexer(lexer, format))
+    }
+
+    /// Creates a new parser for input of the given format.
+    pub fn with_format(reader: R, format: Format) -> Self {
+        Self::from_lexer(Lexer::new(reader), format)
+    }
+
+    /// Creates a new parser reading the given format from the lexer.
+    fn from_lexer(lexer: Lexer<R>, format: Format) -> Self {
+        let source = match format {
+            Format::Text => Source::Text(lexer),
+            Format::Binary => Source::Binary(BinaryReader::new(lexer.into_inner())),
+        };
+        Self {
+            source,
+            done: false,
+        }
+    }
+
+    /// Returns the format of the input.
+    pub fn format(&self) -> Format {
+        match self.source {
+            Source::Text(_) => Format::Text,
+            Source::Binary(_) => Format::Binary,
+        }
+    }
+
+    /// Parses the next step of text input.
+    fn parse_text(lexer: &mut Lexer<R>) -> Result<Option<Step>> {
+        let mut lits = Vec::new();
+        let mut deletion = None;
+        loop {
+            lexer.skip_whitespace()?;
+            let start = lexer.position();
+            let byte = match lexer.peek()? {
+                Some(byte) => byte,
+                None if deletion.is_none() => return Ok(None),
+                None => return lexer.error(ParseErrorKind::UnterminatedClause),
+            };
+            if lexer.take_line_start() && byte == b'c' {
+                lexer.skip_line()?;
+                continue
+            }
+            if deletion.is_none() {
+                deletion = Some(byte == b'd');
+                if byte == b'd' {
+                    lexer.bump(byte);
+                    continue
+                }
+            }
+            match lexer.read_int()? {
+                Some(0) => break,
+                Some(value) => match Lit::try_from_wide(value) {
+                    Ok(lit) => lits.push(lit),
+                    Err(err) => return lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+                },
+                None => return lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+        let clause = OwnedClause::from(lits);
+        Ok(Some(if deletion == Some(true) { Step::Delete(clause) } else { Step::Add(clause) }))
+    }
+
+    /// Parses the next step of binary input.
+    fn parse_binary(reader: &mut BinaryReader<R>) -> Result<Option<Step>> {
+        let deletion = match reader.read_marker()? {
+            Some(deletion) => deletion,
+            None => return Ok(None),
+        };
+        let clause = OwnedClause::from(reader.read_lits()?);
+        Ok(Some(if deletion { Step::Delete(clause) } else { Step::Add(clause) }))
+    }
+}
+
+impl<R> Iterator for Parser<R>
+where
+    R: Read,
+{
+    type Item = Result<Step>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let step = match &mut self.source {
+            Source::Text(lexer) => Self::parse_text(lexer),
+            Source::Binary(reader) => Self::parse_binary(reader),
+        };
+        match step {
+            Ok(Some(step)) => Some(Ok(step)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::*;
+use crate::{
+    Lit,
+    dimacs::read_cnf,
+    lrat,
+    tests::lit,
+};
+use std::{
+    string::ToString,
+    vec::Vec,
+};
+
+/// An unsatisfiable formula without conflict under unit propagation.
+const FORMULA: &str = "p cnf 4 5\n-1 2 0\n2 3 0\n2 -3 0\n-2 4 0\n-2 -4 0\n";
+
+/// Returns the text steps of the proof.
+fn steps(proof: &str) -> Vec<Step> {
+    Parser::with_format(proof.as_bytes(), Format::Text).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn parse_text_and_binary() {
+    let proof = steps("c comment\n1 -2\n0 d 1 -2 0\n-30 0\n0\n");
+    assert_eq!(proof, vec![
+        Step::Add(vec![lit(1), lit(-2)].into()),
+        Step::Delete(vec![lit(1), lit(-2)].into()),
+        Step::Add(vec![lit(-30)].into()),
+        Step::Add(Vec::<Lit>::new().into()),
+    ]);
+    let mut binary = Vec::new();
+    for step in &proof {
+        write_step(&mut binary, step, Format::Binary).unwrap();
+    }
+    assert_eq!(&binary[..7], b"a\x02\x05\0d\x02\x05");
+    assert_eq!(&binary[8..], b"a\x3d\0a\0");
+    let mut parser = Parser::new(binary.as_slice()).unwrap();
+    assert_eq!(parser.format(), Format::Binary);
+    assert_eq!(parser.by_ref().collect::<Result<Vec<_>, _>>().unwrap(), proof);
+    let err = Parser::new(&b"a\x02\x05"[..]).unwrap().next().unwrap().unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 4));
+    assert_eq!(err.kind().to_string(), "last clause is not terminated by 0");
+    let overlong = &b"a\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01\0"[..];
+    let err = Parser::new(overlong).unwrap().next().unwrap().unwrap_err();
+    assert!(matches!(err.kind().format_kind(), Some(DratErrorKind::InvalidValue(_))));
+}
+
+#[test]
+fn accept_rup_and_rat_lemmas() {
+    let report = check_drat(FORMULA.as_bytes(), "1 0\n2 0\n0\n".as_bytes()).unwrap();
+    assert_eq!(report, Report { lemmas: 3, core_lemmas: 3, ignored_deletions: 0 });
+    // `1` is RAT since resolving with `-1 2` yields the RUP clause `2`.
+    let report = check_drat(FORMULA.as_bytes(), "1 0\nd 3 0\n".as_bytes()).unwrap();
+    assert_eq!(report, Report { lemmas: 1, core_lemmas: 1, ignored_deletions: 1 });
+}
+
+#[test]
+fn analyze_trail_above_conflict_vars() {
+    let report = check_drat("p cnf 5 4\n1 0\n-1 2 0\n-2 0\n5 0\n".as_bytes(), "".as_bytes()).unwrap();
+    assert_eq!(report, Report { lemmas: 0, core_lemmas: 0, ignored_deletions: 0 });
+}
+
+#[test]
+fn reject_failing_lemma() {
+    match check_drat("p cnf 2 2\n1 2 0\n-1 2 0\n".as_bytes(), "-2 0\n0\n".as_bytes()) {
+        Err(CheckError::Rejected { step, lemma }) => {
+            assert_eq!((step, lemma.as_slice()), (1, &[lit(-2)][..]));
+        }
+        other => panic!("unexpected check result: {:?}", other),
+    }
+    let cnf = read_cnf(FORMULA.as_bytes()).unwrap();
+    let mut checker = Checker::new(&cnf);
+    for step in steps("d -1 2 0\n1 0\n0\n") {
+        checker.add_step(step);
+    }
+    let err = checker.check().unwrap_err();
+    assert_eq!(err.to_string(), "lemma [] of proof step 3 is neither RUP nor RAT");
+    assert!(matches!(
+        check_drat(FORMULA.as_bytes(), "c empty\n".as_bytes()),
+        Err(CheckError::NoConflict)
+    ));
+}
+
+#[test]
+fn rat_pivot_survives_propagation() {
+    // Propagation reorders the stored lemma `2 5` whose RAT pivot is `2`.
+    let formula = "p cnf 5 8\n1 2 -3 0\n-2 -1 3 0\n3 1 0\n-3 1 -2 0\n2 3 1 0\n-2 -3 0\n-3 -1 0\n-1 3 0\n";
+    let proof = "3 5 -1 0\n-3 1 -2 0\n-5 0\n2 5 0\n2 0\n0\n";
+    check_drat(formula.as_bytes(), proof.as_bytes()).unwrap();
+    let mut checker = Checker::new(&read_cnf(formula.as_bytes()).unwrap());
+    for step in steps(proof) {
+        checker.add_step(step);
+    }
+    let steps = checker.elaborate().unwrap();
+    let lemma = steps.iter().find_map(|step| match step {
+        lrat::Step::Add { id: 12, clause, .. } => Some(clause.as_slice()),
+        _ => None,
+    });
+    assert_eq!(lemma, Some(&[lit(2), lit(5)][..]));
+}
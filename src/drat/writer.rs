@@ -0,0 +1,50 @@
+use super::{
+    Format,
+    Step,
+};
+use std::io::{
+    self,
+    Write,
+};
+
+/// Writes the proof step in the given format.
+pub fn write_step<W>(writer: &mut W, step: &Step, format: Format) -> io::Result<()>
+where
+    W: Write,
+{
+    let (deletion, clause) = match step {
+        Step::Add(clause) => (false, clause),
+        Step::Delete(clause) => (true, clause),
+    };
+    match format {
+        Format::Text => {
+            if deletion {
+                write!(writer, "d ")?;
+            }
+            for lit in clause {
+                write!(writer, "{} ", lit.to_raw())?;
+            }
+            writeln!(writer, "0")
+        }
+        Format::Binary => {
+            writer.write_all(if deletion { b"d" } else { b"a" })?;
+            for lit in clause {
+                write_encoded(writer, i64::from(lit.to_raw()))?;
+            }
+            writer.write_all(&[0])
+        }
+    }
+}
+
+/// Writes the signed integer as variable-length integer `2 * |x| + (x < 0)`.
+pub(crate) fn write_encoded<W>(writer: &mut W, value: i64) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut encoded = 2 * value.unsigned_abs() + (value < 0) as u64;
+    while encoded > 0x7f {
+        writer.write_all(&[(encoded & 0x7f) as u8 | 0x80])?;
+        encoded >>= 7;
+    }
+    writer.write_all(&[encoded as u8])
+}
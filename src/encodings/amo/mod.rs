@@ -0,0 +1,210 @@
+//! Encodings of at-most-one and exactly-one constraints.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// The encoding of at-most-one constraints into clauses.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AmoEncoding {
+    /// Chooses an encoding by the number of literals, see `AmoEncoding::for_size`.
+    #[default]
+    Auto,
+    /// One binary clause per pair of literals without auxiliary variables.
+    Pairwise,
+    /// The sequential counter of Sinz with one auxiliary variable per literal.
+    SequentialCounter,
+    /// The commander encoding of Klieber and Kwon over groups of three literals.
+    Commander,
+    /// The product encoding of Chen over a two-dimensional grid of the literals.
+    Product,
+    /// The bimander encoding of Nguyen and Mai over pairs of literals
+    /// whose group is identified by binary auxiliary variables.
+    Bimander,
+    /// The ladder encoding of Gent and Nightingale.
+    Ladder,
+}
+
+impl AmoEncoding {
+    /// Returns the encoding used by `AmoEncoding::Auto` for the number of literals.
+    ///
+    /// The pairwise encoding has the fewest clauses up to six literals, the
+    /// sequential counter propagates well for mid-sized constraints and the
+    /// product encoding needs the fewest auxiliary variables and clauses beyond.
+    pub fn for_size(len: usize) -> Self {
+        match len {
+            0..=6 => AmoEncoding::Pairwise,
+            7..=64 => AmoEncoding::SequentialCounter,
+            _ => AmoEncoding::Product,
+        }
+    }
+}
+
+/// Adds clauses to the solver that allow at most one of the literals to be `true`.
+///
+/// The variables of the literals are reserved in the variable manager
+/// before auxiliary variables are taken from it.
+pub fn at_most_one<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit], encoding: AmoEncoding)
+where
+    S: IpasirSolver,
+{
+    for lit in lits {
+        vars.reserve(lit.var());
+    }
+    encode(solver, vars, lits, encoding);
+}
+
+/// Adds clauses to the solver that require exactly one of the literals to be `true`.
+///
+/// This is the clause of all literals and their at-most-one constraint.
+pub fn exactly_one<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit], encoding: AmoEncoding)
+where
+    S: IpasirSolver,
+{
+    solver.add_clause(lits.iter().cloned());
+    at_most_one(solver, vars, lits, encoding);
+}
+
+/// Dispatches to the given encoding.
+fn encode<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit], encoding: AmoEncoding)
+where
+    S: IpasirSolver,
+{
+    if lits.len() <= 1 {
+        return
+    }
+    match encoding {
+        AmoEncoding::Auto => encode(solver, vars, lits, AmoEncoding::for_size(lits.len())),
+        AmoEncoding::Pairwise => pairwise(solver, lits),
+        AmoEncoding::SequentialCounter => sequential_counter(solver, vars, lits),
+        AmoEncoding::Commander => commander(solver, vars, lits),
+        AmoEncoding::Product => product(solver, vars, lits),
+        AmoEncoding::Bimander => bimander(solver, vars, lits),
+        AmoEncoding::Ladder => ladder(solver, vars, lits),
+    }
+}
+
+/// Adds `!a | !b` for every pair of literals.
+fn pairwise<S>(solver: &mut S, lits: &[Lit])
+where
+    S: IpasirSolver,
+{
+    for (n, &a) in lits.iter().enumerate() {
+        for &b in &lits[n + 1..] {
+            solver.add_clause(vec![!a, !b]);
+        }
+    }
+}
+
+/// Adds the sequential counter where `s_i` is `true` if one of the first `i` literals is.
+fn sequential_counter<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit])
+where
+    S: IpasirSolver,
+{
+    let last = lits.len() - 1;
+    let mut prev = vars.fresh_lit();
+    solver.add_clause(vec![!lits[0], prev]);
+    for &lit in &lits[1..last] {
+        let next = vars.fresh_lit();
+        solver.add_clause(vec![!lit, next]);
+        solver.add_clause(vec![!prev, next]);
+        solver.add_clause(vec![!lit, !prev]);
+        prev = next;
+    }
+    solver.add_clause(vec![!lits[last], !prev]);
+}
+
+/// Adds the ladder `y_1 <- y_2 <- ... <- y_{n-1}` where literal `i` implies
+/// `y_{i-1}` and `!y_i`, so two literals would need a rung to be both.
+fn ladder<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit])
+where
+    S: IpasirSolver,
+{
+    let rungs: Vec<Lit> = (1..lits.len()).map(|_| vars.fresh_lit()).collect();
+    for pair in rungs.windows(2) {
+        solver.add_clause(vec![!pair[1], pair[0]]);
+    }
+    for (n, &lit) in lits.iter().enumerate() {
+        if n > 0 {
+            solver.add_clause(vec![!lit, rungs[n - 1]]);
+        }
+        if n < rungs.len() {
+            solver.add_clause(vec![!lit, !rungs[n]]);
+        }
+    }
+}
+
+/// Adds the commander encoding recursively over the commanders of the groups.
+fn commander<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit])
+where
+    S: IpasirSolver,
+{
+    const GROUP_SIZE: usize = 3;
+    if lits.len() <= GROUP_SIZE + 1 {
+        return pairwise(solver, lits)
+    }
+    let mut commanders = Vec::with_capacity(lits.len() / GROUP_SIZE + 1);
+    for group in lits.chunks(GROUP_SIZE) {
+        let commander = vars.fresh_lit();
+        pairwise(solver, group);
+        for &lit in group {
+            solver.add_clause(vec![!lit, commander]);
+        }
+        commanders.push(commander);
+    }
+    commander(solver, vars, &commanders);
+}
+
+/// Adds the product encoding where every literal implies its row and column
+/// of a grid, with at most one row and at most one column encoded recursively.
+fn product<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit])
+where
+    S: IpasirSolver,
+{
+    if lits.len() <= 4 {
+        return pairwise(solver, lits)
+    }
+    let mut num_rows = 1;
+    while num_rows * num_rows < lits.len() {
+        num_rows += 1;
+    }
+    let num_columns = lits.len().div_ceil(num_rows);
+    let rows: Vec<Lit> = (0..num_rows).map(|_| vars.fresh_lit()).collect();
+    let columns: Vec<Lit> = (0..num_columns).map(|_| vars.fresh_lit()).collect();
+    for (n, &lit) in lits.iter().enumerate() {
+        solver.add_clause(vec![!lit, rows[n / num_columns]]);
+        solver.add_clause(vec![!lit, columns[n % num_columns]]);
+    }
+    product(solver, vars, &rows);
+    product(solver, vars, &columns);
+}
+
+/// Adds the bimander encoding where every literal implies the binary
+/// representation of the index of its pair.
+fn bimander<S>(solver: &mut S, vars: &mut VarManager, lits: &[Lit])
+where
+    S: IpasirSolver,
+{
+    const GROUP_SIZE: usize = 2;
+    let num_groups = lits.len().div_ceil(GROUP_SIZE);
+    let mut num_bits = 0;
+    while 1 << num_bits < num_groups {
+        num_bits += 1;
+    }
+    let bits: Vec<Lit> = (0..num_bits).map(|_| vars.fresh_lit()).collect();
+    for (index, group) in lits.chunks(GROUP_SIZE).enumerate() {
+        pairwise(solver, group);
+        for &lit in group {
+            for (n, &bit) in bits.iter().enumerate() {
+                let bit = if index >> n & 1 == 1 { bit } else { !bit };
+                solver.add_clause(vec![!lit, bit]);
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+use crate::{
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+
+/// All encodings but `AmoEncoding::Auto`.
+const ENCODINGS: [AmoEncoding; 6] = [
+    AmoEncoding::Pairwise,
+    AmoEncoding::SequentialCounter,
+    AmoEncoding::Commander,
+    AmoEncoding::Product,
+    AmoEncoding::Bimander,
+    AmoEncoding::Ladder,
+];
+
+/// Checks that the constraint over `len` literals admits exactly
+/// the assignments with at most one or exactly one `true` literal.
+fn check_exhaustive(len: i32, encoding: AmoEncoding, exactly: bool) {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    // Mix polarities to make sure literals are not confused with variables.
+    let lits: Vec<Lit> = (1..=len).map(|var| lit(if var % 3 == 0 { -var } else { var })).collect();
+    if exactly {
+        exactly_one(&mut solver, &mut vars, &lits, encoding);
+    } else {
+        at_most_one(&mut solver, &mut vars, &lits, encoding);
+    }
+    for bits in 0u32..1 << len {
+        for (n, &lit) in lits.iter().enumerate() {
+            solver.assume(if bits >> n & 1 == 1 { lit } else { !lit });
+        }
+        let ok = bits.count_ones() <= 1 && (!exactly || bits.count_ones() == 1);
+        let expected = if ok { SolveResponse::Sat } else { SolveResponse::Unsat };
+        assert_eq!(solver.solve(), Ok(expected), "{:?} over {} literals with {:b}", encoding, len, bits);
+    }
+}
+
+#[test]
+fn at_most_one_is_exact() {
+    for &encoding in &ENCODINGS {
+        for len in 0..=9 {
+            check_exhaustive(len, encoding, false);
+        }
+    }
+}
+
+#[test]
+fn exactly_one_is_exact() {
+    for &encoding in &ENCODINGS {
+        check_exhaustive(0, encoding, true);
+        check_exhaustive(7, encoding, true);
+    }
+}
+
+#[test]
+fn auto_picks_by_size() {
+    assert_eq!(AmoEncoding::for_size(5), AmoEncoding::Pairwise);
+    assert_eq!(AmoEncoding::for_size(1000), AmoEncoding::Product);
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let lits: Vec<Lit> = (1..=4).map(lit).collect();
+    at_most_one(&mut solver, &mut vars, &lits, AmoEncoding::Auto);
+    assert_eq!(solver.clauses().count(), 6);
+    assert_eq!(vars.num_vars(), 4);
+}
@@ -0,0 +1,184 @@
+use super::{
+    BitBlaster,
+    BitVec,
+};
+use crate::{
+    Lit,
+    IpasirSolver,
+};
+use alloc::vec::Vec;
+
+impl<'a, S> BitBlaster<'a, S>
+where
+    S: IpasirSolver,
+{
+    /// Returns a literal equivalent to the majority of the three literals.
+    fn majority_gate(&mut self, a: Lit, b: Lit, c: Lit) -> Lit {
+        let lits = [a, b, c];
+        for (n, &lit) in lits.iter().enumerate() {
+            if let Some(value) = self.value(lit) {
+                let (x, y) = (lits[(n + 1) % 3], lits[(n + 2) % 3]);
+                return if value { self.or_gate(x, y) } else { self.and_gate(x, y) }
+            }
+        }
+        if a == b || a == c {
+            return a
+        }
+        if b == c {
+            return b
+        }
+        let out = self.vars.fresh_lit();
+        for &(x, y) in &[(a, b), (a, c), (b, c)] {
+            self.solver.add_clause(vec![!out, x, y]);
+            self.solver.add_clause(vec![out, !x, !y]);
+        }
+        out
+    }
+
+    /// Returns the sum and carry bits of a full adder.
+    fn full_adder(&mut self, a: Lit, b: Lit, carry: Lit) -> (Lit, Lit) {
+        let partial = self.xor_gate(a, b);
+        let sum = self.xor_gate(partial, carry);
+        (sum, self.majority_gate(a, b, carry))
+    }
+
+    /// Returns the sum of both bit-vectors and the carry bit with a ripple-carry adder.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn add_with_carry(&mut self, a: &BitVec, b: &BitVec, mut carry: Lit) -> (BitVec, Lit) {
+        assert_eq!(a.width(), b.width(), "the bit-vectors must have the same width");
+        let mut bits = Vec::with_capacity(a.width());
+        for (&a, &b) in a.bits().iter().zip(b.bits()) {
+            let (sum, next) = self.full_adder(a, b, carry);
+            bits.push(sum);
+            carry = next;
+        }
+        (BitVec::new(bits), carry)
+    }
+
+    /// Returns the sum modulo `2^width`.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn add(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        let carry = self.lit(false);
+        self.add_with_carry(a, b, carry).0
+    }
+
+    /// Returns the difference modulo `2^width`.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn sub(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        let carry = self.lit(true);
+        self.add_with_carry(a, &!b, carry).0
+    }
+
+    /// Returns the two's complement negation.
+    pub fn neg(&mut self, a: &BitVec) -> BitVec {
+        let zero = self.constant(a.width(), 0);
+        self.sub(&zero, a)
+    }
+
+    /// Returns the product modulo `2^width` with an array multiplier.
+    ///
+    /// Every bit of `b` selects a shifted copy of `a` that is added to the product.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn mul(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        assert_eq!(a.width(), b.width(), "the bit-vectors must have the same width");
+        let width = a.width();
+        let mut product = self.constant(width, 0);
+        for (shift, &bit) in b.bits().iter().enumerate() {
+            if self.value(bit) == Some(false) {
+                continue
+            }
+            let shifted = self.shl_const(a, shift);
+            let row = self.ite(bit, &shifted, &self.constant(width, 0));
+            // The lower bits of the row are zero, so only the upper bits need adders.
+            let upper = product.extract(shift..width);
+            let sum = self.add(&upper, &row.extract(shift..width));
+            product = product.extract(0..shift).concat(&sum);
+        }
+        product
+    }
+
+    /// Returns the unsigned quotient and remainder with a restoring divider.
+    ///
+    /// Division by zero yields a quotient of all ones and the dividend as remainder.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn udiv_rem(&mut self, a: &BitVec, b: &BitVec) -> (BitVec, BitVec) {
+        assert_eq!(a.width(), b.width(), "the bit-vectors must have the same width");
+        let width = a.width();
+        let divisor = self.zero_extend(b, width + 1);
+        let mut rem = self.constant(width, 0);
+        let mut quotient = vec![self.lit(false); width];
+        for n in (0..width).rev() {
+            // Shift in the next bit of the dividend with one extra bit for the comparison.
+            let shifted = BitVec::new(vec![a.bits()[n]]).concat(&rem);
+            let fits = !self.ult(&shifted, &divisor);
+            let diff = self.sub(&shifted, &divisor);
+            rem = self.ite(fits, &diff, &shifted).extract(0..width);
+            quotient[n] = fits;
+        }
+        (BitVec::new(quotient), rem)
+    }
+
+    /// Returns the unsigned quotient.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn udiv(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        self.udiv_rem(a, b).0
+    }
+
+    /// Returns the unsigned remainder.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn urem(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        self.udiv_rem(a, b).1
+    }
+
+    /// Returns the absolute value in two's complement.
+    fn abs(&mut self, a: &BitVec) -> BitVec {
+        let neg = self.neg(a);
+        self.ite(a.msb(), &neg, a)
+    }
+
+    /// Returns the signed quotient rounded towards zero.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ or the bit-vectors have no bits.
+    pub fn sdiv(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        let (abs_a, abs_b) = (self.abs(a), self.abs(b));
+        let quotient = self.udiv(&abs_a, &abs_b);
+        let neg = self.neg(&quotient);
+        let negative = self.xor_gate(a.msb(), b.msb());
+        self.ite(negative, &neg, &quotient)
+    }
+
+    /// Returns the signed remainder whose sign follows the dividend.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ or the bit-vectors have no bits.
+    pub fn srem(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        let (abs_a, abs_b) = (self.abs(a), self.abs(b));
+        let rem = self.urem(&abs_a, &abs_b);
+        let neg = self.neg(&rem);
+        self.ite(a.msb(), &neg, &rem)
+    }
+}
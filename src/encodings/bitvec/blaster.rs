@@ -0,0 +1,377 @@
+use super::BitVec;
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// Adds clauses for bit-vector operations to a solver.
+///
+/// Constants are represented by a literal forced to be `true` and its
+/// negation. Gates with constant or equal inputs are simplified away,
+/// so operations on constants add no clauses.
+#[derive(Debug)]
+pub struct BitBlaster<'a, S> {
+    /// The solver receiving the clauses.
+    pub(super) solver: &'a mut S,
+    /// The source of auxiliary variables.
+    pub(super) vars: &'a mut VarManager,
+    /// The literal forced to be `true`.
+    pub(super) true_lit: Lit,
+}
+
+impl<'a, S> BitBlaster<'a, S>
+where
+    S: IpasirSolver,
+{
+    /// Creates a new bit-blaster whose constant `true` is a fresh variable.
+    ///
+    /// # Note
+    ///
+    /// All variables used in bit-vectors must be reserved in the variable
+    /// manager or be taken from it.
+    pub fn new(solver: &'a mut S, vars: &'a mut VarManager) -> Self {
+        let true_lit = vars.fresh_lit();
+        solver.add_clause(Some(true_lit));
+        Self { solver, vars, true_lit }
+    }
+
+    /// Creates a new bit-blaster using the given literal as constant `true`.
+    ///
+    /// Use this to share the constants of an earlier bit-blaster of the same
+    /// solver. The literal must already be forced to be `true`.
+    pub fn with_true_lit(solver: &'a mut S, vars: &'a mut VarManager, true_lit: Lit) -> Self {
+        vars.reserve(true_lit.var());
+        Self { solver, vars, true_lit }
+    }
+
+    /// Returns the literal forced to be `true`.
+    pub fn true_lit(&self) -> Lit {
+        self.true_lit
+    }
+
+    /// Returns the literal forced to be `false`.
+    pub fn false_lit(&self) -> Lit {
+        !self.true_lit
+    }
+
+    /// Adds the literal as unit clause.
+    pub fn assert(&mut self, lit: Lit) {
+        self.solver.add_clause(Some(lit));
+    }
+
+    /// Returns the constant literal of the value.
+    pub fn lit(&self, value: bool) -> Lit {
+        if value { self.true_lit } else { !self.true_lit }
+    }
+
+    /// Returns a bit-vector of fresh variables.
+    pub fn fresh(&mut self, width: usize) -> BitVec {
+        BitVec::new((0..width).map(|_| self.vars.fresh_lit()).collect())
+    }
+
+    /// Returns the constant bit-vector of the lower `width` bits of the value.
+    pub fn constant(&self, width: usize, value: u128) -> BitVec {
+        BitVec::new((0..width).map(|n| self.lit(n < 128 && value >> n & 1 == 1)).collect())
+    }
+
+    /// Returns the constant bit-vector of the lower `width` bits
+    /// of the value in two's complement.
+    pub fn signed_constant(&self, width: usize, value: i128) -> BitVec {
+        BitVec::new((0..width).map(|n| self.lit(value >> n.min(127) & 1 == 1)).collect())
+    }
+
+    /// Returns the value of the literal if it is constant.
+    pub(super) fn value(&self, lit: Lit) -> Option<bool> {
+        if lit == self.true_lit {
+            Some(true)
+        } else if lit == !self.true_lit {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a literal equivalent to `a & b`.
+    pub(super) fn and_gate(&mut self, a: Lit, b: Lit) -> Lit {
+        match (self.value(a), self.value(b)) {
+            (Some(false), _) | (_, Some(false)) => return self.lit(false),
+            (Some(true), _) => return b,
+            (_, Some(true)) => return a,
+            _ => (),
+        }
+        if a == b {
+            return a
+        }
+        if a == !b {
+            return self.lit(false)
+        }
+        let out = self.vars.fresh_lit();
+        self.solver.add_clause(vec![!out, a]);
+        self.solver.add_clause(vec![!out, b]);
+        self.solver.add_clause(vec![out, !a, !b]);
+        out
+    }
+
+    /// Returns a literal equivalent to `a | b`.
+    pub(super) fn or_gate(&mut self, a: Lit, b: Lit) -> Lit {
+        !self.and_gate(!a, !b)
+    }
+
+    /// Returns a literal equivalent to `a ^ b`.
+    pub(super) fn xor_gate(&mut self, a: Lit, b: Lit) -> Lit {
+        match (self.value(a), self.value(b)) {
+            (Some(value), _) => return if value { !b } else { b },
+            (_, Some(value)) => return if value { !a } else { a },
+            _ => (),
+        }
+        if a == b {
+            return self.lit(false)
+        }
+        if a == !b {
+            return self.lit(true)
+        }
+        let out = self.vars.fresh_lit();
+        self.solver.add_clause(vec![!out, a, b]);
+        self.solver.add_clause(vec![!out, !a, !b]);
+        self.solver.add_clause(vec![out, !a, b]);
+        self.solver.add_clause(vec![out, a, !b]);
+        out
+    }
+
+    /// Returns a literal equivalent to `cond ? then : otherwise`.
+    pub(super) fn ite_gate(&mut self, cond: Lit, then: Lit, otherwise: Lit) -> Lit {
+        if let Some(value) = self.value(cond) {
+            return if value { then } else { otherwise }
+        }
+        if then == otherwise {
+            return then
+        }
+        if then == !otherwise {
+            return !self.xor_gate(cond, then)
+        }
+        match (self.value(then), self.value(otherwise)) {
+            (Some(true), _) => return self.or_gate(cond, otherwise),
+            (Some(false), _) => return self.and_gate(!cond, otherwise),
+            (_, Some(true)) => return self.or_gate(!cond, then),
+            (_, Some(false)) => return self.and_gate(cond, then),
+            _ => (),
+        }
+        let out = self.vars.fresh_lit();
+        self.solver.add_clause(vec![!out, !cond, then]);
+        self.solver.add_clause(vec![!out, cond, otherwise]);
+        self.solver.add_clause(vec![out, !cond, !then]);
+        self.solver.add_clause(vec![out, cond, !otherwise]);
+        self.solver.add_clause(vec![!out, then, otherwise]);
+        self.solver.add_clause(vec![out, !then, !otherwise]);
+        out
+    }
+
+    /// Returns a literal equivalent to the conjunction of all literals.
+    pub(super) fn and_all<I>(&mut self, lits: I) -> Lit
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        let true_lit = self.true_lit;
+        lits.into_iter().fold(true_lit, |acc, lit| self.and_gate(acc, lit))
+    }
+
+    /// Returns the bit-vector of the gate applied to all pairs of bits.
+    fn bitwise<F>(&mut self, a: &BitVec, b: &BitVec, mut gate: F) -> BitVec
+    where
+        F: FnMut(&mut Self, Lit, Lit) -> Lit,
+    {
+        assert_eq!(a.width(), b.width(), "the bit-vectors must have the same width");
+        BitVec::new(a.bits().iter().zip(b.bits()).map(|(&a, &b)| gate(self, a, b)).collect())
+    }
+
+    /// Returns the bitwise conjunction.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn and(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        self.bitwise(a, b, Self::and_gate)
+    }
+
+    /// Returns the bitwise disjunction.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn or(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        self.bitwise(a, b, Self::or_gate)
+    }
+
+    /// Returns the bitwise exclusive or.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn xor(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        self.bitwise(a, b, Self::xor_gate)
+    }
+
+    /// Returns `then` if the condition is `true` and `otherwise` if not.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn ite(&mut self, cond: Lit, then: &BitVec, otherwise: &BitVec) -> BitVec {
+        self.bitwise(then, otherwise, |blaster, then, otherwise| blaster.ite_gate(cond, then, otherwise))
+    }
+
+    /// Returns the bit-vector zero-extended to the given width.
+    ///
+    /// # Panics
+    ///
+    /// If the width is smaller than the width of the bit-vector.
+    pub fn zero_extend(&self, a: &BitVec, width: usize) -> BitVec {
+        assert!(width >= a.width(), "the width must not shrink");
+        let zeros = self.constant(width - a.width(), 0);
+        a.concat(&zeros)
+    }
+
+    /// Returns the bit-vector sign-extended to the given width.
+    ///
+    /// # Panics
+    ///
+    /// If the width is smaller than the width of the bit-vector
+    /// or the bit-vector has no bits.
+    pub fn sign_extend(&self, a: &BitVec, width: usize) -> BitVec {
+        assert!(width >= a.width(), "the width must not shrink");
+        let signs = BitVec::new(vec![a.msb(); width - a.width()]);
+        a.concat(&signs)
+    }
+
+    /// Returns the bit-vector shifted left by a constant amount.
+    pub fn shl_const(&self, a: &BitVec, amount: usize) -> BitVec {
+        let width = a.width();
+        BitVec::new((0..width).map(|n| if n >= amount { a.bits()[n - amount] } else { self.lit(false) }).collect())
+    }
+
+    /// Returns the bit-vector shifted right by a constant amount filling in zeros.
+    pub fn lshr_const(&self, a: &BitVec, amount: usize) -> BitVec {
+        let fill = self.lit(false);
+        Self::shr_const(a, amount, fill)
+    }
+
+    /// Returns the bit-vector shifted right by a constant amount filling in the sign bit.
+    pub fn ashr_const(&self, a: &BitVec, amount: usize) -> BitVec {
+        if a.is_empty() {
+            return a.clone()
+        }
+        Self::shr_const(a, amount, a.msb())
+    }
+
+    /// Returns the bit-vector shifted right by a constant amount filling in the given bit.
+    fn shr_const(a: &BitVec, amount: usize, fill: Lit) -> BitVec {
+        let width = a.width();
+        BitVec::new((0..width).map(|n| if n < width.saturating_sub(amount) { a.bits()[n + amount] } else { fill }).collect())
+    }
+
+    /// Returns the bit-vector shifted by the unsigned amount using a barrel shifter.
+    ///
+    /// Amounts of at least the width shift out all bits.
+    fn shift<F>(&mut self, a: &BitVec, amount: &BitVec, fill: Lit, shift: F) -> BitVec
+    where
+        F: Fn(&Self, &BitVec, usize) -> BitVec,
+    {
+        let mut result = a.clone();
+        let mut overflow = self.lit(false);
+        for (k, &bit) in amount.bits().iter().enumerate() {
+            if k < usize::BITS as usize && 1 << k < a.width() {
+                let shifted = shift(self, &result, 1 << k);
+                result = self.ite(bit, &shifted, &result);
+            } else {
+                overflow = self.or_gate(overflow, bit);
+            }
+        }
+        let filled = BitVec::new(vec![fill; a.width()]);
+        self.ite(overflow, &filled, &result)
+    }
+
+    /// Returns the bit-vector shifted left by the unsigned amount.
+    pub fn shl(&mut self, a: &BitVec, amount: &BitVec) -> BitVec {
+        let fill = self.lit(false);
+        self.shift(a, amount, fill, Self::shl_const)
+    }
+
+    /// Returns the bit-vector shifted right by the unsigned amount filling in zeros.
+    pub fn lshr(&mut self, a: &BitVec, amount: &BitVec) -> BitVec {
+        let fill = self.lit(false);
+        self.shift(a, amount, fill, Self::lshr_const)
+    }
+
+    /// Returns the bit-vector shifted right by the unsigned amount filling in the sign bit.
+    pub fn ashr(&mut self, a: &BitVec, amount: &BitVec) -> BitVec {
+        if a.is_empty() {
+            return a.clone()
+        }
+        self.shift(a, amount, a.msb(), Self::ashr_const)
+    }
+
+    /// Returns a literal that is `true` if and only if both bit-vectors are equal.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn eq(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        let equal = self.bitwise(a, b, |blaster, a, b| !blaster.xor_gate(a, b));
+        self.and_all(equal.bits().iter().cloned())
+    }
+
+    /// Returns a literal that is `true` if and only if `a < b` as unsigned values.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn ult(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        assert_eq!(a.width(), b.width(), "the bit-vectors must have the same width");
+        // The highest differing bit decides, so fold from the least significant bit.
+        let mut less = self.lit(false);
+        for (&a, &b) in a.bits().iter().zip(b.bits()) {
+            let differ = self.xor_gate(a, b);
+            less = self.ite_gate(differ, b, less);
+        }
+        less
+    }
+
+    /// Returns a literal that is `true` if and only if `a <= b` as unsigned values.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn ule(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        !self.ult(b, a)
+    }
+
+    /// Returns a literal that is `true` if and only if `a < b` as signed values.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn slt(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        self.ult(&flip_sign(a), &flip_sign(b))
+    }
+
+    /// Returns a literal that is `true` if and only if `a <= b` as signed values.
+    ///
+    /// # Panics
+    ///
+    /// If the widths differ.
+    pub fn sle(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        !self.slt(b, a)
+    }
+}
+
+/// Returns the bit-vector with negated sign bit which orders
+/// signed values like unsigned values.
+fn flip_sign(a: &BitVec) -> BitVec {
+    let mut bits: Vec<Lit> = a.bits().to_vec();
+    if let Some(msb) = bits.last_mut() {
+        *msb = !*msb;
+    }
+    BitVec::new(bits)
+}
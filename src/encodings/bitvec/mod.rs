@@ -0,0 +1,132 @@
+//! Bit-blasting of fixed-width bit-vector operations.
+//!
+//! A `BitVec` is a vector of literals with the least significant bit first.
+//! The `BitBlaster` adds clauses for the operations to a solver, where all
+//! arithmetic wraps around and division follows SMT-LIB semantics: division by
+//! zero yields all ones and the remainder is the dividend.
+
+mod arith;
+mod blaster;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::blaster::BitBlaster;
+use crate::{
+    Lit,
+    Model,
+    LitValue,
+};
+use alloc::vec::Vec;
+use core::ops;
+
+/// A fixed-width bit-vector of literals, least significant bit first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    /// The bits, least significant bit first.
+    bits: Vec<Lit>,
+}
+
+impl BitVec {
+    /// Creates a bit-vector from its bits, least significant bit first.
+    pub fn new(bits: Vec<Lit>) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the number of bits.
+    pub fn width(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Returns `true` if the bit-vector has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Returns the bits, least significant bit first.
+    pub fn bits(&self) -> &[Lit] {
+        &self.bits
+    }
+
+    /// Returns the most significant bit which is the sign bit of signed values.
+    ///
+    /// # Panics
+    ///
+    /// If the bit-vector has no bits.
+    pub fn msb(&self) -> Lit {
+        *self.bits.last().expect("the bit-vector has no bits")
+    }
+
+    /// Returns the bits in the given range.
+    ///
+    /// # Panics
+    ///
+    /// If the range exceeds the width.
+    pub fn extract(&self, range: ops::Range<usize>) -> Self {
+        Self::new(self.bits[range].to_vec())
+    }
+
+    /// Returns the bit-vector with the bits of `high` above its own bits.
+    pub fn concat(&self, high: &BitVec) -> Self {
+        Self::new(self.bits.iter().chain(&high.bits).cloned().collect())
+    }
+
+    /// Returns the unsigned value in the model.
+    ///
+    /// Returns `None` if a bit is unassigned.
+    ///
+    /// # Panics
+    ///
+    /// If the bit-vector is wider than 128 bits.
+    pub fn value(&self, model: &Model) -> Option<u128> {
+        assert!(self.width() <= 128, "the bit-vector is wider than 128 bits");
+        let mut value = 0;
+        for (n, &bit) in self.bits.iter().enumerate() {
+            match model.value(bit) {
+                LitValue::True => value |= 1 << n,
+                LitValue::False => (),
+                LitValue::DontCare => return None,
+            }
+        }
+        Some(value)
+    }
+
+    /// Returns the signed value in two's complement in the model.
+    ///
+    /// Returns `None` if a bit is unassigned.
+    ///
+    /// # Panics
+    ///
+    /// If the bit-vector is wider than 128 bits.
+    pub fn signed_value(&self, model: &Model) -> Option<i128> {
+        let value = self.value(model)?;
+        let width = self.width();
+        if width == 0 || width == 128 || value >> (width - 1) == 0 {
+            Some(value as i128)
+        } else {
+            Some((value | !0 << width) as i128)
+        }
+    }
+}
+
+impl From<Vec<Lit>> for BitVec {
+    fn from(bits: Vec<Lit>) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl ops::Not for &BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> BitVec {
+        BitVec::new(self.bits.iter().map(|&bit| !bit).collect())
+    }
+}
+
+impl ops::Not for BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> BitVec {
+        !&self
+    }
+}
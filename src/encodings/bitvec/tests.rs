@@ -0,0 +1,206 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    SolveResponse,
+    VarManager,
+    tests::TestSolver,
+};
+
+/// The width of the exhaustively tested bit-vectors.
+const WIDTH: usize = 4;
+
+/// The mask of the tested width.
+const MASK: u128 = (1 << WIDTH) - 1;
+
+/// Returns the signed value of the tested width.
+fn signed(value: u128) -> i128 {
+    if value >> (WIDTH - 1) == 1 { value as i128 - (1 << WIDTH) } else { value as i128 }
+}
+
+/// Returns the unsigned quotient and remainder following SMT-LIB.
+fn udiv_rem(a: u128, b: u128) -> (u128, u128) {
+    match a.checked_div(b) {
+        Some(quotient) => (quotient, a % b),
+        None => (MASK, a),
+    }
+}
+
+/// Returns the two's complement negation.
+fn neg(a: u128) -> u128 {
+    a.wrapping_neg() & MASK
+}
+
+/// Returns the signed quotient and remainder following SMT-LIB.
+fn sdiv_rem(a: u128, b: u128) -> (u128, u128) {
+    let abs = |value: u128| if signed(value) < 0 { neg(value) } else { value };
+    let (quotient, rem) = udiv_rem(abs(a), abs(b));
+    let quotient = if (signed(a) < 0) != (signed(b) < 0) { neg(quotient) } else { quotient };
+    let rem = if signed(a) < 0 { neg(rem) } else { rem };
+    (quotient, rem)
+}
+
+/// The results of all operations on two bit-vectors.
+struct Results {
+    /// The bit-vector results by operation.
+    vectors: Vec<(&'static str, BitVec)>,
+    /// The comparison results by operation.
+    lits: Vec<(&'static str, Lit)>,
+}
+
+/// Returns the results of all operations on both bit-vectors.
+fn blast_all<S>(blaster: &mut BitBlaster<'_, S>, a: &BitVec, b: &BitVec) -> Results
+where
+    S: IpasirSolver,
+{
+    let (udiv, urem) = blaster.udiv_rem(a, b);
+    let vectors = vec![
+        ("and", blaster.and(a, b)),
+        ("or", blaster.or(a, b)),
+        ("xor", blaster.xor(a, b)),
+        ("not", !a),
+        ("add", blaster.add(a, b)),
+        ("sub", blaster.sub(a, b)),
+        ("neg", blaster.neg(a)),
+        ("mul", blaster.mul(a, b)),
+        ("udiv", udiv),
+        ("urem", urem),
+        ("sdiv", blaster.sdiv(a, b)),
+        ("srem", blaster.srem(a, b)),
+        ("shl", blaster.shl(a, b)),
+        ("lshr", blaster.lshr(a, b)),
+        ("ashr", blaster.ashr(a, b)),
+        ("shl_const", blaster.shl_const(a, 1)),
+        ("ashr_const", blaster.ashr_const(a, 2)),
+        ("zero_extend", blaster.zero_extend(a, WIDTH + 2)),
+        ("sign_extend", blaster.sign_extend(a, WIDTH + 2)),
+        ("extract", a.extract(1..3)),
+        ("concat", a.concat(b)),
+    ];
+    let lits = vec![
+        ("eq", blaster.eq(a, b)),
+        ("ult", blaster.ult(a, b)),
+        ("ule", blaster.ule(a, b)),
+        ("slt", blaster.slt(a, b)),
+        ("sle", blaster.sle(a, b)),
+    ];
+    Results { vectors, lits }
+}
+
+/// Returns the expected value of the named operation.
+fn expected_vector(name: &str, a: u128, b: u128) -> u128 {
+    let ext = (1 << (WIDTH + 2)) - 1;
+    match name {
+        "and" => a & b,
+        "or" => a | b,
+        "xor" => a ^ b,
+        "not" => !a & MASK,
+        "add" => (a + b) & MASK,
+        "sub" => a.wrapping_sub(b) & MASK,
+        "neg" => neg(a),
+        "mul" => (a * b) & MASK,
+        "udiv" => udiv_rem(a, b).0,
+        "urem" => udiv_rem(a, b).1,
+        "sdiv" => sdiv_rem(a, b).0,
+        "srem" => sdiv_rem(a, b).1,
+        "shl" => if b >= WIDTH as u128 { 0 } else { (a << b) & MASK },
+        "lshr" => if b >= WIDTH as u128 { 0 } else { a >> b },
+        "ashr" => (signed(a) >> b.min(WIDTH as u128 - 1)) as u128 & MASK,
+        "shl_const" => (a << 1) & MASK,
+        "ashr_const" => (signed(a) >> 2) as u128 & MASK,
+        "zero_extend" => a,
+        "sign_extend" => signed(a) as u128 & ext,
+        "extract" => a >> 1 & 3,
+        "concat" => a | b << WIDTH,
+        _ => unreachable!(),
+    }
+}
+
+/// Returns the expected value of the named comparison.
+fn expected_lit(name: &str, a: u128, b: u128) -> bool {
+    match name {
+        "eq" => a == b,
+        "ult" => a < b,
+        "ule" => a <= b,
+        "slt" => signed(a) < signed(b),
+        "sle" => signed(a) <= signed(b),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn operations_match_reference() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let (a, b, results) = {
+        let mut blaster = BitBlaster::new(&mut solver, &mut vars);
+        let a = blaster.fresh(WIDTH);
+        let b = blaster.fresh(WIDTH);
+        let results = blast_all(&mut blaster, &a, &b);
+        (a, b, results)
+    };
+    let max_var = vars.max_var().unwrap();
+    for x in 0..=MASK {
+        for y in 0..=MASK {
+            for (n, (&a, &b)) in a.bits().iter().zip(b.bits()).enumerate() {
+                solver.assume(if x >> n & 1 == 1 { a } else { !a });
+                solver.assume(if y >> n & 1 == 1 { b } else { !b });
+            }
+            assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+            let model = Model::from_solver(&mut solver, max_var).unwrap();
+            assert_eq!(a.value(&model), Some(x));
+            for (name, result) in &results.vectors {
+                assert_eq!(result.value(&model), Some(expected_vector(name, x, y)), "{} of {} and {}", name, x, y);
+            }
+            for &(name, result) in &results.lits {
+                assert_eq!(model.value(result), LitValue::from(expected_lit(name, x, y)), "{} of {} and {}", name, x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn constants_are_folded() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let mut blaster = BitBlaster::new(&mut solver, &mut vars);
+    let true_lit = blaster.true_lit();
+    let value = |vector: &BitVec| {
+        vector.bits().iter().enumerate().fold(0, |value, (n, &bit)| {
+            assert!(bit.var() == true_lit.var(), "{:?} is not constant", bit);
+            value | u128::from(bit == true_lit) << n
+        })
+    };
+    for x in 0..=MASK {
+        for y in 0..=MASK {
+            let a = blaster.constant(WIDTH, x);
+            let b = blaster.constant(WIDTH, y);
+            let results = blast_all(&mut blaster, &a, &b);
+            for (name, result) in &results.vectors {
+                assert_eq!(value(result), expected_vector(name, x, y), "{} of {} and {}", name, x, y);
+            }
+            for &(name, result) in &results.lits {
+                assert_eq!(result, blaster.lit(expected_lit(name, x, y)), "{} of {} and {}", name, x, y);
+            }
+        }
+    }
+    assert_eq!(solver.clauses().count(), 1);
+    assert_eq!(vars.num_vars(), 1);
+}
+
+#[test]
+fn decode_signed_values() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let mut blaster = BitBlaster::new(&mut solver, &mut vars);
+    let a = blaster.fresh(WIDTH);
+    let negative = blaster.signed_constant(WIDTH, -3);
+    let equal = blaster.eq(&a, &negative);
+    blaster.assert(equal);
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    let model = Model::from_solver(&mut solver, vars.max_var().unwrap()).unwrap();
+    assert_eq!(a.value(&model), Some(13));
+    assert_eq!(a.signed_value(&model), Some(-3));
+    assert_eq!(a.extract(0..3).signed_value(&model), Some(-3));
+    assert_eq!(a.extract(0..2).signed_value(&model), Some(1));
+    assert_eq!(a.value(&Model::new()), None);
+}
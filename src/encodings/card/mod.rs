@@ -0,0 +1,133 @@
+//! Encodings of cardinality constraints by sorting and cardinality networks.
+//!
+//! The networks sort their input literals into unary outputs where output `i`
+//! is `true` if at least `i + 1` inputs are. Only the comparators and clauses
+//! needed for the requested outputs and relation are added, and unit propagation
+//! on the resulting clauses maintains generalized arc consistency.
+
+mod network;
+
+#[cfg(test)]
+mod tests;
+
+pub use super::pb::Relation;
+use crate::{
+    Lit,
+    Model,
+    LitValue,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// The network used to encode cardinality constraints.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CardNetwork {
+    /// The cardinality network of Asín et al. that merges sorted blocks
+    /// of the bound's size with simplified mergers keeping only the top half.
+    #[default]
+    Cardinality,
+    /// Batcher's odd-even merge sorting network.
+    OddEvenMerge,
+    /// Parberry's pairwise sorting network.
+    Pairwise,
+}
+
+/// Adds the network over the literals and returns its first unary outputs.
+///
+/// Output `i` is implied by at least `i + 1` true literals if the relation is
+/// `AtMost`, implies at least `i + 1` true literals if the relation is `AtLeast`
+/// and both if the relation is `Equal`. At most as many outputs as literals
+/// are returned.
+///
+/// The variables of the literals are reserved in the variable manager
+/// before auxiliary variables are taken from it.
+pub fn sort<S>(
+    solver: &mut S,
+    vars: &mut VarManager,
+    lits: &[Lit],
+    num_outputs: usize,
+    network: CardNetwork,
+    relation: Relation,
+) -> Vec<Lit>
+where
+    S: IpasirSolver,
+{
+    for lit in lits {
+        vars.reserve(lit.var());
+    }
+    let num_outputs = num_outputs.min(lits.len());
+    if num_outputs == 0 {
+        return Vec::new()
+    }
+    network::encode(solver, vars, lits, num_outputs, network, relation)
+}
+
+/// A cardinality constraint `sum(lits) <relation> bound`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardConstraint {
+    /// The counted literals.
+    pub lits: Vec<Lit>,
+    /// The relation between the number of true literals and the bound.
+    pub relation: Relation,
+    /// The bound.
+    pub bound: usize,
+}
+
+impl CardConstraint {
+    /// Creates a new constraint.
+    pub fn new<I, L>(lits: I, relation: Relation, bound: usize) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        Self {
+            lits: lits.into_iter().map(Into::into).collect(),
+            relation,
+            bound,
+        }
+    }
+
+    /// Returns `true` if the model satisfies the constraint.
+    ///
+    /// Literals that are not `true` in the model count as `false`.
+    pub fn is_satisfied(&self, model: &Model) -> bool {
+        let count = self.lits.iter().filter(|&&lit| model.value(lit) == LitValue::True).count();
+        match self.relation {
+            Relation::AtMost => count <= self.bound,
+            Relation::AtLeast => count >= self.bound,
+            Relation::Equal => count == self.bound,
+        }
+    }
+
+    /// Adds clauses enforcing the constraint to the solver.
+    ///
+    /// Constraints that are always satisfied add no clauses and
+    /// constraints that cannot be satisfied add the empty clause.
+    pub fn encode<S>(&self, solver: &mut S, vars: &mut VarManager, network: CardNetwork)
+    where
+        S: IpasirSolver,
+    {
+        let len = self.lits.len();
+        let upper = self.relation != Relation::AtLeast && self.bound < len;
+        let lower = self.relation != Relation::AtMost && self.bound > 0;
+        if self.relation != Relation::AtMost && self.bound > len {
+            solver.add_clause(core::iter::empty::<Lit>());
+            return
+        }
+        let num_outputs = if upper { self.bound + 1 } else { self.bound };
+        let relation = match (upper, lower) {
+            (false, false) => return,
+            (true, false) => Relation::AtMost,
+            (false, true) => Relation::AtLeast,
+            (true, true) => Relation::Equal,
+        };
+        let outputs = sort(solver, vars, &self.lits, num_outputs, network, relation);
+        if upper {
+            solver.add_clause(Some(!outputs[self.bound]));
+        }
+        if lower {
+            solver.add_clause(Some(outputs[self.bound - 1]));
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use super::{
+    CardNetwork,
+    Relation,
+};
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// A comparator writing the maximum of two wires to its first
+/// and the minimum to its second wire.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Comparator {
+    /// The wire of the maximum.
+    max: usize,
+    /// The wire of the minimum.
+    min: usize,
+    /// `true` if the maximum is used later.
+    use_max: bool,
+    /// `true` if the minimum is used later.
+    use_min: bool,
+}
+
+/// Records the comparators of a network over numbered wires.
+#[derive(Debug, Default)]
+struct Network {
+    /// The comparators in order.
+    comparators: Vec<Comparator>,
+}
+
+impl Network {
+    /// Adds a comparator of the two wires.
+    fn compare(&mut self, max: usize, min: usize) {
+        self.comparators.push(Comparator { max, min, use_max: true, use_min: true });
+    }
+
+    /// Adds Batcher's odd-even merge sort of the wires, whose number must be a
+    /// power of two, and returns them ordered by decreasing values.
+    fn odd_even_sort(&mut self, wires: &[usize]) -> Vec<usize> {
+        if wires.len() <= 1 {
+            return wires.to_vec()
+        }
+        let (left, right) = wires.split_at(wires.len() / 2);
+        let left = self.odd_even_sort(left);
+        let right = self.odd_even_sort(right);
+        self.odd_even_merge(&left, &right)
+    }
+
+    /// Adds Batcher's odd-even merge of two sorted sequences of the same length
+    /// which must be a power of two and returns the merged sequence.
+    fn odd_even_merge(&mut self, a: &[usize], b: &[usize]) -> Vec<usize> {
+        if a.len() == 1 {
+            self.compare(a[0], b[0]);
+            return vec![a[0], b[0]]
+        }
+        let evens = self.odd_even_merge(&every_other(a, 0), &every_other(b, 0));
+        let odds = self.odd_even_merge(&every_other(a, 1), &every_other(b, 1));
+        self.interleave(&evens, &odds, evens.len() - 1, true)
+    }
+
+    /// Returns `evens[0]` followed by the compared pairs of `evens[i + 1]` and `odds[i]`
+    /// for all `i` below `num_pairs`, and the last of `odds` if requested.
+    fn interleave(&mut self, evens: &[usize], odds: &[usize], num_pairs: usize, last: bool) -> Vec<usize> {
+        let mut merged = Vec::with_capacity(2 * num_pairs + 2);
+        merged.push(evens[0]);
+        for i in 0..num_pairs {
+            self.compare(evens[i + 1], odds[i]);
+            merged.push(evens[i + 1]);
+            merged.push(odds[i]);
+        }
+        if last {
+            merged.push(odds[odds.len() - 1]);
+        }
+        merged
+    }
+
+    /// Adds the simplified merge of Asín et al. of two sorted sequences of the same
+    /// length `k`, a power of two, and returns the largest `k + 1` values in order.
+    fn simplified_merge(&mut self, a: &[usize], b: &[usize]) -> Vec<usize> {
+        if a.len() == 1 {
+            self.compare(a[0], b[0]);
+            return vec![a[0], b[0]]
+        }
+        let evens = self.simplified_merge(&every_other(a, 0), &every_other(b, 0));
+        let odds = self.simplified_merge(&every_other(a, 1), &every_other(b, 1));
+        self.interleave(&evens, &odds, a.len() / 2, false)
+    }
+
+    /// Adds the cardinality network of Asín et al. over the wires, whose number must
+    /// be a multiple of `k`, a power of two, and returns the largest `k` values in order.
+    fn cardinality(&mut self, wires: &[usize], k: usize) -> Vec<usize> {
+        if wires.len() == k {
+            return self.odd_even_sort(wires)
+        }
+        let first = self.cardinality(&wires[..k], k);
+        let rest = self.cardinality(&wires[k..], k);
+        let mut merged = self.simplified_merge(&first, &rest);
+        merged.truncate(k);
+        merged
+    }
+
+    /// Adds Parberry's pairwise sorting network of the wires `0..n`, where `n`
+    /// must be a power of two, leaving them ordered by decreasing values.
+    fn pairwise_sort(&mut self, n: usize) {
+        let mut a = 1;
+        while a < n {
+            let mut b = a;
+            let mut c = 0;
+            while b < n {
+                self.compare(b - a, b);
+                b += 1;
+                c = (c + 1) % a;
+                if c == 0 {
+                    b += a;
+                }
+            }
+            a *= 2;
+        }
+        a /= 4;
+        let mut e = 1;
+        while a > 0 {
+            let mut d = e;
+            while d > 0 {
+                let mut b = (d + 1) * a;
+                let mut c = 0;
+                while b < n {
+                    self.compare(b - d * a, b);
+                    b += 1;
+                    c = (c + 1) % a;
+                    if c == 0 {
+                        b += a;
+                    }
+                }
+                d /= 2;
+            }
+            a /= 2;
+            e = e * 2 + 1;
+        }
+    }
+
+    /// Drops comparators whose outputs do not influence the given wires.
+    fn prune(&mut self, outputs: &[usize], num_wires: usize) {
+        let mut used = vec![false; num_wires];
+        for &wire in outputs {
+            used[wire] = true;
+        }
+        for comparator in self.comparators.iter_mut().rev() {
+            comparator.use_max = used[comparator.max];
+            comparator.use_min = used[comparator.min];
+            if comparator.use_max || comparator.use_min {
+                used[comparator.max] = true;
+                used[comparator.min] = true;
+            }
+        }
+        self.comparators.retain(|comparator| comparator.use_max || comparator.use_min);
+    }
+}
+
+/// Returns every other wire starting at the given offset.
+fn every_other(wires: &[usize], offset: usize) -> Vec<usize> {
+    wires.iter().skip(offset).step_by(2).cloned().collect()
+}
+
+/// Adds a half or full comparator of two literals and returns the maximum and minimum.
+///
+/// `AtMost` needs `a | b -> max` and `a & b -> min`, `AtLeast` needs
+/// `max -> a | b` and `min -> a & b` and `Equal` needs both directions.
+fn compare<S>(
+    solver: &mut S,
+    vars: &mut VarManager,
+    relation: Relation,
+    (a, b): (Lit, Lit),
+    (use_max, use_min): (bool, bool),
+) -> (Option<Lit>, Option<Lit>)
+where
+    S: IpasirSolver,
+{
+    let upwards = relation != Relation::AtLeast;
+    let downwards = relation != Relation::AtMost;
+    let max = if use_max { Some(vars.fresh_lit()) } else { None };
+    let min = if use_min { Some(vars.fresh_lit()) } else { None };
+    if let Some(max) = max {
+        if upwards {
+            solver.add_clause(vec![!a, max]);
+            solver.add_clause(vec![!b, max]);
+        }
+        if downwards {
+            solver.add_clause(vec![!max, a, b]);
+        }
+    }
+    if let Some(min) = min {
+        if upwards {
+            solver.add_clause(vec![!a, !b, min]);
+        }
+        if downwards {
+            solver.add_clause(vec![!min, a]);
+            solver.add_clause(vec![!min, b]);
+        }
+    }
+    (max, min)
+}
+
+/// Adds the network over the literals and returns the largest `num_outputs` values in order.
+///
+/// `num_outputs` must be positive and at most the number of literals.
+pub(super) fn encode<S>(
+    solver: &mut S,
+    vars: &mut VarManager,
+    lits: &[Lit],
+    num_outputs: usize,
+    network: CardNetwork,
+    relation: Relation,
+) -> Vec<Lit>
+where
+    S: IpasirSolver,
+{
+    let mut recorder = Network::default();
+    let (num_wires, outputs) = match network {
+        CardNetwork::Cardinality => {
+            let k = num_outputs.next_power_of_two();
+            let num_wires = lits.len().div_ceil(k) * k;
+            let wires: Vec<usize> = (0..num_wires).collect();
+            (num_wires, recorder.cardinality(&wires, k))
+        }
+        CardNetwork::OddEvenMerge => {
+            let num_wires = lits.len().next_power_of_two();
+            let wires: Vec<usize> = (0..num_wires).collect();
+            (num_wires, recorder.odd_even_sort(&wires))
+        }
+        CardNetwork::Pairwise => {
+            let num_wires = lits.len().next_power_of_two();
+            recorder.pairwise_sort(num_wires);
+            (num_wires, (0..num_wires).collect())
+        }
+    };
+    let outputs = &outputs[..num_outputs];
+    recorder.prune(outputs, num_wires);
+    // Padding wires are constant `false` which comparators pass through.
+    let mut wires: Vec<Option<Lit>> = lits.iter().cloned().map(Some).collect();
+    wires.resize(num_wires, None);
+    for comparator in &recorder.comparators {
+        let (max, min) = match (wires[comparator.max], wires[comparator.min]) {
+            (Some(a), Some(b)) => {
+                let uses = (comparator.use_max, comparator.use_min);
+                compare(solver, vars, relation, (a, b), uses)
+            }
+            (a, b) => (a.or(b), None),
+        };
+        wires[comparator.max] = max;
+        wires[comparator.min] = min;
+    }
+    outputs
+        .iter()
+        .map(|&wire| wires[wire].expect("outputs within the number of literals are never constant"))
+        .collect()
+}
@@ -0,0 +1,129 @@
+use super::*;
+use crate::{
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+
+/// All networks.
+const NETWORKS: [CardNetwork; 3] = [CardNetwork::Cardinality, CardNetwork::OddEvenMerge, CardNetwork::Pairwise];
+
+/// Returns the literals of the first `len` variables with mixed polarities.
+fn lits(len: i32) -> Vec<Lit> {
+    (1..=len).map(|var| lit(if var % 3 == 0 { -var } else { var })).collect()
+}
+
+#[test]
+fn networks_sort_inputs() {
+    for &network in &NETWORKS {
+        for len in 1..=9 {
+            for num_outputs in 1..=len as usize {
+                let lits = lits(len);
+                let mut solver = TestSolver::init();
+                let mut vars = VarManager::new();
+                let outputs = sort(&mut solver, &mut vars, &lits, num_outputs, network, Relation::Equal);
+                assert_eq!(outputs.len(), num_outputs);
+                for bits in 0u32..1 << len {
+                    for (n, &lit) in lits.iter().enumerate() {
+                        solver.assume(if bits >> n & 1 == 1 { lit } else { !lit });
+                    }
+                    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+                    for (n, &output) in outputs.iter().enumerate() {
+                        let expected = LitValue::from(bits.count_ones() as usize > n);
+                        assert_eq!(solver.val(output), Ok(expected), "{:?} output {} under {:b}", network, n, bits);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn constraints_are_exact() {
+    let lits = lits(5);
+    for &network in &NETWORKS {
+        for &relation in &[Relation::AtMost, Relation::AtLeast, Relation::Equal] {
+            for bound in 0..=6 {
+                let constraint = CardConstraint::new(lits.iter().cloned(), relation, bound);
+                let mut solver = TestSolver::init();
+                let mut vars = VarManager::new();
+                constraint.encode(&mut solver, &mut vars, network);
+                for bits in 0u32..1 << lits.len() {
+                    let assignment: Vec<Lit> = lits
+                        .iter()
+                        .enumerate()
+                        .map(|(n, &lit)| if bits >> n & 1 == 1 { lit } else { !lit })
+                        .collect();
+                    for &lit in &assignment {
+                        solver.assume(lit);
+                    }
+                    let satisfied = constraint.is_satisfied(&assignment.iter().cloned().collect::<Model>());
+                    let expected = if satisfied { SolveResponse::Sat } else { SolveResponse::Unsat };
+                    assert_eq!(solver.solve(), Ok(expected), "{:?} {:?} {} under {:b}", network, relation, bound, bits);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn propagation_is_arc_consistent() {
+    let lits = lits(5);
+    for &network in &NETWORKS {
+        for &relation in &[Relation::AtMost, Relation::AtLeast, Relation::Equal] {
+            for bound in 0..=5 {
+                let constraint = CardConstraint::new(lits.iter().cloned(), relation, bound);
+                let mut solver = TestSolver::init();
+                let mut vars = VarManager::new();
+                constraint.encode(&mut solver, &mut vars, network);
+                let satisfiable = |count: usize, open: usize| (count..=count + open).any(|count| match relation {
+                    Relation::AtMost => count <= bound,
+                    Relation::AtLeast => count >= bound,
+                    Relation::Equal => count == bound,
+                });
+                // Every input is unassigned, `true` or `false`.
+                for code in 0..3usize.pow(lits.len() as u32) {
+                    let states: Vec<usize> = (0..lits.len()).map(|n| code / 3usize.pow(n as u32) % 3).collect();
+                    let assumed: Vec<Lit> = lits
+                        .iter()
+                        .zip(&states)
+                        .filter_map(|(&lit, &state)| match state {
+                            1 => Some(lit),
+                            2 => Some(!lit),
+                            _ => None,
+                        })
+                        .collect();
+                    let count = states.iter().filter(|&&state| state == 1).count();
+                    let open = states.iter().filter(|&&state| state == 0).count();
+                    let propagated = solver.propagate(&assumed);
+                    let context = (network, relation, bound, &assumed);
+                    if !satisfiable(count, open) {
+                        assert_eq!(propagated, None, "{:?}", context);
+                        continue
+                    }
+                    let propagated = propagated.unwrap_or_else(|| panic!("conflict for {:?}", context));
+                    for (&lit, _) in lits.iter().zip(&states).filter(|(_, &state)| state == 0) {
+                        if !satisfiable(count + 1, open - 1) {
+                            assert!(propagated.contains(&!lit), "{:?} does not force {:?}", context, !lit);
+                        }
+                        if !satisfiable(count, open - 1) {
+                            assert!(propagated.contains(&lit), "{:?} does not force {:?}", context, lit);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cardinality_network_is_smaller_for_small_bounds() {
+    let lits = lits(16);
+    let mut sizes = Vec::new();
+    for &network in &NETWORKS {
+        let mut solver = TestSolver::init();
+        let mut vars = VarManager::new();
+        sort(&mut solver, &mut vars, &lits, 2, network, Relation::AtMost);
+        sizes.push(solver.clauses().count());
+    }
+    assert!(sizes[0] < sizes[1], "{:?}", sizes);
+}
@@ -0,0 +1,11 @@
+//! Encodings of constraints into clauses.
+//!
+//! All encodings add their clauses to an `IpasirSolver`
+//! and take auxiliary variables from a `VarManager`.
+
+pub mod amo;
+pub mod bitvec;
+pub mod card;
+pub mod pb;
+pub mod totalizer;
+pub mod xor;
@@ -0,0 +1,113 @@
+use super::AtMost;
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::{
+    collections::VecDeque,
+    vec::Vec,
+};
+
+/// Returns the sum bit of a full adder or a half adder if `c` is `None`.
+fn sum<S>(solver: &mut S, vars: &mut VarManager, a: Lit, b: Lit, c: Option<Lit>) -> Lit
+where
+    S: IpasirSolver,
+{
+    let out = vars.fresh_lit();
+    let inputs: Vec<Lit> = [a, b].iter().cloned().chain(c).collect();
+    // Every assignment of the inputs fixes the output to their parity.
+    for bits in 0u32..1 << inputs.len() {
+        let mut clause: Vec<Lit> = inputs
+            .iter()
+            .enumerate()
+            .map(|(n, &lit)| if bits >> n & 1 == 1 { !lit } else { lit })
+            .collect();
+        clause.push(if bits.count_ones() % 2 == 1 { out } else { !out });
+        solver.add_clause(clause);
+    }
+    out
+}
+
+/// Returns the carry bit of a full adder or a half adder if `c` is `None`.
+fn carry<S>(solver: &mut S, vars: &mut VarManager, a: Lit, b: Lit, c: Option<Lit>) -> Lit
+where
+    S: IpasirSolver,
+{
+    let out = vars.fresh_lit();
+    match c {
+        None => {
+            solver.add_clause(vec![!out, a]);
+            solver.add_clause(vec![!out, b]);
+            solver.add_clause(vec![out, !a, !b]);
+        }
+        Some(c) => {
+            for &(x, y) in &[(a, b), (a, c), (b, c)] {
+                solver.add_clause(vec![out, !x, !y]);
+                solver.add_clause(vec![!out, x, y]);
+            }
+        }
+    }
+    out
+}
+
+/// Encodes the simplified constraint as adder network.
+///
+/// The bits of all coefficients are collected in buckets by position and
+/// reduced to one bit per position by full and half adders whose carries
+/// go to the next position. The resulting binary sum is then compared
+/// with the bound.
+pub(super) fn encode<S>(solver: &mut S, vars: &mut VarManager, constraint: AtMost)
+where
+    S: IpasirSolver,
+{
+    let mut buckets: Vec<VecDeque<Lit>> = Vec::new();
+    for &(coef, lit) in &constraint.terms {
+        let width = (128 - coef.leading_zeros()) as usize;
+        if buckets.len() < width {
+            buckets.resize(width, VecDeque::new());
+        }
+        for (position, bucket) in buckets.iter_mut().enumerate().take(width) {
+            if coef >> position & 1 == 1 {
+                bucket.push_back(lit);
+            }
+        }
+    }
+    let mut bits = Vec::new();
+    let mut position = 0;
+    while position < buckets.len() {
+        while buckets[position].len() > 1 {
+            let a = buckets[position].pop_front().unwrap();
+            let b = buckets[position].pop_front().unwrap();
+            let c = if buckets[position].is_empty() { None } else { buckets[position].pop_front() };
+            let sum = sum(solver, vars, a, b, c);
+            let carry = carry(solver, vars, a, b, c);
+            buckets[position].push_back(sum);
+            if buckets.len() == position + 1 {
+                buckets.push(VecDeque::new());
+            }
+            buckets[position + 1].push_back(carry);
+        }
+        bits.push(buckets[position].pop_front());
+        position += 1;
+    }
+    // The sum exceeds the bound if it has a bit set where the bound has not
+    // and all higher bits set where the bound has.
+    let bound = constraint.bound as u128;
+    'positions: for (position, &bit) in bits.iter().enumerate() {
+        let bit = match bit {
+            Some(bit) if position >= 128 || bound >> position & 1 == 0 => bit,
+            _ => continue,
+        };
+        let mut clause = vec![!bit];
+        for (higher, &higher_bit) in bits.iter().enumerate().skip(position + 1) {
+            if higher < 128 && bound >> higher & 1 == 1 {
+                match higher_bit {
+                    Some(higher_bit) => clause.push(!higher_bit),
+                    None => continue 'positions,
+                }
+            }
+        }
+        solver.add_clause(clause);
+    }
+}
@@ -0,0 +1,143 @@
+use super::AtMost;
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+/// A node of a decision diagram.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Node {
+    /// The constant `true` node.
+    True,
+    /// The constant `false` node.
+    False,
+    /// A node implied by its literal.
+    Lit(Lit),
+}
+
+/// A level of a decision diagram counting the true literals of a group of
+/// terms with the same coefficient.
+///
+/// The unary count has one literal per possible count from `1` on which is
+/// implied by at least that many true literals, so a single term is counted
+/// by its own literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Level {
+    /// The coefficient of the terms.
+    pub coef: u128,
+    /// The unary count of the true literals.
+    pub count: Vec<Lit>,
+}
+
+/// Builds the decision diagram of a normalized constraint.
+///
+/// The function `sum(levels[i..]) <= bound` is the same for a whole interval
+/// of bounds, so every node is stored with the interval it stands for and
+/// shared by all bounds in it.
+struct Builder<'a, S> {
+    /// The solver receiving the clauses.
+    solver: &'a mut S,
+    /// The source of auxiliary variables.
+    vars: &'a mut VarManager,
+    /// The levels ordered by decreasing coefficients.
+    levels: Vec<Level>,
+    /// The greatest sums of all levels from each position on.
+    rest: Vec<i128>,
+    /// The nodes with the upper end of their interval by level position
+    /// and lower end of their interval.
+    nodes: Vec<BTreeMap<i128, (i128, Node)>>,
+}
+
+impl<'a, S> Builder<'a, S>
+where
+    S: IpasirSolver,
+{
+    /// Returns the node for `sum(levels[i..]) <= bound` and the
+    /// interval of bounds for which it is the same function.
+    fn node(&mut self, i: usize, bound: i128) -> (i128, i128, Node) {
+        if bound < 0 {
+            return (i128::MIN, -1, Node::False)
+        }
+        if self.rest[i] <= bound {
+            return (self.rest[i], i128::MAX, Node::True)
+        }
+        if let Some((&lower, &(upper, node))) = self.nodes[i].range(..=bound).next_back() {
+            if bound <= upper {
+                return (lower, upper, node)
+            }
+        }
+        let coef = self.levels[i].coef as i128;
+        let mut lower = i128::MIN;
+        let mut upper = i128::MAX;
+        let mut children = Vec::new();
+        for count in 0..=self.levels[i].count.len() {
+            let offset = coef * count as i128;
+            let (child_lower, child_upper, child) = self.node(i + 1, bound - offset);
+            lower = lower.max(child_lower.saturating_add(offset));
+            upper = upper.min(child_upper.saturating_add(offset));
+            children.push(child);
+            if child == Node::False {
+                break
+            }
+        }
+        let node = if children.iter().all(|&child| child == children[0]) {
+            children[0]
+        } else {
+            let out = self.vars.fresh_lit();
+            for (count, &child) in children.iter().enumerate() {
+                // Reaching the count requires the child.
+                let reached = count.checked_sub(1).map(|k| !self.levels[i].count[k]);
+                match child {
+                    Node::True => (),
+                    Node::False => self.solver.add_clause(Some(!out).into_iter().chain(reached)),
+                    Node::Lit(child) => self.solver.add_clause(vec![!out, child].into_iter().chain(reached)),
+                }
+            }
+            Node::Lit(out)
+        };
+        self.nodes[i].insert(lower, (upper, node));
+        (lower, upper, node)
+    }
+}
+
+/// Encodes `sum(levels) <= bound` as decision diagram.
+pub(super) fn encode_levels<S>(solver: &mut S, vars: &mut VarManager, mut levels: Vec<Level>, bound: i128)
+where
+    S: IpasirSolver,
+{
+    levels.sort_by_key(|level| core::cmp::Reverse(level.coef));
+    let mut rest = Vec::with_capacity(levels.len() + 1);
+    rest.push(0i128);
+    for level in levels.iter().rev() {
+        let max = (level.coef as i128).saturating_mul(level.count.len() as i128);
+        let sum = rest[rest.len() - 1].saturating_add(max);
+        rest.push(sum);
+    }
+    rest.reverse();
+    let mut builder = Builder {
+        solver,
+        vars,
+        nodes: vec![BTreeMap::new(); levels.len()],
+        levels,
+        rest,
+    };
+    match builder.node(0, bound).2 {
+        Node::True => (),
+        Node::False => builder.solver.add_clause(core::iter::empty::<Lit>()),
+        Node::Lit(root) => builder.solver.add_clause(Some(root)),
+    }
+}
+
+/// Encodes the simplified constraint as binary decision diagram.
+pub(super) fn encode<S>(solver: &mut S, vars: &mut VarManager, constraint: AtMost)
+where
+    S: IpasirSolver,
+{
+    let levels = constraint.terms.into_iter().map(|(coef, lit)| Level { coef, count: Vec::from([lit]) });
+    encode_levels(solver, vars, levels.collect(), constraint.bound)
+}
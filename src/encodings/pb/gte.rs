@@ -0,0 +1,70 @@
+use super::AtMost;
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+/// The outputs of a node of the generalized totalizer by partial sum.
+///
+/// Sums above the bound are all represented by `bound + 1`.
+type Outputs = BTreeMap<u128, Lit>;
+
+/// Returns the outputs of the node summing both children.
+///
+/// Every combination of partial sums of the children implies the output
+/// of their sum.
+fn merge<S>(solver: &mut S, vars: &mut VarManager, left: &Outputs, right: &Outputs, limit: u128) -> Outputs
+where
+    S: IpasirSolver,
+{
+    let mut outputs = Outputs::new();
+    let zero = Some((0, None));
+    let left_sums = zero.into_iter().chain(left.iter().map(|(&sum, &lit)| (sum, Some(lit))));
+    for (left_sum, left_lit) in left_sums {
+        let right_sums = zero.into_iter().chain(right.iter().map(|(&sum, &lit)| (sum, Some(lit))));
+        for (right_sum, right_lit) in right_sums {
+            if left_sum + right_sum == 0 {
+                continue
+            }
+            let sum = (left_sum + right_sum).min(limit);
+            let output = *outputs.entry(sum).or_insert_with(|| vars.fresh_lit());
+            let clause: Vec<Lit> = left_lit.into_iter().chain(right_lit).map(|lit| !lit).chain(Some(output)).collect();
+            solver.add_clause(clause);
+        }
+    }
+    outputs
+}
+
+/// Encodes the simplified constraint as generalized totalizer.
+///
+/// The terms are the leaves of a balanced binary tree whose root must not
+/// reach a sum above the bound.
+pub(super) fn encode<S>(solver: &mut S, vars: &mut VarManager, constraint: AtMost)
+where
+    S: IpasirSolver,
+{
+    let limit = constraint.bound as u128 + 1;
+    let mut layer: Vec<Outputs> = constraint.terms
+        .iter()
+        .map(|&(coef, lit)| Some((coef, lit)).into_iter().collect())
+        .collect();
+    while layer.len() > 1 {
+        let mut next = Vec::with_capacity(layer.len().div_ceil(2));
+        for pair in layer.chunks(2) {
+            match pair {
+                [left, right] => next.push(merge(solver, vars, left, right, limit)),
+                [single] => next.push(single.clone()),
+                _ => unreachable!(),
+            }
+        }
+        layer = next;
+    }
+    if let Some(&output) = layer.first().and_then(|root| root.get(&limit)) {
+        solver.add_clause(Some(!output));
+    }
+}
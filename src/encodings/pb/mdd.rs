@@ -0,0 +1,48 @@
+use super::{
+    bdd::{
+        self,
+        Level,
+    },
+    AtMost,
+    Relation,
+};
+use crate::{
+    encodings::card::{
+        self,
+        CardNetwork,
+    },
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+/// Encodes the simplified constraint as multi-valued decision diagram.
+///
+/// The terms are grouped by their coefficients and every group becomes one
+/// level branching on how many of its literals are `true`. The count is
+/// sorted by a cardinality network up to the first count exceeding the bound.
+pub(super) fn encode<S>(solver: &mut S, vars: &mut VarManager, constraint: AtMost)
+where
+    S: IpasirSolver,
+{
+    let mut groups: BTreeMap<u128, Vec<Lit>> = BTreeMap::new();
+    for (coef, lit) in constraint.terms {
+        groups.entry(coef).or_default().push(lit);
+    }
+    let bound = constraint.bound as u128;
+    let mut levels = Vec::with_capacity(groups.len());
+    for (coef, lits) in groups {
+        let count = if lits.len() == 1 {
+            lits
+        } else {
+            let num_outputs = (bound / coef + 1).min(lits.len() as u128) as usize;
+            card::sort(solver, vars, &lits, num_outputs, CardNetwork::Cardinality, Relation::AtMost)
+        };
+        levels.push(Level { coef, count });
+    }
+    bdd::encode_levels(solver, vars, levels, constraint.bound)
+}
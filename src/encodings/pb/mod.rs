@@ -0,0 +1,234 @@
+//! Encodings of linear pseudo-Boolean constraints.
+//!
+//! Constraints are normalized to `sum(coef * lit) <= bound` with positive
+//! coefficients and distinct variables before they are encoded. Literals whose
+//! coefficient exceeds the bound are fixed to `false`, the coefficients are
+//! divided by their greatest common divisor and trivially satisfied constraints
+//! add no clauses.
+
+mod adder;
+mod bdd;
+mod mdd;
+mod gte;
+mod swc;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Var,
+    Lit,
+    Sign,
+    Model,
+    LitValue,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+/// The relation of a pseudo-Boolean constraint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// The weighted sum is at most the right-hand side.
+    AtMost,
+    /// The weighted sum is at least the right-hand side.
+    AtLeast,
+    /// The weighted sum equals the right-hand side.
+    Equal,
+}
+
+/// The encoding of pseudo-Boolean constraints into clauses.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PbEncoding {
+    /// A reduced ordered binary decision diagram over the terms
+    /// ordered by decreasing coefficients, where nodes with equal
+    /// functions are shared by intervals of bounds.
+    #[default]
+    Bdd,
+    /// A multi-valued decision diagram with one level per coefficient which
+    /// branches on how many of the literals with that coefficient are `true`.
+    Mdd,
+    /// The generalized totalizer which sums the weights in a binary
+    /// tree of nodes with one output per reachable partial sum.
+    Gte,
+    /// A network of full and half adders computing the binary sum
+    /// which is compared with the bound.
+    Adder,
+    /// The sequential weight counter with unary registers of partial sums.
+    SequentialWeightCounter,
+}
+
+/// A linear pseudo-Boolean constraint `sum(coef * lit) <relation> rhs`
+/// where a literal counts `1` if it is `true` and `0` otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PbConstraint {
+    /// The weighted literals.
+    pub terms: Vec<(i64, Lit)>,
+    /// The relation between the weighted sum and the right-hand side.
+    pub relation: Relation,
+    /// The right-hand side.
+    pub rhs: i64,
+}
+
+/// A normalized constraint `sum(coef * lit) <= bound` with positive coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AtMost {
+    /// The weighted literals with positive coefficients and distinct variables.
+    pub terms: Vec<(u128, Lit)>,
+    /// The bound which is negative for infeasible constraints.
+    pub bound: i128,
+}
+
+/// Returns the greatest common divisor.
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+    a
+}
+
+impl AtMost {
+    /// Normalizes `sum(coef * lit) <= bound` for arbitrary coefficients.
+    ///
+    /// Terms over the same variable are merged.
+    fn new<I>(terms: I, mut bound: i128) -> Self
+    where
+        I: IntoIterator<Item = (i128, Lit)>,
+    {
+        let mut coefs: BTreeMap<Var, i128> = BTreeMap::new();
+        for (coef, lit) in terms {
+            let pos_coef = coefs.entry(lit.var()).or_insert(0);
+            if lit.sign() == Sign::Pos {
+                *pos_coef += coef;
+            } else {
+                // coef * !lit = coef - coef * lit
+                bound -= coef;
+                *pos_coef -= coef;
+            }
+        }
+        let mut normalized = Vec::new();
+        for (var, coef) in coefs {
+            let lit = var.lit(Sign::Pos);
+            if coef < 0 {
+                // coef * lit = coef + |coef| * !lit
+                bound -= coef;
+                normalized.push((coef.unsigned_abs(), !lit));
+            } else if coef > 0 {
+                normalized.push((coef as u128, lit));
+            }
+        }
+        Self { terms: normalized, bound }
+    }
+
+    /// Returns `true` if every assignment satisfies the constraint.
+    fn is_trivial(&self) -> bool {
+        let sum = self.terms.iter().fold(0u128, |sum, &(coef, _)| sum.saturating_add(coef));
+        self.bound >= 0 && sum <= self.bound as u128
+    }
+
+    /// Removes the terms whose coefficient exceeds the non-negative bound
+    /// and returns their literals which must be `false`.
+    ///
+    /// The remaining coefficients and the bound are divided by the greatest
+    /// common divisor of the coefficients, rounding the bound down.
+    fn simplify(&mut self) -> Vec<Lit> {
+        let bound = self.bound as u128;
+        let mut falsified = Vec::new();
+        self.terms.retain(|&(coef, lit)| {
+            if coef > bound {
+                falsified.push(lit);
+            }
+            coef <= bound
+        });
+        let divisor = self.terms.iter().fold(0, |divisor, &(coef, _)| gcd(divisor, coef));
+        if divisor > 1 {
+            for (coef, _) in &mut self.terms {
+                *coef /= divisor;
+            }
+            self.bound = (bound / divisor) as i128;
+        }
+        falsified
+    }
+}
+
+impl PbConstraint {
+    /// Creates a new constraint.
+    pub fn new<I, L>(terms: I, relation: Relation, rhs: i64) -> Self
+    where
+        I: IntoIterator<Item = (i64, L)>,
+        L: Into<Lit>,
+    {
+        Self {
+            terms: terms.into_iter().map(|(coef, lit)| (coef, lit.into())).collect(),
+            relation,
+            rhs,
+        }
+    }
+
+    /// Returns `true` if the model satisfies the constraint.
+    ///
+    /// Literals that are not `true` in the model count as `false`.
+    pub fn is_satisfied(&self, model: &Model) -> bool {
+        let sum: i128 = self.terms
+            .iter()
+            .filter(|(_, lit)| model.value(*lit) == LitValue::True)
+            .map(|(coef, _)| i128::from(*coef))
+            .sum();
+        let rhs = i128::from(self.rhs);
+        match self.relation {
+            Relation::AtMost => sum <= rhs,
+            Relation::AtLeast => sum >= rhs,
+            Relation::Equal => sum == rhs,
+        }
+    }
+
+    /// Returns the equivalent constraints in normalized form.
+    pub(crate) fn normalize(&self) -> Vec<AtMost> {
+        let terms = || self.terms.iter().map(|&(coef, lit)| (i128::from(coef), lit));
+        let at_most = || AtMost::new(terms(), i128::from(self.rhs));
+        let at_least = || AtMost::new(terms().map(|(coef, lit)| (-coef, lit)), -i128::from(self.rhs));
+        match self.relation {
+            Relation::AtMost => Vec::from([at_most()]),
+            Relation::AtLeast => Vec::from([at_least()]),
+            Relation::Equal => Vec::from([at_most(), at_least()]),
+        }
+    }
+
+    /// Adds clauses enforcing the constraint to the solver.
+    ///
+    /// The variables of the constraint are reserved in the variable manager
+    /// before auxiliary variables are taken from it. Infeasible constraints
+    /// add the empty clause.
+    pub fn encode<S>(&self, solver: &mut S, vars: &mut VarManager, encoding: PbEncoding)
+    where
+        S: IpasirSolver,
+    {
+        for (_, lit) in &self.terms {
+            vars.reserve(lit.var());
+        }
+        for mut constraint in self.normalize() {
+            if constraint.bound < 0 {
+                solver.add_clause(core::iter::empty::<Lit>());
+                continue
+            }
+            for lit in constraint.simplify() {
+                solver.add_clause(Some(!lit));
+            }
+            if constraint.is_trivial() {
+                continue
+            }
+            match encoding {
+                PbEncoding::Bdd => bdd::encode(solver, vars, constraint),
+                PbEncoding::Mdd => mdd::encode(solver, vars, constraint),
+                PbEncoding::Gte => gte::encode(solver, vars, constraint),
+                PbEncoding::Adder => adder::encode(solver, vars, constraint),
+                PbEncoding::SequentialWeightCounter => swc::encode(solver, vars, constraint),
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::AtMost;
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// Encodes the simplified constraint as sequential weight counter.
+///
+/// The register of every term but the last holds one literal per value up
+/// to the bound which is implied if the weighted sum of the terms up to it
+/// reaches that value. A term whose weight would push the previous register
+/// above the bound must be `false`.
+pub(super) fn encode<S>(solver: &mut S, vars: &mut VarManager, constraint: AtMost)
+where
+    S: IpasirSolver,
+{
+    let bound = constraint.bound as usize;
+    let terms = constraint.terms;
+    // The register of the previous term where `register[j]` means a sum of at least `j + 1`.
+    let mut register: Vec<Lit> = Vec::new();
+    for (i, &(coef, lit)) in terms.iter().enumerate() {
+        let coef = coef as usize;
+        if !register.is_empty() {
+            solver.add_clause(vec![!lit, !register[bound - coef]]);
+        }
+        if i + 1 == terms.len() {
+            break
+        }
+        let next: Vec<Lit> = (0..bound).map(|_| vars.fresh_lit()).collect();
+        for &output in &next[..coef] {
+            solver.add_clause(vec![!lit, output]);
+        }
+        for (j, &prev) in register.iter().enumerate() {
+            solver.add_clause(vec![!prev, next[j]]);
+            if j + coef < bound {
+                solver.add_clause(vec![!lit, !prev, next[j + coef]]);
+            }
+        }
+        register = next;
+    }
+}
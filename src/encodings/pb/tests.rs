@@ -0,0 +1,126 @@
+use super::*;
+use crate::{
+    Model,
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+
+/// Checks that the encoding admits exactly the assignments of the
+/// first `num_vars` variables that satisfy the constraint.
+fn check_exhaustive(constraint: &PbConstraint, num_vars: i32, encoding: PbEncoding) {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::with_max_var(Some(lit(num_vars).var()));
+    constraint.encode(&mut solver, &mut vars, encoding);
+    for bits in 0..1 << num_vars {
+        let lits: Vec<Lit> = (1..=num_vars)
+            .map(|var| if bits >> (var - 1) & 1 == 1 { lit(var) } else { lit(-var) })
+            .collect();
+        for &lit in &lits {
+            solver.assume(lit);
+        }
+        let expected = if constraint.is_satisfied(&lits.iter().cloned().collect::<Model>()) {
+            SolveResponse::Sat
+        } else {
+            SolveResponse::Unsat
+        };
+        assert_eq!(solver.solve(), Ok(expected), "{:?} under {:?}", constraint, lits);
+    }
+}
+
+/// All encodings.
+const ENCODINGS: [PbEncoding; 5] = [
+    PbEncoding::Bdd,
+    PbEncoding::Mdd,
+    PbEncoding::Gte,
+    PbEncoding::Adder,
+    PbEncoding::SequentialWeightCounter,
+];
+
+/// Returns constraints covering all relations, negative coefficients,
+/// complementary literals, common divisors and trivial cases.
+fn constraints() -> Vec<PbConstraint> {
+    let terms = vec![
+        vec![(3, lit(1)), (-2, lit(2)), (5, lit(-3)), (1, lit(4)), (2, lit(1))],
+        vec![(7, lit(1)), (4, lit(-2)), (6, lit(3)), (-3, lit(-1)), (9, lit(4)), (2, lit(5))],
+        vec![(4, lit(1)), (6, lit(2)), (-10, lit(3)), (8, lit(-4))],
+    ];
+    let mut constraints = Vec::new();
+    for terms in terms {
+        for &relation in &[Relation::AtMost, Relation::AtLeast, Relation::Equal] {
+            for rhs in -11..=20 {
+                constraints.push(PbConstraint::new(terms.clone(), relation, rhs));
+            }
+        }
+    }
+    constraints
+}
+
+#[test]
+fn encodings_are_exact() {
+    for &encoding in &ENCODINGS {
+        for constraint in constraints() {
+            check_exhaustive(&constraint, 5, encoding);
+        }
+    }
+}
+
+#[test]
+fn equal_coefficients_are_counted_together() {
+    let terms = vec![(2, lit(1)), (2, lit(2)), (-2, lit(3)), (3, lit(4)), (3, lit(5))];
+    for &relation in &[Relation::AtMost, Relation::AtLeast, Relation::Equal] {
+        for rhs in -3..=11 {
+            check_exhaustive(&PbConstraint::new(terms.clone(), relation, rhs), 5, PbEncoding::Mdd);
+        }
+    }
+}
+
+#[test]
+fn trivial_constraints_add_no_auxiliary_vars() {
+    let terms = vec![(3, lit(1)), (-2, lit(2)), (2, lit(-1))];
+    for &encoding in &ENCODINGS {
+        let mut solver = TestSolver::init();
+        let mut vars = VarManager::new();
+        PbConstraint::new(terms.clone(), Relation::AtMost, 3).encode(&mut solver, &mut vars, encoding);
+        PbConstraint::new(terms.clone(), Relation::AtLeast, -2).encode(&mut solver, &mut vars, encoding);
+        assert_eq!(solver.clauses().count(), 0);
+        PbConstraint::new(terms.clone(), Relation::AtLeast, 4).encode(&mut solver, &mut vars, encoding);
+        assert!(solver.clauses().any(|clause| clause.is_empty()));
+        assert_eq!(vars.num_vars(), 2);
+    }
+}
+
+#[test]
+fn large_coefficients_are_fixed_to_false() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let constraint = PbConstraint::new(vec![(9, lit(1)), (2, lit(2)), (2, lit(-3))], Relation::AtMost, 8);
+    constraint.encode(&mut solver, &mut vars, PbEncoding::Bdd);
+    assert_eq!(vars.num_vars(), 3);
+    let clauses: Vec<Vec<Lit>> = solver.clauses().map(|clause| clause.iter().collect()).collect();
+    assert_eq!(clauses, vec![vec![lit(-1)]]);
+}
+
+#[test]
+fn common_divisor_is_divided_out() {
+    let scaled = |factor: i64| {
+        let mut solver = TestSolver::init();
+        let mut vars = VarManager::new();
+        let terms = (1..=6).map(|var| (factor * i64::from(var), lit(var)));
+        PbConstraint::new(terms, Relation::AtMost, factor * 7 + factor - 1).encode(&mut solver, &mut vars, PbEncoding::SequentialWeightCounter);
+        (solver.clauses().count(), vars.num_vars())
+    };
+    assert_eq!(scaled(1000), scaled(1));
+}
+
+#[test]
+fn auxiliary_vars_follow_constraint_vars() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let constraint = PbConstraint::new(vec![(1, lit(1)), (2, lit(2)), (3, lit(5))], Relation::AtMost, 3);
+    for &encoding in &ENCODINGS {
+        constraint.encode(&mut solver, &mut vars, encoding);
+        assert!(vars.num_vars() > 5);
+        assert!(solver.clauses().flat_map(|clause| clause.iter()).all(|lit| lit.var().index() < vars.num_vars()));
+    }
+}
+
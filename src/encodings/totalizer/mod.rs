@@ -0,0 +1,214 @@
+//! The incremental totalizer encoding of cardinality constraints.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// A node of the totalizer tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    /// The left and right child of an inner node.
+    children: Option<(usize, usize)>,
+    /// The unary count of the node built so far.
+    ///
+    /// Output `i` is implied by at least `i + 1` true literals below the node.
+    /// A leaf has its literal as only output.
+    outputs: Vec<Lit>,
+    /// The number of literals below the node.
+    len: usize,
+}
+
+/// A totalizer counting how many of its literals are `true`.
+///
+/// The tree of the totalizer is built eagerly but its clauses are only added
+/// up to the requested bound, so the bound can be extended later without
+/// re-encoding. Only clauses propagating upwards are added, which makes an
+/// output `true` if enough literals are `true`, so bounds are enforced by
+/// assuming negated outputs.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::{IpasirSolver, Lit, VarManager};
+/// use ipasir::encodings::totalizer::Totalizer;
+///
+/// fn solve_at_most<S: IpasirSolver>(solver: &mut S, lits: &[Lit]) {
+///     let mut vars = VarManager::new();
+///     let mut totalizer = Totalizer::new(lits.iter().cloned());
+///     for bound in (0..lits.len()).rev() {
+///         if let Some(assumption) = totalizer.at_most(solver, &mut vars, bound) {
+///             solver.assume(assumption);
+///         }
+///         let _ = solver.solve();
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Totalizer {
+    /// The nodes of the tree where children precede their parents.
+    nodes: Vec<Node>,
+}
+
+impl Totalizer {
+    /// Creates a new totalizer over the literals without adding any clauses.
+    pub fn new<I, L>(lits: I) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        let lits: Vec<Lit> = lits.into_iter().map(Into::into).collect();
+        let mut totalizer = Self { nodes: Vec::with_capacity(2 * lits.len()) };
+        if !lits.is_empty() {
+            totalizer.build(&lits);
+        }
+        totalizer
+    }
+
+    /// Adds the balanced subtree over the non-empty literals and returns its root.
+    fn build(&mut self, lits: &[Lit]) -> usize {
+        if let [lit] = lits {
+            return self.push(Node { children: None, outputs: vec![*lit], len: 1 })
+        }
+        let (left, right) = lits.split_at(lits.len() / 2);
+        let left = self.build(left);
+        let right = self.build(right);
+        self.push(Node { children: Some((left, right)), outputs: Vec::new(), len: lits.len() })
+    }
+
+    /// Adds the node and returns its index.
+    fn push(&mut self, node: Node) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Returns the index of the root if there are any literals.
+    fn root(&self) -> Option<usize> {
+        self.nodes.len().checked_sub(1)
+    }
+
+    /// Returns the number of counted literals.
+    pub fn len(&self) -> usize {
+        self.root().map(|root| self.nodes[root].len).unwrap_or(0)
+    }
+
+    /// Returns `true` if the totalizer counts no literals.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the output literals built so far.
+    ///
+    /// Output `i` is implied by at least `i + 1` true literals.
+    pub fn outputs(&self) -> &[Lit] {
+        self.root().map(|root| self.nodes[root].outputs.as_slice()).unwrap_or(&[])
+    }
+
+    /// Adds the clauses needed to enforce bounds up to the given one.
+    ///
+    /// Afterwards the totalizer has `min(bound + 1, len)` outputs.
+    /// Clauses added for smaller bounds before are kept.
+    /// The variables of the literals are reserved in the variable manager
+    /// before auxiliary variables are taken from it.
+    pub fn encode_up_to<S>(&mut self, solver: &mut S, vars: &mut VarManager, bound: usize)
+    where
+        S: IpasirSolver,
+    {
+        let root = match self.root() {
+            Some(root) => root,
+            None => return,
+        };
+        for node in self.nodes.iter().filter(|node| node.children.is_none()) {
+            vars.reserve(node.outputs[0].var());
+        }
+        self.encode_node(solver, vars, root, bound.saturating_add(1));
+    }
+
+    /// Extends the outputs of the node up to the given number.
+    fn encode_node<S>(&mut self, solver: &mut S, vars: &mut VarManager, node: usize, limit: usize)
+    where
+        S: IpasirSolver,
+    {
+        let (left, right) = match self.nodes[node].children {
+            Some(children) => children,
+            None => return,
+        };
+        let limit = limit.min(self.nodes[node].len);
+        let old = self.nodes[node].outputs.len();
+        if limit <= old {
+            return
+        }
+        self.encode_node(solver, vars, left, limit);
+        self.encode_node(solver, vars, right, limit);
+        let outputs = &mut self.nodes[node].outputs;
+        outputs.extend((old..limit).map(|_| vars.fresh_lit()));
+        let outputs = outputs.clone();
+        let (left, right) = (&self.nodes[left].outputs, &self.nodes[right].outputs);
+        // Clauses for sums up to the old number of outputs are already there.
+        for a in 0..=left.len() {
+            for b in 0..=right.len() {
+                let sum = a + b;
+                if sum <= old || sum > limit {
+                    continue
+                }
+                let mut clause = Vec::with_capacity(3);
+                if a > 0 {
+                    clause.push(!left[a - 1]);
+                }
+                if b > 0 {
+                    clause.push(!right[b - 1]);
+                }
+                clause.push(outputs[sum - 1]);
+                solver.add_clause(clause);
+            }
+        }
+    }
+
+    /// Returns an assumption literal that enforces at most `bound` true literals.
+    ///
+    /// Adds the clauses for the bound first if they are missing.
+    /// Returns `None` if the bound is at least the number of literals.
+    pub fn at_most<S>(&mut self, solver: &mut S, vars: &mut VarManager, bound: usize) -> Option<Lit>
+    where
+        S: IpasirSolver,
+    {
+        if bound >= self.len() {
+            return None
+        }
+        self.encode_up_to(solver, vars, bound);
+        Some(!self.outputs()[bound])
+    }
+
+    /// Returns a totalizer counting the literals of both totalizers.
+    ///
+    /// The clauses added for both totalizers are reused and
+    /// only the new root is encoded when bounds are requested.
+    ///
+    /// # Note
+    ///
+    /// The variables of the literals of both totalizers must have been reserved
+    /// in the variable manager before either of them was encoded.
+    pub fn merge(mut self, other: Self) -> Self {
+        let left = match self.root() {
+            Some(left) => left,
+            None => return other,
+        };
+        let offset = self.nodes.len();
+        self.nodes.extend(other.nodes.into_iter().map(|mut node| {
+            node.children = node.children.map(|(left, right)| (left + offset, right + offset));
+            node
+        }));
+        let right = match self.root() {
+            Some(right) if right >= offset => right,
+            _ => return self,
+        };
+        let len = self.nodes[left].len + self.nodes[right].len;
+        self.push(Node { children: Some((left, right)), outputs: Vec::new(), len });
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+use crate::{
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+
+/// Checks that assuming the bound admits exactly the assignments
+/// of the literals with at most `bound` true literals.
+fn check_bound(solver: &mut TestSolver, lits: &[Lit], assumption: Option<Lit>, bound: usize) {
+    for bits in 0u32..1 << lits.len() {
+        for (n, &lit) in lits.iter().enumerate() {
+            solver.assume(if bits >> n & 1 == 1 { lit } else { !lit });
+        }
+        if let Some(assumption) = assumption {
+            solver.assume(assumption);
+        }
+        let expected = if bits.count_ones() as usize <= bound { SolveResponse::Sat } else { SolveResponse::Unsat };
+        assert_eq!(solver.solve(), Ok(expected), "at most {} under {:b}", bound, bits);
+    }
+}
+
+#[test]
+fn tightening_bounds() {
+    let lits: Vec<Lit> = vec![lit(1), lit(-2), lit(3), lit(4), lit(-5), lit(6)];
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let mut totalizer = Totalizer::new(lits.iter().cloned());
+    assert_eq!(totalizer.at_most(&mut solver, &mut vars, 6), None);
+    for bound in (0..6).rev() {
+        let assumption = totalizer.at_most(&mut solver, &mut vars, bound);
+        assert!(assumption.is_some());
+        check_bound(&mut solver, &lits, assumption, bound);
+    }
+    assert_eq!(totalizer.outputs().len(), 6);
+}
+
+#[test]
+fn lazy_encoding_and_extension() {
+    let lits: Vec<Lit> = (1..=8).map(lit).collect();
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let mut totalizer = Totalizer::new(lits.iter().cloned());
+    let assumption = totalizer.at_most(&mut solver, &mut vars, 1);
+    assert_eq!(totalizer.outputs().len(), 2);
+    let lazy = solver.clauses().count();
+    check_bound(&mut solver, &lits, assumption, 1);
+    let assumption = totalizer.at_most(&mut solver, &mut vars, 3);
+    assert!(solver.clauses().count() > lazy);
+    check_bound(&mut solver, &lits, assumption, 3);
+    check_bound(&mut solver, &lits, Some(!totalizer.outputs()[1]), 1);
+}
+
+#[test]
+fn merge_reuses_encoded_children() {
+    let lits: Vec<Lit> = (1..=6).map(lit).collect();
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::with_max_var(Some(lit(6).var()));
+    let mut left = Totalizer::new(lits[..4].iter().cloned());
+    let assumption = left.at_most(&mut solver, &mut vars, 1);
+    check_bound(&mut solver, &lits[..4], assumption, 1);
+    let mut merged = left.merge(Totalizer::new(lits[4..].iter().cloned()));
+    assert_eq!(merged.len(), 6);
+    let assumption = merged.at_most(&mut solver, &mut vars, 2);
+    check_bound(&mut solver, &lits, assumption, 2);
+    let empty = Totalizer::new(Vec::<Lit>::new());
+    assert_eq!(empty.merge(merged.clone()), merged);
+}
@@ -0,0 +1,143 @@
+//! Encoding of XOR constraints by cutting them into short XORs.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Lit,
+    Sign,
+    Cnf,
+    Model,
+    LitValue,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::vec::Vec;
+
+/// The default number of literals of the short XORs after cutting.
+pub const DEFAULT_CUTTING_LENGTH: usize = 5;
+
+/// A constraint that the parity of the number of `true` literals is the given one.
+///
+/// The parity is `true` if an odd number of literals must be `true`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorConstraint {
+    /// The literals.
+    pub lits: Vec<Lit>,
+    /// The required parity.
+    pub parity: bool,
+}
+
+impl XorConstraint {
+    /// Creates a new constraint.
+    pub fn new<I, L>(lits: I, parity: bool) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        Self {
+            lits: lits.into_iter().map(Into::into).collect(),
+            parity,
+        }
+    }
+
+    /// Returns `true` if the model satisfies the constraint.
+    ///
+    /// Literals that are not `true` in the model count as `false`.
+    pub fn is_satisfied(&self, model: &Model) -> bool {
+        let odd = self.lits.iter().filter(|&&lit| model.value(lit) == LitValue::True).count() % 2 == 1;
+        odd == self.parity
+    }
+
+    /// Returns the positive literals of the constraint and its parity.
+    ///
+    /// Negative literals flip the parity and literals of the same
+    /// variable cancel each other out.
+    fn normalize(&self) -> (Vec<Lit>, bool) {
+        let mut parity = self.parity;
+        let mut lits: Vec<Lit> = self
+            .lits
+            .iter()
+            .map(|&lit| {
+                parity ^= lit.sign() == Sign::Neg;
+                lit.var().lit(Sign::Pos)
+            })
+            .collect();
+        lits.sort();
+        let mut normalized: Vec<Lit> = Vec::with_capacity(lits.len());
+        for lit in lits {
+            if normalized.last() == Some(&lit) {
+                normalized.pop();
+            } else {
+                normalized.push(lit);
+            }
+        }
+        (normalized, parity)
+    }
+
+    /// Calls `emit` for every clause of the encoding.
+    fn emit_clauses<F>(&self, vars: &mut VarManager, cutting_length: usize, mut emit: F)
+    where
+        F: FnMut(&[Lit]),
+    {
+        assert!((3..=20).contains(&cutting_length), "the cutting length must be between 3 and 20");
+        let (mut lits, parity) = self.normalize();
+        while lits.len() > cutting_length {
+            // Replace the first literals by a fresh one defined as their XOR.
+            let rest = lits.split_off(cutting_length - 1);
+            let aux = vars.fresh_lit();
+            lits.push(aux);
+            emit_direct(&lits, false, &mut emit);
+            lits.clear();
+            lits.push(aux);
+            lits.extend(rest);
+        }
+        emit_direct(&lits, parity, &mut emit);
+    }
+
+    /// Adds clauses enforcing the constraint to the solver.
+    ///
+    /// XORs longer than the cutting length are split into chained XORs of at most
+    /// that many literals over fresh variables, each of which is encoded directly
+    /// with one clause per forbidden assignment.
+    ///
+    /// # Panics
+    ///
+    /// If the cutting length is less than `3` or greater than `20`.
+    pub fn encode<S>(&self, solver: &mut S, vars: &mut VarManager, cutting_length: usize)
+    where
+        S: IpasirSolver,
+    {
+        self.emit_clauses(vars, cutting_length, |clause| solver.add_clause(clause.iter().cloned()));
+    }
+
+    /// Returns the clauses of the encoding described at `encode`.
+    ///
+    /// # Panics
+    ///
+    /// If the cutting length is less than `3` or greater than `20`.
+    pub fn to_cnf(&self, vars: &mut VarManager, cutting_length: usize) -> Cnf {
+        let mut cnf = Cnf::new();
+        self.emit_clauses(vars, cutting_length, |clause| cnf.add_clause(clause.iter().cloned()));
+        cnf
+    }
+}
+
+/// Calls `emit` for the clauses that forbid every assignment of
+/// the positive literals whose parity differs from the given one.
+fn emit_direct<F>(lits: &[Lit], parity: bool, emit: &mut F)
+where
+    F: FnMut(&[Lit]),
+{
+    let mut clause = Vec::with_capacity(lits.len());
+    for assignment in 0..1u64 << lits.len() {
+        if (assignment.count_ones() % 2 == 1) == parity {
+            continue
+        }
+        clause.clear();
+        clause.extend(lits.iter().enumerate().map(|(n, &lit)| {
+            if assignment >> n & 1 == 1 { !lit } else { lit }
+        }));
+        emit(&clause);
+    }
+}
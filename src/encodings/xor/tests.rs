@@ -0,0 +1,52 @@
+use super::*;
+use crate::{
+    Var,
+    tests::lit,
+};
+
+/// Returns the model assigning the first `num_vars` variables to the bits of `bits`.
+fn model(num_vars: usize, bits: u32) -> Model {
+    let mut model = Model::new();
+    for index in 0..num_vars {
+        let var = Var::from_index(index);
+        model.set(if bits >> index & 1 == 1 { var.lit(Sign::Pos) } else { var.lit(Sign::Neg) });
+    }
+    model
+}
+
+/// Returns `true` if the clauses extended by the auxiliary variables are satisfiable under the model.
+fn extends(cnf: &Cnf, model: &Model, num_vars: usize, num_aux: usize) -> bool {
+    (0..1u32 << num_aux).any(|aux| {
+        let mut extended = model.clone();
+        for index in 0..num_aux {
+            let var = Var::from_index(num_vars + index);
+            extended.set(if aux >> index & 1 == 1 { var.lit(Sign::Pos) } else { var.lit(Sign::Neg) });
+        }
+        extended.first_unsatisfied(cnf).is_none()
+    })
+}
+
+#[test]
+fn cut_encoding_is_exact() {
+    let xor = XorConstraint::new(vec![lit(1), lit(-2), lit(3), lit(4), lit(-5), lit(6), lit(7)], true);
+    for &cutting_length in &[3, 4, DEFAULT_CUTTING_LENGTH, 7] {
+        let mut vars = VarManager::with_max_var(Some(lit(7).var()));
+        let cnf = xor.to_cnf(&mut vars, cutting_length);
+        let num_aux = vars.num_vars() - 7;
+        assert!(cnf.iter().all(|clause| clause.len() <= cutting_length));
+        for bits in 0..1 << 7 {
+            let model = model(7, bits);
+            assert_eq!(extends(&cnf, &model, 7, num_aux), xor.is_satisfied(&model));
+        }
+    }
+}
+
+#[test]
+fn duplicates_cancel_out() {
+    let mut vars = VarManager::with_max_var(Some(lit(2).var()));
+    let tautology = XorConstraint::new(vec![lit(1), lit(-1)], true);
+    assert!(tautology.to_cnf(&mut vars, 3).is_empty());
+    let contradiction = XorConstraint::new(vec![lit(2), lit(2)], true);
+    assert_eq!(contradiction.to_cnf(&mut vars, 3).iter().map(|clause| clause.len()).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(vars.num_vars(), 2);
+}
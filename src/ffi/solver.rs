@@ -1,17 +1,11 @@
-pub use crate::{
+use crate::{
     ffi::sys::*,
 
     Result,
 
-    Sign,
-    Var,
     Lit,
-    InvalidLitVal,
     Clause,
-    LitIter,
 
-    SolverErrorKind,
-    SolverError,
     SolveResponse,
     ResponseError,
     LitValue,
@@ -19,20 +13,26 @@ pub use crate::{
     SolveControl,
 };
 use std::{
+    boxed::Box,
     os::raw::{
         c_int,
         c_void,
     },
     ffi::CStr,
     marker,
-    mem,
 };
 
+/// The boxed user-provided terminate callback.
+type TerminateCallback = Box<dyn FnMut() -> SolveControl>;
+
+/// The boxed user-provided learn callback.
+type LearnCallback = Box<dyn FnMut(Clause)>;
+
 /// The incremental solver implementing the IPASIR interface.
 pub struct Solver {
     ptr: *mut SysSolver,
-    terminate_cb: Option<Box<Box<FnMut() -> SolveControl>>>,
-    learn_cb: Option<Box<Box<FnMut(Clause)>>>,
+    terminate_cb: Option<Box<TerminateCallback>>,
+    learn_cb: Option<Box<LearnCallback>>,
 }
 
 unsafe impl marker::Send for Solver {}
@@ -90,7 +90,7 @@ impl IpasirSolver for Solver {
             0 => Ok(LitValue::DontCare),
             p if p == lit.to_raw() => Ok(LitValue::True),
             n if n == -lit.to_raw() => Ok(LitValue::False),
-            invalid => Err(InvalidLitVal(invalid).into())
+            invalid => Err(ResponseError::Val(invalid).into())
         }
     }
 
@@ -148,8 +148,8 @@ impl Drop for Solver {
 /// Don't use this directly!
 extern "C" fn ipasir_set_terminate_callback(state: *const c_void) -> c_int
 {
-    let cb: &mut Box<FnMut() -> SolveControl> = unsafe {
-        mem::transmute(state)
+    let cb = unsafe {
+        &mut *(state as *mut TerminateCallback)
     };
     match cb() {
         SolveControl::Continue => 0,
@@ -167,8 +167,8 @@ extern "C" fn ipasir_set_terminate_callback(state: *const c_void) -> c_int
 /// Don't use this directly!
 extern "C" fn ipasir_set_learn_callback(state: *const c_void, learnt_clause: *const c_int)
 {
-    let cb: &mut Box<FnMut(Clause)> = unsafe {
-        mem::transmute(state)
+    let cb = unsafe {
+        &mut *(state as *mut LearnCallback)
     };
     let mut count_lits = 0;
     for n in 0.. {
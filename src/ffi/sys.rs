@@ -8,6 +8,8 @@ use std::os::raw::{
     c_void
 };
 
+pub use crate::MAX_VAR;
+
 /// Sealed FFI solver type.
 ///
 /// This is used to improve type safety in FFI context.
@@ -64,6 +66,7 @@ extern "C" {
     /// or equal to `INT_MAX` and strictly larger than `INT_MIN`
     /// (to avoid negation overflow).  This applies to all the
     /// literal arguments in API functions.
+    /// The greatest valid variable is available as [`MAX_VAR`].
     /// 
     /// [dimacs-fmt]: http://www.satcompetition.org/2009/format-benchmarks2009.html
     /// 
@@ -162,7 +165,7 @@ extern "C" {
     pub fn ipasir_set_terminate(
         solver: *mut SysSolver,
         state: *const c_void,
-        terminate: extern fn(state: *const c_void) -> c_int
+        terminate: extern "C" fn(state: *const c_void) -> c_int
     );
 
     /// Set a callback function used to extract learned clauses up to a given length from the solver.
@@ -191,6 +194,6 @@ extern "C" {
         solver: *mut SysSolver,
         state: *const c_void,
         max_length: c_int,
-        learn: extern fn(state: *const c_void, clause: *const c_int)
+        learn: extern "C" fn(state: *const c_void, clause: *const c_int)
     );
 }
@@ -0,0 +1,422 @@
+use super::Formula;
+use crate::{
+    Lit,
+    IpasirSolver,
+    VarManager,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::ops;
+
+/// The translation of formulas into clauses.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Translation {
+    /// The Tseitin transformation which makes the literal of every
+    /// gate equivalent to the gate.
+    #[default]
+    Tseitin,
+    /// The Plaisted-Greenbaum transformation which only adds the direction
+    /// of the definition of a gate required by the polarities it occurs in.
+    PlaistedGreenbaum,
+}
+
+/// The polarities a formula occurs in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Polarity {
+    /// The literal of the formula only needs to imply it.
+    Positive,
+    /// The literal of the formula only needs to be implied by it.
+    Negative,
+    /// The literal of the formula needs to be equivalent to it.
+    Both,
+}
+
+impl Polarity {
+    /// Returns the polarity of a negated occurrence.
+    fn flip(self) -> Self {
+        match self {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+            Polarity::Both => Polarity::Both,
+        }
+    }
+
+    /// Returns `true` if the literal needs to imply the formula.
+    fn is_positive(self) -> bool {
+        self != Polarity::Negative
+    }
+
+    /// Returns `true` if the literal needs to be implied by the formula.
+    fn is_negative(self) -> bool {
+        self != Polarity::Positive
+    }
+}
+
+/// The value of a formula after constant propagation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Value {
+    /// The formula is constant.
+    Const(bool),
+    /// The formula is represented by the literal.
+    Lit(Lit),
+}
+
+impl ops::Not for Value {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        match self {
+            Value::Const(value) => Value::Const(!value),
+            Value::Lit(lit) => Value::Lit(!lit),
+        }
+    }
+}
+
+/// A gate in normal form for structural hashing.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Gate {
+    /// The conjunction of at least two sorted and distinct literals.
+    And(Vec<Lit>),
+    /// The parity of two distinct positive literals in ascending order.
+    Xor(Lit, Lit),
+    /// The if-then-else with a positive condition.
+    Ite(Lit, Lit, Lit),
+}
+
+impl Gate {
+    /// Adds the clauses for `out -> gate` if `positive` and else for `gate -> out`.
+    fn define<S>(&self, solver: &mut S, out: Lit, positive: bool)
+    where
+        S: IpasirSolver,
+    {
+        let (out, sign) = if positive { (!out, false) } else { (out, true) };
+        // Negates the literal of the gate for the implied direction.
+        let lit = |lit: Lit| if sign { !lit } else { lit };
+        match self {
+            Gate::And(lits) => {
+                if positive {
+                    for &input in lits {
+                        solver.add_clause(vec![out, input]);
+                    }
+                } else {
+                    solver.add_clause(Some(out).into_iter().chain(lits.iter().map(|&input| !input)));
+                }
+            }
+            &Gate::Xor(a, b) => {
+                solver.add_clause(vec![out, lit(a), b]);
+                solver.add_clause(vec![out, !lit(a), !b]);
+            }
+            &Gate::Ite(cond, then, otherwise) => {
+                solver.add_clause(vec![out, !cond, lit(then)]);
+                solver.add_clause(vec![out, cond, lit(otherwise)]);
+                solver.add_clause(vec![out, lit(then), lit(otherwise)]);
+            }
+        }
+    }
+}
+
+/// The literal of a gate and the directions of its definition added so far.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Definition {
+    /// The literal of the gate.
+    lit: Lit,
+    /// `true` if the literal implies the gate.
+    positive: bool,
+    /// `true` if the gate implies the literal.
+    negative: bool,
+}
+
+/// Translates formulas into clauses and remembers the literals of their gates.
+///
+/// Gates are normalized and hashed structurally so that equal subformulas of all
+/// formulas encoded by the same encoder share their literal. Constants are
+/// propagated and do not introduce gates.
+///
+/// # Note
+///
+/// All formulas of an encoder must be added to the same solver and
+/// take their auxiliary variables from the same variable manager.
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    /// The translation of new gates.
+    translation: Translation,
+    /// The definitions by gate.
+    gates: BTreeMap<Gate, Definition>,
+    /// The literal forced to be `true` if needed.
+    true_lit: Option<Lit>,
+}
+
+impl Encoder {
+    /// Creates a new encoder using the given translation.
+    pub fn new(translation: Translation) -> Self {
+        Self {
+            translation,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the translation of the encoder.
+    pub fn translation(&self) -> Translation {
+        self.translation
+    }
+
+    /// Returns the number of distinct gates encoded so far.
+    pub fn num_gates(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Adds the definition of the formula in the given polarity and returns its literal.
+    ///
+    /// With the Plaisted-Greenbaum translation the literal only implies the formula
+    /// for `Polarity::Positive` and is only implied by it for `Polarity::Negative`.
+    /// The Tseitin translation always makes it equivalent to the formula.
+    ///
+    /// The variables of the formula are reserved in the variable manager
+    /// before auxiliary variables are taken from it.
+    pub fn encode<S>(&mut self, solver: &mut S, vars: &mut VarManager, formula: &Formula, polarity: Polarity) -> Lit
+    where
+        S: IpasirSolver,
+    {
+        reserve(vars, formula);
+        match self.value(solver, vars, formula, polarity) {
+            Value::Lit(lit) => lit,
+            Value::Const(value) => {
+                let true_lit = *self.true_lit.get_or_insert_with(|| {
+                    let lit = vars.fresh_lit();
+                    solver.add_clause(Some(lit));
+                    lit
+                });
+                if value { true_lit } else { !true_lit }
+            }
+        }
+    }
+
+    /// Adds clauses to the solver that are satisfiable if and only if the formula is.
+    ///
+    /// Top-level conjunctions and disjunctions are added as units and clauses
+    /// and all other gates are encoded in positive polarity.
+    pub fn assert<S>(&mut self, solver: &mut S, vars: &mut VarManager, formula: &Formula)
+    where
+        S: IpasirSolver,
+    {
+        reserve(vars, formula);
+        self.assert_reserved(solver, vars, formula);
+    }
+
+    /// Asserts the formula whose variables are reserved.
+    fn assert_reserved<S>(&mut self, solver: &mut S, vars: &mut VarManager, formula: &Formula)
+    where
+        S: IpasirSolver,
+    {
+        match formula {
+            Formula::And(formulas) => {
+                for formula in formulas {
+                    self.assert_reserved(solver, vars, formula);
+                }
+            }
+            Formula::Or(formulas) => {
+                let mut clause = Vec::with_capacity(formulas.len());
+                for formula in formulas {
+                    match self.value(solver, vars, formula, Polarity::Positive) {
+                        Value::Const(true) => return,
+                        Value::Const(false) => (),
+                        Value::Lit(lit) => clause.push(lit),
+                    }
+                }
+                solver.add_clause(clause);
+            }
+            _ => match self.value(solver, vars, formula, Polarity::Positive) {
+                Value::Const(true) => (),
+                Value::Const(false) => solver.add_clause(core::iter::empty::<Lit>()),
+                Value::Lit(lit) => solver.add_clause(Some(lit)),
+            },
+        }
+    }
+
+    /// Returns the value of the formula after defining its gates in the given polarity.
+    fn value<S>(&mut self, solver: &mut S, vars: &mut VarManager, formula: &Formula, polarity: Polarity) -> Value
+    where
+        S: IpasirSolver,
+    {
+        match formula {
+            Formula::Const(value) => Value::Const(*value),
+            Formula::Lit(lit) => Value::Lit(*lit),
+            Formula::Not(formula) => !self.value(solver, vars, formula, polarity.flip()),
+            Formula::And(formulas) => {
+                let values = formulas
+                    .iter()
+                    .map(|formula| self.value(solver, vars, formula, polarity))
+                    .collect();
+                self.and(solver, vars, values, polarity)
+            }
+            Formula::Or(formulas) => {
+                let values = formulas
+                    .iter()
+                    .map(|formula| !self.value(solver, vars, formula, polarity))
+                    .collect();
+                !self.and(solver, vars, values, polarity.flip())
+            }
+            Formula::Xor(formulas) => {
+                let mut value = Value::Const(false);
+                for (n, formula) in formulas.iter().enumerate() {
+                    let operand = self.value(solver, vars, formula, Polarity::Both);
+                    let last = n + 1 == formulas.len();
+                    value = self.xor(solver, vars, value, operand, if last { polarity } else { Polarity::Both });
+                }
+                value
+            }
+            Formula::Implies(lhs, rhs) => {
+                let lhs = self.value(solver, vars, lhs, polarity.flip());
+                let rhs = self.value(solver, vars, rhs, polarity);
+                !self.and(solver, vars, vec![lhs, !rhs], polarity.flip())
+            }
+            Formula::Iff(lhs, rhs) => {
+                let lhs = self.value(solver, vars, lhs, Polarity::Both);
+                let rhs = self.value(solver, vars, rhs, Polarity::Both);
+                !self.xor(solver, vars, lhs, rhs, polarity.flip())
+            }
+            Formula::Ite(cond, then, otherwise) => {
+                let cond = self.value(solver, vars, cond, Polarity::Both);
+                let then = self.value(solver, vars, then, polarity);
+                let otherwise = self.value(solver, vars, otherwise, polarity);
+                self.ite(solver, vars, cond, then, otherwise, polarity)
+            }
+        }
+    }
+
+    /// Returns the value of the conjunction.
+    fn and<S>(&mut self, solver: &mut S, vars: &mut VarManager, values: Vec<Value>, polarity: Polarity) -> Value
+    where
+        S: IpasirSolver,
+    {
+        let mut lits = Vec::with_capacity(values.len());
+        for value in values {
+            match value {
+                Value::Const(true) => (),
+                Value::Const(false) => return Value::Const(false),
+                Value::Lit(lit) => lits.push(lit),
+            }
+        }
+        lits.sort();
+        lits.dedup();
+        if lits.iter().any(|&lit| lits.binary_search(&!lit).is_ok()) {
+            return Value::Const(false)
+        }
+        match lits.len() {
+            0 => Value::Const(true),
+            1 => Value::Lit(lits[0]),
+            _ => Value::Lit(self.gate(solver, vars, Gate::And(lits), polarity)),
+        }
+    }
+
+    /// Returns the value of the parity of both values.
+    fn xor<S>(&mut self, solver: &mut S, vars: &mut VarManager, lhs: Value, rhs: Value, polarity: Polarity) -> Value
+    where
+        S: IpasirSolver,
+    {
+        match (lhs, rhs) {
+            (Value::Const(value), other) | (other, Value::Const(value)) => if value { !other } else { other },
+            (Value::Lit(lhs), Value::Lit(rhs)) => {
+                let parity = lhs.sign() != rhs.sign();
+                let (lhs, rhs) = (lhs.var().into(), rhs.var().into());
+                if lhs == rhs {
+                    return Value::Const(parity)
+                }
+                let gate = if lhs < rhs { Gate::Xor(lhs, rhs) } else { Gate::Xor(rhs, lhs) };
+                if parity {
+                    !Value::Lit(self.gate(solver, vars, gate, polarity.flip()))
+                } else {
+                    Value::Lit(self.gate(solver, vars, gate, polarity))
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the if-then-else.
+    fn ite<S>(
+        &mut self,
+        solver: &mut S,
+        vars: &mut VarManager,
+        cond: Value,
+        then: Value,
+        otherwise: Value,
+        polarity: Polarity,
+    ) -> Value
+    where
+        S: IpasirSolver,
+    {
+        if then == otherwise {
+            return then
+        }
+        if then == !otherwise {
+            return !self.xor(solver, vars, cond, then, polarity.flip())
+        }
+        match (cond, then, otherwise) {
+            (Value::Const(true), then, _) => then,
+            (Value::Const(false), _, otherwise) => otherwise,
+            (cond, Value::Const(true), otherwise) => !self.and(solver, vars, vec![!cond, !otherwise], polarity.flip()),
+            (cond, Value::Const(false), otherwise) => self.and(solver, vars, vec![!cond, otherwise], polarity),
+            (cond, then, Value::Const(true)) => !self.and(solver, vars, vec![cond, !then], polarity.flip()),
+            (cond, then, Value::Const(false)) => self.and(solver, vars, vec![cond, then], polarity),
+            (Value::Lit(cond), Value::Lit(then), Value::Lit(otherwise)) => {
+                let gate = if cond == cond.var().into() {
+                    Gate::Ite(cond, then, otherwise)
+                } else {
+                    Gate::Ite(!cond, otherwise, then)
+                };
+                Value::Lit(self.gate(solver, vars, gate, polarity))
+            }
+        }
+    }
+
+    /// Returns the literal of the gate after adding the missing directions
+    /// of its definition for the given polarity.
+    fn gate<S>(&mut self, solver: &mut S, vars: &mut VarManager, gate: Gate, polarity: Polarity) -> Lit
+    where
+        S: IpasirSolver,
+    {
+        let polarity = match self.translation {
+            Translation::Tseitin => Polarity::Both,
+            Translation::PlaistedGreenbaum => polarity,
+        };
+        let definition = self.gates.entry(gate.clone()).or_insert_with(|| Definition {
+            lit: vars.fresh_lit(),
+            positive: false,
+            negative: false,
+        });
+        if polarity.is_positive() && !definition.positive {
+            definition.positive = true;
+            gate.define(solver, definition.lit, true);
+        }
+        if polarity.is_negative() && !definition.negative {
+            definition.negative = true;
+            gate.define(solver, definition.lit, false);
+        }
+        definition.lit
+    }
+}
+
+/// Reserves the variables of all literals of the formula.
+fn reserve(vars: &mut VarManager, formula: &Formula) {
+    match formula {
+        Formula::Const(_) => (),
+        Formula::Lit(lit) => vars.reserve(lit.var()),
+        Formula::Not(formula) => reserve(vars, formula),
+        Formula::And(formulas) | Formula::Or(formulas) | Formula::Xor(formulas) => {
+            for formula in formulas {
+                reserve(vars, formula);
+            }
+        }
+        Formula::Implies(lhs, rhs) | Formula::Iff(lhs, rhs) => {
+            reserve(vars, lhs);
+            reserve(vars, rhs);
+        }
+        Formula::Ite(cond, then, otherwise) => {
+            reserve(vars, cond);
+            reserve(vars, then);
+            reserve(vars, otherwise);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Propositional formulas and their translation into clauses.
+//!
+//! An `Encoder` translates formulas into clauses by introducing one literal
+//! per gate, either with the full Tseitin transformation or with the
+//! polarity-aware Plaisted-Greenbaum transformation. Gates are hashed
+//! structurally so equal subformulas share their literal across calls.
+
+mod encode;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::encode::{
+    Encoder,
+    Polarity,
+    Translation,
+};
+use crate::{
+    Var,
+    Lit,
+    Model,
+    LitValue,
+};
+use alloc::{
+    boxed::Box,
+    vec::Vec,
+};
+use core::ops;
+
+/// A propositional formula over literals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Formula {
+    /// The constant `true` or `false`.
+    Const(bool),
+    /// A literal.
+    Lit(Lit),
+    /// The negation of a formula.
+    Not(Box<Formula>),
+    /// The conjunction of all formulas, `true` if there are none.
+    And(Vec<Formula>),
+    /// The disjunction of all formulas, `false` if there are none.
+    Or(Vec<Formula>),
+    /// The parity of all formulas, `false` if there are none.
+    Xor(Vec<Formula>),
+    /// The implication `lhs -> rhs`.
+    Implies(Box<Formula>, Box<Formula>),
+    /// The equivalence `lhs <-> rhs`.
+    Iff(Box<Formula>, Box<Formula>),
+    /// The if-then-else `cond ? then : else`.
+    Ite(Box<Formula>, Box<Formula>, Box<Formula>),
+}
+
+impl Formula {
+    /// Returns the conjunction of the formulas.
+    pub fn and<I>(formulas: I) -> Self
+    where
+        I: IntoIterator<Item = Formula>,
+    {
+        Formula::And(formulas.into_iter().collect())
+    }
+
+    /// Returns the disjunction of the formulas.
+    pub fn or<I>(formulas: I) -> Self
+    where
+        I: IntoIterator<Item = Formula>,
+    {
+        Formula::Or(formulas.into_iter().collect())
+    }
+
+    /// Returns the parity of the formulas.
+    pub fn xor<I>(formulas: I) -> Self
+    where
+        I: IntoIterator<Item = Formula>,
+    {
+        Formula::Xor(formulas.into_iter().collect())
+    }
+
+    /// Returns the implication `self -> rhs`.
+    pub fn implies(self, rhs: Formula) -> Self {
+        Formula::Implies(Box::new(self), Box::new(rhs))
+    }
+
+    /// Returns the equivalence `self <-> rhs`.
+    pub fn iff(self, rhs: Formula) -> Self {
+        Formula::Iff(Box::new(self), Box::new(rhs))
+    }
+
+    /// Returns the if-then-else `cond ? then : otherwise`.
+    pub fn ite(cond: Formula, then: Formula, otherwise: Formula) -> Self {
+        Formula::Ite(Box::new(cond), Box::new(then), Box::new(otherwise))
+    }
+
+    /// Evaluates the formula under the model.
+    ///
+    /// Unassigned literals are `LitValue::DontCare` which propagates
+    /// unless the value is fixed by the other operands.
+    pub fn eval(&self, model: &Model) -> LitValue {
+        match self {
+            Formula::Const(value) => LitValue::from(*value),
+            Formula::Lit(lit) => model.value(*lit),
+            Formula::Not(formula) => !formula.eval(model),
+            Formula::And(formulas) => formulas.iter().fold(LitValue::True, |acc, formula| acc & formula.eval(model)),
+            Formula::Or(formulas) => formulas.iter().fold(LitValue::False, |acc, formula| acc | formula.eval(model)),
+            Formula::Xor(formulas) => formulas.iter().fold(LitValue::False, |acc, formula| acc ^ formula.eval(model)),
+            Formula::Implies(lhs, rhs) => lhs.eval(model).implies(rhs.eval(model)),
+            Formula::Iff(lhs, rhs) => !(lhs.eval(model) ^ rhs.eval(model)),
+            Formula::Ite(cond, then, otherwise) => match cond.eval(model) {
+                LitValue::True => then.eval(model),
+                LitValue::False => otherwise.eval(model),
+                LitValue::DontCare => match (then.eval(model), otherwise.eval(model)) {
+                    (then, otherwise) if then == otherwise => then,
+                    _ => LitValue::DontCare,
+                },
+            },
+        }
+    }
+}
+
+impl From<bool> for Formula {
+    fn from(value: bool) -> Self {
+        Formula::Const(value)
+    }
+}
+
+impl From<Lit> for Formula {
+    fn from(lit: Lit) -> Self {
+        Formula::Lit(lit)
+    }
+}
+
+impl From<Var> for Formula {
+    fn from(var: Var) -> Self {
+        Formula::Lit(var.into())
+    }
+}
+
+impl ops::Not for Formula {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Formula::Not(Box::new(self))
+    }
+}
+
+impl ops::BitAnd for Formula {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Formula::And(vec![self, rhs])
+    }
+}
+
+impl ops::BitOr for Formula {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Formula::Or(vec![self, rhs])
+    }
+}
+
+impl ops::BitXor for Formula {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Formula::Xor(vec![self, rhs])
+    }
+}
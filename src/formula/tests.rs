@@ -0,0 +1,188 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    SolveResponse,
+    VarManager,
+    tests::{TestSolver, lit},
+};
+
+/// The number of input variables of the test formulas.
+const NUM_VARS: i32 = 4;
+
+/// Returns the literal formula of the given value.
+fn var(val: i32) -> Formula {
+    Formula::Lit(lit(val))
+}
+
+/// Returns a pseudo-random formula of the given depth.
+fn random_formula(seed: &mut u64, depth: u32) -> Formula {
+    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    let choice = (*seed >> 33) as u32;
+    if depth == 0 || choice.is_multiple_of(11) {
+        let val = (choice / 11 % NUM_VARS as u32) as i32 + 1;
+        return match choice / 64 % 9 {
+            0 => Formula::Const(choice.is_multiple_of(2)),
+            n if n % 2 == 0 => var(val),
+            _ => var(-val),
+        }
+    }
+    let mut operand = || random_formula(seed, depth - 1);
+    match choice % 8 {
+        0 => !operand(),
+        1 => Formula::and((0..choice / 8 % 4).map(|_| operand()).collect::<Vec<_>>()),
+        2 => Formula::or((0..choice / 8 % 4).map(|_| operand()).collect::<Vec<_>>()),
+        3 => Formula::xor((0..choice / 8 % 4).map(|_| operand()).collect::<Vec<_>>()),
+        4 => operand().implies(operand()),
+        5 => operand().iff(operand()),
+        6 => Formula::ite(operand(), operand(), operand()),
+        _ => {
+            let shared = operand();
+            shared.clone() & (shared | operand())
+        }
+    }
+}
+
+/// Returns formulas covering all operators, constants and shared subformulas.
+fn formulas() -> Vec<Formula> {
+    let mut formulas = vec![
+        var(1) & var(2),
+        var(1) | !var(1),
+        var(1) ^ var(-1),
+        Formula::ite(var(1), var(2), var(-2)),
+        Formula::ite(var(-1), Formula::Const(true), var(3)),
+        var(1).implies(var(2) ^ var(3) ^ var(4)),
+        (var(1) & var(2)).iff(var(2) & var(1)),
+        Formula::and(Vec::new()),
+        Formula::or(Vec::new()),
+    ];
+    let mut seed = 17;
+    for depth in 1..=4 {
+        for _ in 0..25 {
+            formulas.push(random_formula(&mut seed, depth));
+        }
+    }
+    formulas
+}
+
+/// Calls the function with every assignment of the input variables
+/// as literals and as model.
+fn for_each_assignment<F>(mut f: F)
+where
+    F: FnMut(&[Lit], &Model),
+{
+    for bits in 0..1 << NUM_VARS {
+        let lits: Vec<Lit> = (1..=NUM_VARS)
+            .map(|val| if bits >> (val - 1) & 1 == 1 { lit(val) } else { lit(-val) })
+            .collect();
+        f(&lits, &lits.iter().cloned().collect())
+    }
+}
+
+/// Returns a variable manager with the input variables in use.
+fn input_vars() -> VarManager {
+    VarManager::with_max_var(Some(lit(NUM_VARS).var()))
+}
+
+/// Returns the result of solving under the assumptions.
+fn solve_under(solver: &mut TestSolver, assumptions: &[Lit]) -> bool {
+    for &lit in assumptions {
+        solver.assume(lit);
+    }
+    solver.solve() == Ok(SolveResponse::Sat)
+}
+
+#[test]
+fn eval_formulas() {
+    let model: Model = vec![lit(1), lit(-2)].into_iter().collect();
+    assert_eq!((var(1) & var(2)).eval(&model), LitValue::False);
+    assert_eq!((var(1) ^ var(2)).eval(&model), LitValue::True);
+    assert_eq!(var(2).implies(var(3)).eval(&model), LitValue::True);
+    assert_eq!((var(1) & var(3)).eval(&model), LitValue::DontCare);
+    assert_eq!(Formula::ite(var(3), var(1), var(-2)).eval(&model), LitValue::True);
+}
+
+#[test]
+fn added_formulas_are_equivalent_to_their_literal() {
+    for &translation in &[Translation::Tseitin, Translation::PlaistedGreenbaum] {
+        let mut solver = TestSolver::init();
+        let mut vars = input_vars();
+        let mut encoder = Encoder::new(translation);
+        for formula in formulas() {
+            let output = solver.add_formula(&mut encoder, &mut vars, &formula);
+            for_each_assignment(|lits, model| {
+                let value = formula.eval(model) == LitValue::True;
+                let assumptions: Vec<Lit> = lits.iter().cloned().chain(Some(output)).collect();
+                assert_eq!(solve_under(&mut solver, &assumptions), value, "{:?} under {:?}", formula, lits);
+                let assumptions: Vec<Lit> = lits.iter().cloned().chain(Some(!output)).collect();
+                assert_eq!(solve_under(&mut solver, &assumptions), !value, "{:?} under {:?}", formula, lits);
+            });
+        }
+    }
+}
+
+#[test]
+fn asserted_formulas_are_equisatisfiable() {
+    for &translation in &[Translation::Tseitin, Translation::PlaistedGreenbaum] {
+        for formula in formulas() {
+            let mut solver = TestSolver::init();
+            let mut vars = input_vars();
+            solver.assert_formula(&mut Encoder::new(translation), &mut vars, &formula);
+            for_each_assignment(|lits, model| {
+                let value = formula.eval(model) == LitValue::True;
+                assert_eq!(solve_under(&mut solver, lits), value, "{:?} under {:?}", formula, lits);
+            });
+        }
+    }
+}
+
+#[test]
+fn plaisted_greenbaum_respects_polarity() {
+    for formula in formulas() {
+        let mut solver = TestSolver::init();
+        let mut vars = input_vars();
+        let mut encoder = Encoder::new(Translation::PlaistedGreenbaum);
+        let positive = encoder.encode(&mut solver, &mut vars, &formula, Polarity::Positive);
+        let negative = encoder.encode(&mut solver, &mut vars, &!formula.clone(), Polarity::Negative);
+        for_each_assignment(|lits, model| {
+            let value = formula.eval(model) == LitValue::True;
+            let assumptions: Vec<Lit> = lits.iter().cloned().chain(Some(positive)).collect();
+            assert_eq!(solve_under(&mut solver, &assumptions), value, "{:?} under {:?}", formula, lits);
+            let assumptions: Vec<Lit> = lits.iter().cloned().chain(Some(!negative)).collect();
+            assert_eq!(solve_under(&mut solver, &assumptions), value, "{:?} under {:?}", formula, lits);
+        });
+    }
+}
+
+#[test]
+fn plaisted_greenbaum_adds_fewer_clauses() {
+    let formula = Formula::or(vec![var(1) & var(2), var(3) & !(var(4) | var(2)), var(1).implies(var(3) & var(4))]);
+    let count = |translation| {
+        let mut solver = TestSolver::init();
+        solver.assert_formula(&mut Encoder::new(translation), &mut VarManager::new(), &formula);
+        solver.clauses().count()
+    };
+    assert!(count(Translation::PlaistedGreenbaum) < count(Translation::Tseitin));
+}
+
+#[test]
+fn equal_gates_share_their_literal() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    let mut encoder = Encoder::default();
+    let and = solver.add_formula(&mut encoder, &mut vars, &(var(1) & var(2)));
+    let num_clauses = solver.clauses().count();
+    assert_eq!(solver.add_formula(&mut encoder, &mut vars, &Formula::and(vec![var(2), var(1), var(2)])), and);
+    assert_eq!(solver.add_formula(&mut encoder, &mut vars, &!(var(-1) | var(-2))), and);
+    assert_eq!(solver.add_formula(&mut encoder, &mut vars, &(var(-1) ^ var(2))), !solver.add_formula(&mut encoder, &mut vars, &(var(2) ^ var(1))));
+    assert_eq!(encoder.num_gates(), 2);
+    assert_eq!(solver.add_formula(&mut encoder, &mut vars, &(var(1) & var(2) & Formula::Const(true))), and);
+    assert_eq!(solver.clauses().count(), num_clauses + 4);
+}
+
+#[test]
+fn auxiliary_vars_follow_formula_vars() {
+    let mut solver = TestSolver::init();
+    let mut vars = VarManager::new();
+    solver.add_formula(&mut Encoder::default(), &mut vars, &((var(1) & var(2)) | var(7)));
+    assert_eq!(vars.num_vars(), 9);
+}
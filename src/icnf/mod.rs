@@ -0,0 +1,30 @@
+//! Reading, writing and replaying the iCNF incremental format.
+//!
+//! The format starts with the header line `p inccnf` followed by clause lines
+//! as in DIMACS CNF and assumption lines `a <lits> 0`.
+//! Each assumption line triggers a solve under the listed assumptions,
+//! so the format maps one-to-one onto `add_clause`, `assume` and `solve`.
+//! Comment lines start with `c`.
+
+mod parser;
+mod writer;
+mod replay;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    parser::{
+        Parser,
+        Step,
+    },
+    writer::{
+        Logging,
+        Writer,
+    },
+    replay::{
+        replay,
+        ReplayError,
+        SolveReport,
+    },
+};
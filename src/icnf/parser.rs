@@ -0,0 +1,165 @@
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    Lit,
+    Clause,
+};
+use std::{
+    io::Read,
+    vec::Vec,
+};
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// A single step of an incremental session.
+#[derive(Debug, Copy, Clone)]
+pub enum Step<'a> {
+    /// Adds the clause.
+    Clause(Clause<'a>),
+    /// Solves under the given assumptions.
+    Solve(Clause<'a>),
+}
+
+/// A streaming parser for iCNF input.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::icnf::{Parser, Step};
+///
+/// let mut parser = Parser::new("p inccnf\n1 2 0\na -1 0\n".as_bytes());
+/// let mut solves = 0;
+/// while let Some(step) = parser.next_step() {
+///     if let Step::Solve(assumptions) = step.unwrap() {
+///         assert_eq!(assumptions.len(), 1);
+///         solves += 1;
+///     }
+/// }
+/// assert_eq!(solves, 1);
+/// ```
+pub struct Parser<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+    /// `true` if the header has been parsed.
+    header: bool,
+    /// The literals of the current line.
+    lits: Vec<Lit>,
+    /// `true` if the input is exhausted or an error occured.
+    done: bool,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Creates a new parser over the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lexer: Lexer::new(reader),
+            header: false,
+            lits: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Parses the header line after its leading `p`.
+    fn parse_header(&mut self, start: (usize, usize)) -> Result<()> {
+        if self.header {
+            return self.lexer.error_at(start, ParseErrorKind::UnexpectedHeader)
+        }
+        self.lexer.skip_blanks()?;
+        if self.lexer.read_word()? != b"inccnf" {
+            return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+        }
+        self.lexer.expect_line_end(start, ParseErrorKind::InvalidHeader)?;
+        self.header = true;
+        Ok(())
+    }
+
+    /// Parses literals into the literal buffer up to and including the terminating `0`.
+    fn parse_lits(&mut self) -> Result<()> {
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            match self.lexer.read_int()? {
+                Some(0) => return Ok(()),
+                Some(value) => match Lit::try_from_wide(value) {
+                    Ok(lit) => self.lits.push(lit),
+                    Err(err) => return self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+                },
+                None => return self.lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+    }
+
+    /// Parses the next step into the literal buffer.
+    ///
+    /// Returns `None` if there are no more steps
+    /// or `Some(true)` for a solve step.
+    fn parse_step(&mut self) -> Result<Option<bool>> {
+        self.lits.clear();
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            let byte = match self.lexer.peek()? {
+                Some(byte) => byte,
+                None if self.header => return Ok(None),
+                None => return self.lexer.error(ParseErrorKind::MissingHeader),
+            };
+            let line_start = self.lexer.take_line_start();
+            match byte {
+                b'c' if line_start => {
+                    self.lexer.skip_line()?;
+                    continue
+                }
+                b'p' if line_start => {
+                    self.lexer.bump(byte);
+                    self.parse_header(start)?;
+                    continue
+                }
+                _ if !self.header => return self.lexer.error_at(start, ParseErrorKind::MissingHeader),
+                b'a' => {
+                    self.lexer.bump(byte);
+                    self.parse_lits()?;
+                    return Ok(Some(true))
+                }
+                _ => {
+                    self.parse_lits()?;
+                    return Ok(Some(false))
+                }
+            }
+        }
+    }
+
+    /// Returns the next step of the session.
+    ///
+    /// Returns `None` after the last step or after an error.
+    ///
+    /// # Note
+    ///
+    /// The returned step borrows the internal buffer of the parser
+    /// and is only valid until the next call.
+    pub fn next_step(&mut self) -> Option<Result<Step<'_>>> {
+        if self.done {
+            return None
+        }
+        match self.parse_step() {
+            Ok(Some(solve)) => {
+                let lits = Clause::from(self.lits.as_slice());
+                Some(Ok(if solve { Step::Solve(lits) } else { Step::Clause(lits) }))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
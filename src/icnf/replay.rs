@@ -0,0 +1,104 @@
+use super::{
+    Parser,
+    Step,
+};
+use crate::{
+    dimacs::ParseError,
+    Lit,
+    IpasirSolver,
+    SolveResponse,
+    SolverError,
+};
+use std::{
+    error::Error,
+    fmt,
+    io::Read,
+    vec::Vec,
+};
+
+/// The outcome of a single solve of a replayed session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveReport {
+    /// The assumptions of the solve.
+    pub assumptions: Vec<Lit>,
+    /// The response of the solver.
+    pub response: SolveResponse,
+    /// The failed assumptions if the response is `SolveResponse::Unsat`.
+    pub failed: Vec<Lit>,
+}
+
+/// An error encountered while replaying a session.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The input is malformed.
+    Parse(ParseError),
+    /// The solver returned an error.
+    Solver(SolverError),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Parse(err) => err.fmt(f),
+            ReplayError::Solver(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReplayError::Parse(err) => Some(err),
+            ReplayError::Solver(err) => Some(err),
+        }
+    }
+}
+
+impl From<ParseError> for ReplayError {
+    fn from(err: ParseError) -> Self {
+        ReplayError::Parse(err)
+    }
+}
+
+impl From<SolverError> for ReplayError {
+    fn from(err: SolverError) -> Self {
+        ReplayError::Solver(err)
+    }
+}
+
+/// Drives the solver through the iCNF session of the reader.
+///
+/// Returns a report for every solve of the session in order.
+pub fn replay<S, R>(solver: &mut S, reader: R) -> Result<Vec<SolveReport>, ReplayError>
+where
+    S: IpasirSolver,
+    R: Read,
+{
+    let mut parser = Parser::new(reader);
+    let mut reports = Vec::new();
+    while let Some(step) = parser.next_step() {
+        match step? {
+            Step::Clause(clause) => solver.add_clause(clause.iter()),
+            Step::Solve(assumptions) => {
+                for lit in assumptions.iter() {
+                    solver.assume(lit);
+                }
+                let response = solver.solve()?;
+                let mut failed = Vec::new();
+                if response == SolveResponse::Unsat {
+                    for lit in assumptions.iter() {
+                        if solver.failed(lit)? {
+                            failed.push(lit);
+                        }
+                    }
+                }
+                reports.push(SolveReport {
+                    assumptions: assumptions.iter().collect(),
+                    response,
+                    failed,
+                });
+            }
+        }
+    }
+    Ok(reports)
+}
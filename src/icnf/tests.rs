@@ -0,0 +1,66 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+use std::{
+    string::ToString,
+    vec::Vec,
+};
+
+#[test]
+fn parse_steps() {
+    let input = "c session\np inccnf\n1 2 0\n-1 0 3 0\na -2 0\na 0\n";
+    let mut parser = Parser::new(input.as_bytes());
+    let mut steps = Vec::new();
+    while let Some(step) = parser.next_step() {
+        steps.push(match step.unwrap() {
+            Step::Clause(clause) => ('c', clause.iter().map(|lit| lit.to_raw()).collect::<Vec<_>>()),
+            Step::Solve(lits) => ('a', lits.iter().map(|lit| lit.to_raw()).collect()),
+        });
+    }
+    assert_eq!(steps, vec![
+        ('c', vec![1, 2]),
+        ('c', vec![-1]),
+        ('c', vec![3]),
+        ('a', vec![-2]),
+        ('a', vec![]),
+    ]);
+}
+
+#[test]
+fn missing_header() {
+    let mut parser = Parser::new("1 2 0\n".as_bytes());
+    let err = parser.next_step().unwrap().unwrap_err();
+    assert_eq!((err.line(), err.column(), err.kind().to_string()), (1, 1, "missing header".to_string()));
+    assert!(parser.next_step().is_none());
+}
+
+#[test]
+fn log_and_replay_session() {
+    let mut solver = Logging::new(TestSolver::init(), Vec::new()).unwrap();
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.add_clause(vec![lit(-1), lit(2)]);
+    solver.assume(lit(-2));
+    solver.assume(lit(3));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Unsat));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    let (_, log) = solver.finish().unwrap();
+    let log = log.unwrap();
+    assert_eq!(log, b"p inccnf\n1 2 0\n-1 2 0\na -2 3 0\na 0\n");
+
+    let reports = replay(&mut TestSolver::init(), log.as_slice()).unwrap();
+    assert_eq!(reports, vec![
+        SolveReport {
+            assumptions: vec![lit(-2), lit(3)],
+            response: SolveResponse::Unsat,
+            failed: vec![lit(-2)],
+        },
+        SolveReport {
+            assumptions: vec![],
+            response: SolveResponse::Sat,
+            failed: vec![],
+        },
+    ]);
+}
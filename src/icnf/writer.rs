@@ -0,0 +1,205 @@
+use crate::{
+    Lit,
+    Clause,
+    OwnedClause,
+    IpasirSolver,
+    SolveResponse,
+    Result,
+    LitValue,
+    SolveControl,
+};
+use std::{
+    io::{
+        self,
+        Write,
+    },
+    vec::Vec,
+};
+
+/// Writes iCNF output.
+#[derive(Debug)]
+pub struct Writer<W> {
+    /// The underlying writer.
+    writer: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a new iCNF writer and writes the header.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writeln!(writer, "p inccnf")?;
+        Ok(Self { writer })
+    }
+
+    /// Writes the literals followed by `0` on a line with the given prefix.
+    fn write_line<I>(&mut self, prefix: &str, lits: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        self.writer.write_all(prefix.as_bytes())?;
+        for lit in lits {
+            write!(self.writer, "{} ", lit.to_raw())?;
+        }
+        writeln!(self.writer, "0")
+    }
+
+    /// Writes a clause line.
+    pub fn write_clause(&mut self, clause: Clause) -> io::Result<()> {
+        self.write_line("", clause.iter())
+    }
+
+    /// Writes an assumption line that solves under the given assumptions.
+    pub fn write_solve(&mut self, assumptions: Clause) -> io::Result<()> {
+        self.write_line("a ", assumptions.iter())
+    }
+
+    /// Writes a comment line.
+    pub fn write_comment(&mut self, comment: &str) -> io::Result<()> {
+        for line in comment.lines() {
+            writeln!(self.writer, "c {}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Wraps an IPASIR solver and logs its session in iCNF format.
+///
+/// # Note
+///
+/// Since the solver interface cannot report I/O errors the first
+/// error is stored, logging stops and the error is returned by `finish`.
+/// A solver created through `IpasirSolver::init` has no log target
+/// and does not log anything.
+pub struct Logging<S, W> {
+    /// The inner solver.
+    solver: S,
+    /// The log target.
+    writer: Option<Writer<W>>,
+    /// The assumptions of the next solve.
+    assumptions: Vec<Lit>,
+    /// The first error encountered while logging.
+    error: Option<io::Error>,
+}
+
+impl<S, W> Logging<S, W>
+where
+    W: Write,
+{
+    /// Wraps the given solver logging to the given writer.
+    pub fn new(solver: S, writer: W) -> io::Result<Self> {
+        Ok(Self {
+            solver,
+            writer: Some(Writer::new(writer)?),
+            assumptions: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// Returns a shared reference to the inner solver.
+    pub fn get_ref(&self) -> &S {
+        &self.solver
+    }
+
+    /// Returns the first error encountered while logging if any.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Flushes the log and returns the inner solver and the log target.
+    pub fn finish(self) -> io::Result<(S, Option<W>)> {
+        if let Some(error) = self.error {
+            return Err(error)
+        }
+        let writer = match self.writer {
+            Some(writer) => {
+                let mut writer = writer.into_inner();
+                writer.flush()?;
+                Some(writer)
+            }
+            None => None,
+        };
+        Ok((self.solver, writer))
+    }
+
+    /// Logs with the given function unless logging has failed before.
+    fn log<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Writer<W>) -> io::Result<()>,
+    {
+        if self.error.is_some() {
+            return
+        }
+        if let Some(writer) = self.writer.as_mut() {
+            self.error = f(writer).err();
+        }
+    }
+}
+
+impl<S, W> IpasirSolver for Logging<S, W>
+where
+    S: IpasirSolver,
+    W: Write,
+{
+    fn signature(&self) -> &'static str {
+        self.solver.signature()
+    }
+
+    fn init() -> Self {
+        Self {
+            solver: S::init(),
+            writer: None,
+            assumptions: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn add_clause<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        let clause: OwnedClause = lits.into_iter().collect();
+        self.solver.add_clause(&clause);
+        self.log(|writer| writer.write_clause(clause.as_clause()));
+    }
+
+    fn assume(&mut self, lit: Lit) {
+        self.solver.assume(lit);
+        self.assumptions.push(lit);
+    }
+
+    fn solve(&mut self) -> Result<SolveResponse> {
+        let assumptions = core::mem::take(&mut self.assumptions);
+        self.log(|writer| writer.write_solve(Clause::from(assumptions.as_slice())));
+        self.solver.solve()
+    }
+
+    fn val(&mut self, lit: Lit) -> Result<LitValue> {
+        self.solver.val(lit)
+    }
+
+    fn failed(&mut self, lit: Lit) -> Result<bool> {
+        self.solver.failed(lit)
+    }
+
+    fn set_terminate<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> SolveControl + 'static,
+    {
+        self.solver.set_terminate(callback)
+    }
+
+    fn set_learn<F>(&mut self, max_len: usize, callback: F)
+    where
+        F: FnMut(Clause) + 'static,
+    {
+        self.solver.set_learn(max_len, callback)
+    }
+}
@@ -2,11 +2,19 @@ use crate::{
     Lit,
     InvalidLitVal,
     Clause,
+    OwnedClause,
+    VarManager,
+    formula::{
+        Encoder,
+        Formula,
+        Polarity,
+    },
 };
-use std::{
-    error::Error,
+use alloc::vec::Vec;
+use core::{
+    ffi::c_int,
     fmt,
-    os::raw::c_int,
+    ops,
     result::Result as StdResult,
 };
 
@@ -17,6 +25,7 @@ use std::{
 ///
 /// This can only be encountered when working with an FFI IPASIR solver.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ResponseError {
     /// The `solve` call returned an invalid response.
     Solve(c_int),
@@ -36,15 +45,67 @@ impl fmt::Display for ResponseError {
     }
 }
 
-impl Error for ResponseError {}
+#[cfg(feature = "std")]
+impl std::error::Error for ResponseError {}
+
+/// An error encountered when a solver answer could not be verified.
+///
+/// # Note
+///
+/// This can only be encountered when working with a verifying solver wrapper.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum VerificationError {
+    /// The model of a satisfiable solve violates the clause.
+    ViolatedClause(OwnedClause),
+    /// The model of a satisfiable solve violates the assumption.
+    ViolatedAssumption(Lit),
+    /// The failed assumptions of an unsatisfiable solve are satisfiable.
+    SatisfiableCore(Vec<Lit>),
+    /// The solver checking the failed assumptions was interrupted.
+    Inconclusive,
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerificationError::ViolatedClause(clause) => {
+                write!(f, "model violates clause")?;
+                for lit in clause {
+                    write!(f, " {}", lit.to_raw())?;
+                }
+                Ok(())
+            }
+            VerificationError::ViolatedAssumption(lit) => {
+                write!(f, "model violates assumption {}", lit.to_raw())
+            }
+            VerificationError::SatisfiableCore(core) => {
+                write!(f, "failed assumptions are satisfiable:")?;
+                for lit in core {
+                    write!(f, " {}", lit.to_raw())?;
+                }
+                Ok(())
+            }
+            VerificationError::Inconclusive => {
+                write!(f, "verification of failed assumptions was interrupted")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationError {}
 
 /// A kind of a SAT solver error.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum SolverErrorKind {
     /// A literal value was invalid.
     Lit(InvalidLitVal),
     /// Encountered when calling an invalid FFI IPASIR solver.
     Response(ResponseError),
+    /// Encountered when a solver answer failed verification.
+    Verification(VerificationError),
     /// Returned when a solver was called in an invalid solver state.
     ///
     /// # Note
@@ -58,6 +119,7 @@ impl fmt::Display for SolverErrorKind {
         match self {
             SolverErrorKind::Lit(e) => e.fmt(f),
             SolverErrorKind::Response(e) => e.fmt(f),
+            SolverErrorKind::Verification(e) => e.fmt(f),
             SolverErrorKind::InvalidSolverState => write!(f, "invalid solver state"),
         }
     }
@@ -65,6 +127,7 @@ impl fmt::Display for SolverErrorKind {
 
 /// An error encountered at some solver calls.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct SolverError {
     /// The kind of the solver error.
     kind: SolverErrorKind,
@@ -76,7 +139,8 @@ impl fmt::Display for SolverError {
     }
 }
 
-impl Error for SolverError {}
+#[cfg(feature = "std")]
+impl std::error::Error for SolverError {}
 
 impl SolverError {
     /// Returns the kind of the error.
@@ -85,6 +149,12 @@ impl SolverError {
     }
 }
 
+impl From<SolverErrorKind> for SolverError {
+    fn from(kind: SolverErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
 impl From<InvalidLitVal> for SolverError {
     fn from(err: InvalidLitVal) -> Self {
         Self {
@@ -101,11 +171,20 @@ impl From<ResponseError> for SolverError {
     }
 }
 
+impl From<VerificationError> for SolverError {
+    fn from(err: VerificationError) -> Self {
+        Self {
+            kind: SolverErrorKind::Verification(err)
+        }
+    }
+}
+
 /// Type alias that has a `SolverError` as error variant.
 pub type Result<T> = StdResult<T, SolverError>;
 
 /// Possible responses from a call to `ipasir_solve`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum SolveResponse {
     /// The solver found the input to be satisfiable.
     Sat = 10,
@@ -116,9 +195,11 @@ pub enum SolveResponse {
 }
 
 /// The assignment of a literal.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum LitValue {
     /// Any assignment is okay.
+    #[default]
     DontCare,
     /// The literal is `true`.
     True,
@@ -126,6 +207,117 @@ pub enum LitValue {
     False
 }
 
+impl LitValue {
+    /// Returns the Kleene conjunction of `self` and `other`.
+    pub fn and(self, other: Self) -> Self {
+        match (self, other) {
+            (LitValue::False, _) | (_, LitValue::False) => LitValue::False,
+            (LitValue::True, LitValue::True) => LitValue::True,
+            _ => LitValue::DontCare,
+        }
+    }
+
+    /// Returns the Kleene disjunction of `self` and `other`.
+    pub fn or(self, other: Self) -> Self {
+        match (self, other) {
+            (LitValue::True, _) | (_, LitValue::True) => LitValue::True,
+            (LitValue::False, LitValue::False) => LitValue::False,
+            _ => LitValue::DontCare,
+        }
+    }
+
+    /// Returns the Kleene implication of `other` by `self`.
+    pub fn implies(self, other: Self) -> Self {
+        (!self).or(other)
+    }
+
+    /// Returns the Kleene exclusive disjunction of `self` and `other`.
+    pub fn xor(self, other: Self) -> Self {
+        match (self.to_option(), other.to_option()) {
+            (Some(lhs), Some(rhs)) => LitValue::from(lhs != rhs),
+            _ => LitValue::DontCare,
+        }
+    }
+
+    /// Returns `Some` truth value or `None` for `LitValue::DontCare`.
+    pub fn to_option(self) -> Option<bool> {
+        match self {
+            LitValue::True => Some(true),
+            LitValue::False => Some(false),
+            LitValue::DontCare => None,
+        }
+    }
+
+    /// Returns `true` if `self` is `LitValue::True`.
+    pub fn is_true(self) -> bool {
+        self == LitValue::True
+    }
+
+    /// Returns `true` if `self` is `LitValue::False`.
+    pub fn is_false(self) -> bool {
+        self == LitValue::False
+    }
+}
+
+impl From<bool> for LitValue {
+    fn from(value: bool) -> Self {
+        if value {
+            LitValue::True
+        } else {
+            LitValue::False
+        }
+    }
+}
+
+impl From<Option<bool>> for LitValue {
+    fn from(value: Option<bool>) -> Self {
+        value.map(LitValue::from).unwrap_or(LitValue::DontCare)
+    }
+}
+
+impl From<LitValue> for Option<bool> {
+    fn from(value: LitValue) -> Self {
+        value.to_option()
+    }
+}
+
+impl ops::Not for LitValue {
+    type Output = Self;
+
+    /// Returns the Kleene negation of `self`.
+    fn not(self) -> Self {
+        match self {
+            LitValue::True => LitValue::False,
+            LitValue::False => LitValue::True,
+            LitValue::DontCare => LitValue::DontCare,
+        }
+    }
+}
+
+impl ops::BitAnd for LitValue {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.and(rhs)
+    }
+}
+
+impl ops::BitOr for LitValue {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.or(rhs)
+    }
+}
+
+impl ops::BitXor for LitValue {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        self.xor(rhs)
+    }
+}
+
 /// The IPASIR interface a SAT solver has to implement to be conforming.
 pub trait IpasirSolver {
     /// Returns name and version of the incremental SAT solving implementation.
@@ -218,6 +410,42 @@ pub trait IpasirSolver {
     fn set_learn<F>(&mut self, max_len: usize, callback: F)
     where
         F: FnMut(Clause) + 'static;
+
+    /// Adds the definition of a literal equivalent to the formula and returns it.
+    ///
+    /// # Note
+    ///
+    /// The encoder reuses the literals of gates it has encoded before, so it must
+    /// always be used with the same solver and variable manager.
+    ///
+    /// # States
+    ///
+    /// - **Required:** any
+    /// - **After:** INPUT
+    fn add_formula(&mut self, encoder: &mut Encoder, vars: &mut VarManager, formula: &Formula) -> Lit
+    where
+        Self: Sized,
+    {
+        encoder.encode(self, vars, formula, Polarity::Both)
+    }
+
+    /// Adds clauses that are satisfiable if and only if the formula is.
+    ///
+    /// # Note
+    ///
+    /// The encoder reuses the literals of gates it has encoded before, so it must
+    /// always be used with the same solver and variable manager.
+    ///
+    /// # States
+    ///
+    /// - **Required:** any
+    /// - **After:** INPUT
+    fn assert_formula(&mut self, encoder: &mut Encoder, vars: &mut VarManager, formula: &Formula)
+    where
+        Self: Sized,
+    {
+        encoder.assert(self, vars, formula)
+    }
 }
 
 /// Tells the solver to either stop solving process or continue.
@@ -226,6 +454,7 @@ pub trait IpasirSolver {
 /// 
 /// Use this as return type of the `Solver::set_terminate` callback.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum SolveControl {
     /// Continue with the solving process.
     Continue = 0,
@@ -3,31 +3,94 @@
 //! Visit the IPASIR manual [here][manual].
 //!
 //! [manual]: http://www.cs.utexas.edu/users/moore/acl2/manuals/current/manual/index-seo.php/IPASIR____IPASIR
+//!
+//! # Features
+//!
+//! - `std` (default): Implements `std::error::Error` for the error types
+//!   and enables everything that requires the standard library.
+//!   Without it the crate is `no_std` but still requires `alloc`.
+//! - `ffi`: Enables the `ffi` module to link against a C IPASIR solver. Implies `std`.
+//! - `serde`: Implements `Serialize` and `Deserialize` for the core types.
+
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_test;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
+pub mod verify;
+pub mod record;
+pub mod map;
+pub mod encodings;
+pub mod formula;
+#[cfg(feature = "std")]
+pub mod aiger;
+#[cfg(feature = "std")]
+pub mod dimacs;
+#[cfg(feature = "std")]
+pub mod icnf;
+#[cfg(feature = "std")]
+pub mod drat;
+#[cfg(feature = "std")]
+pub mod lrat;
+#[cfg(feature = "std")]
+pub mod opb;
+#[cfg(feature = "std")]
+pub mod qdimacs;
+#[cfg(feature = "std")]
+pub mod solution;
+#[cfg(feature = "std")]
+pub mod trace;
+#[cfg(feature = "std")]
+pub mod wcnf;
+
 mod types;
 mod interface;
+mod cnf;
+mod model;
+mod vars;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
 
 #[cfg(test)]
 mod tests;
 
 pub use self::{
     types::{
+        MAX_VAR,
         Sign,
         Var,
         Lit,
         InvalidLitVal,
         Clause,
+        OwnedClause,
         LitIter,
     },
+    cnf::Cnf,
+    model::Model,
+    vars::VarManager,
+    map::{
+        VarMap,
+        LitMap,
+        LitSet,
+    },
     interface::{
         SolverErrorKind,
         SolverError,
         SolveResponse,
         Result,
         ResponseError,
+        VerificationError,
         LitValue,
         IpasirSolver,
         SolveControl,
@@ -0,0 +1,278 @@
+use super::{
+    Parser,
+    Step,
+};
+use crate::{
+    dimacs::{
+        read_cnf,
+        ParseError,
+    },
+    Lit,
+    OwnedClause,
+    Cnf,
+    LitValue,
+    VarMap,
+    LitMap,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::Read,
+    vec::Vec,
+};
+
+/// An error encountered while checking an LRAT proof.
+#[derive(Debug)]
+pub enum CheckError {
+    /// The DIMACS CNF formula is malformed.
+    Cnf(ParseError),
+    /// The proof is malformed.
+    Proof(ParseError),
+    /// A lemma does not follow from its hints.
+    Rejected {
+        /// The identifier of the lemma.
+        id: u64,
+        /// The rejected lemma.
+        lemma: OwnedClause,
+    },
+    /// A hint or deletion refers to a clause that does not exist.
+    UnknownClause(u64),
+    /// A lemma reuses the identifier of an existing clause.
+    DuplicateId(u64),
+    /// The proof does not add the empty clause.
+    NoEmptyClause,
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::Cnf(err) => write!(f, "formula: {}", err),
+            CheckError::Proof(err) => write!(f, "proof: {}", err),
+            CheckError::Rejected { id, .. } => write!(f, "lemma {} does not follow from its hints", id),
+            CheckError::UnknownClause(id) => write!(f, "unknown clause {}", id),
+            CheckError::DuplicateId(id) => write!(f, "clause {} already exists", id),
+            CheckError::NoEmptyClause => write!(f, "proof does not derive the empty clause"),
+        }
+    }
+}
+
+impl Error for CheckError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckError::Cnf(err) | CheckError::Proof(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A forward LRAT checker over a formula.
+///
+/// Every lemma is checked when it is added by propagating its hints only.
+///
+/// # Note
+///
+/// RAT lemmas are checked against all clauses containing the negated pivot
+/// which are looked up in an index of the clauses containing each literal.
+pub struct Checker {
+    /// The literals of all clauses by their identifiers.
+    clauses: HashMap<u64, Vec<Lit>>,
+    /// The identifiers of the clauses containing each literal.
+    occurrences: LitMap<Vec<u64>>,
+    /// The current assignment.
+    values: VarMap<LitValue>,
+    /// The assigned literals in order.
+    trail: Vec<Lit>,
+    /// `true` if the empty clause has been added.
+    refuted: bool,
+}
+
+impl Checker {
+    /// Creates a new checker over the clauses of the formula numbered from `1`.
+    pub fn new(cnf: &Cnf) -> Self {
+        let mut checker = Self {
+            clauses: HashMap::new(),
+            occurrences: LitMap::new(),
+            values: VarMap::new(),
+            trail: Vec::new(),
+            refuted: false,
+        };
+        for (n, clause) in cnf.clauses().enumerate() {
+            checker.insert(n as u64 + 1, clause.iter().collect());
+        }
+        checker
+    }
+
+    /// Adds the clause with the given identifier.
+    fn insert(&mut self, id: u64, lits: Vec<Lit>) {
+        for &lit in &lits {
+            let ids = &mut self.occurrences[lit];
+            // Clauses of the formula may repeat a literal.
+            if ids.last() != Some(&id) {
+                ids.push(id);
+            }
+        }
+        self.clauses.insert(id, lits);
+    }
+
+    /// Removes the clause with the given identifier and returns `false` if there is none.
+    fn remove(&mut self, id: u64) -> bool {
+        let lits = match self.clauses.remove(&id) {
+            Some(lits) => lits,
+            None => return false,
+        };
+        for lit in lits {
+            self.occurrences[lit].retain(|&other| other != id);
+        }
+        true
+    }
+
+    /// Returns `true` if the empty clause has been added.
+    pub fn is_refuted(&self) -> bool {
+        self.refuted
+    }
+
+    /// Checks and applies the next step of the proof.
+    pub fn add_step(&mut self, step: &Step) -> Result<(), CheckError> {
+        match step {
+            Step::Add { id, clause, hints } => {
+                if self.clauses.contains_key(id) {
+                    return Err(CheckError::DuplicateId(*id))
+                }
+                let lemma: Vec<Lit> = clause.iter().collect();
+                let justified = self.verify(&lemma, hints);
+                self.backtrack(0);
+                if !justified? {
+                    return Err(CheckError::Rejected { id: *id, lemma: clause.clone() })
+                }
+                self.refuted |= lemma.is_empty();
+                self.insert(*id, lemma);
+            }
+            Step::Delete { clauses, .. } => {
+                for id in clauses {
+                    if !self.remove(*id) {
+                        return Err(CheckError::UnknownClause(*id))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error unless the empty clause has been added.
+    pub fn finish(self) -> Result<(), CheckError> {
+        if self.refuted { Ok(()) } else { Err(CheckError::NoEmptyClause) }
+    }
+
+    /// Returns the value of the literal under the current assignment.
+    fn value(&self, lit: Lit) -> LitValue {
+        lit.eval(self.values.get(lit.var()).cloned().unwrap_or_default())
+    }
+
+    /// Assigns the literal to be `true`.
+    fn assign(&mut self, lit: Lit) {
+        self.values[lit.var()] = lit.eval(LitValue::True);
+        self.trail.push(lit);
+    }
+
+    /// Undoes all assignments after the first `len` ones.
+    fn backtrack(&mut self, len: usize) {
+        for lit in self.trail.drain(len..) {
+            self.values[lit.var()] = LitValue::DontCare;
+        }
+    }
+
+    /// Assigns the negations of the literals unless one of them is `true`.
+    ///
+    /// Returns `true` if one of them is `true`.
+    fn assign_negated<'a, I>(&mut self, lits: I) -> bool
+    where
+        I: IntoIterator<Item = &'a Lit>,
+    {
+        for &lit in lits {
+            match self.value(lit) {
+                LitValue::True => return true,
+                LitValue::False => (),
+                LitValue::DontCare => self.assign(!lit),
+            }
+        }
+        false
+    }
+
+    /// Propagates the hints in order.
+    ///
+    /// Returns `true` if the last hint is falsified and `false`
+    /// if a hint is neither unit nor falsified.
+    fn propagate(&mut self, hints: &[i64]) -> Result<bool, CheckError> {
+        for &hint in hints {
+            let id = hint as u64;
+            let lits = self.clauses.get(&id).ok_or(CheckError::UnknownClause(id))?;
+            let mut unit = None;
+            for &lit in lits {
+                match self.value(lit) {
+                    LitValue::True => return Ok(false),
+                    LitValue::False => (),
+                    LitValue::DontCare if unit.is_none() => unit = Some(lit),
+                    LitValue::DontCare => return Ok(false),
+                }
+            }
+            match unit {
+                Some(lit) => self.assign(lit),
+                None => return Ok(true),
+            }
+        }
+        Ok(false)
+    }
+
+    /// Checks that the lemma follows from the hints.
+    fn verify(&mut self, lemma: &[Lit], hints: &[i64]) -> Result<bool, CheckError> {
+        if self.assign_negated(lemma) {
+            return Ok(true)
+        }
+        let split = hints.iter().position(|&hint| hint < 0).unwrap_or(hints.len());
+        if self.propagate(&hints[..split])? {
+            return Ok(true)
+        }
+        let pivot = match lemma.first() {
+            Some(&pivot) => pivot,
+            None => return Ok(false),
+        };
+        let mut groups = HashMap::new();
+        let mut rest = &hints[split..];
+        while let Some((&candidate, tail)) = rest.split_first() {
+            let len = tail.iter().position(|&hint| hint < 0).unwrap_or(tail.len());
+            groups.insert(candidate.unsigned_abs(), &tail[..len]);
+            rest = &tail[len..];
+        }
+        let mut candidates = self.occurrences.get(!pivot).cloned().unwrap_or_default();
+        candidates.sort_unstable();
+        let base = self.trail.len();
+        for id in candidates {
+            let group = match groups.get(&id) {
+                Some(group) => *group,
+                None => return Ok(false),
+            };
+            let resolvent: Vec<Lit> = self.clauses[&id].iter().cloned().filter(|&lit| lit != !pivot).collect();
+            let justified = self.assign_negated(&resolvent) || self.propagate(group)?;
+            self.backtrack(base);
+            if !justified {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Checks an LRAT proof of either format against a strictly parsed DIMACS CNF formula.
+pub fn check_lrat<C, P>(cnf: C, proof: P) -> Result<(), CheckError>
+where
+    C: Read,
+    P: Read,
+{
+    let cnf = read_cnf(cnf).map_err(CheckError::Cnf)?;
+    let mut checker = Checker::new(&cnf);
+    for step in Parser::new(proof).map_err(CheckError::Proof)? {
+        checker.add_step(&step.map_err(CheckError::Proof)?)?;
+    }
+    checker.finish()
+}
@@ -0,0 +1,58 @@
+//! Reading, writing and checking LRAT proofs of unsatisfiability.
+//!
+//! An LRAT proof annotates every lemma with the clauses that make it follow by
+//! unit propagation. Clauses are identified by positive integers, where the
+//! clauses of the formula are numbered from `1` in order.
+//!
+//! In the text format a lemma is written as `<id> <lits> 0 <hints> 0` and a deletion as
+//! `<id> d <ids> 0`. Positive hints are unit clauses in propagation order and a negative hint
+//! `-<id>` starts the hints for the RAT candidate with that identifier. Comment lines start with `c`.
+//! The binary format uses the markers and integer encoding of binary DRAT proofs.
+//!
+//! DRAT proofs can be turned into LRAT proofs with `drat::Checker::elaborate`.
+
+mod parser;
+mod writer;
+mod checker;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    parser::{
+        LratErrorKind,
+        Parser,
+    },
+    writer::write_step,
+    checker::{
+        check_lrat,
+        CheckError,
+        Checker,
+    },
+};
+pub use crate::drat::Format;
+use crate::OwnedClause;
+use std::vec::Vec;
+
+/// A step of an LRAT proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// Adds a lemma.
+    Add {
+        /// The identifier of the lemma.
+        id: u64,
+        /// The lemma.
+        clause: OwnedClause,
+        /// The hints justifying the lemma.
+        hints: Vec<i64>,
+    },
+    /// Deletes clauses.
+    Delete {
+        /// The identifier of the step.
+        ///
+        /// This is not part of the binary format and `0` for binary proofs.
+        id: u64,
+        /// The identifiers of the deleted clauses.
+        clauses: Vec<u64>,
+    },
+}
@@ -0,0 +1,235 @@
+use super::{
+    Format,
+    Step,
+};
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    drat::BinaryReader,
+    Lit,
+    OwnedClause,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::Read,
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to LRAT proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LratErrorKind {
+    /// A clause identifier, literal or hint is out of its range.
+    InvalidValue(i128),
+}
+
+impl fmt::Display for LratErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LratErrorKind::InvalidValue(value) => write!(f, "invalid value {}", value),
+        }
+    }
+}
+
+impl Error for LratErrorKind {}
+
+impl From<LratErrorKind> for ParseErrorKind {
+    fn from(kind: LratErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// The input of a parser depending on the format.
+enum Source<R> {
+    /// Text input.
+    Text(Lexer<R>),
+    /// Binary input.
+    Binary(BinaryReader<R>),
+}
+
+/// A streaming parser for LRAT proofs in either format.
+///
+/// # Note
+///
+/// Errors in binary proofs are reported at line 1
+/// with the one-based byte offset as column.
+pub struct Parser<R> {
+    /// The input.
+    source: Source<R>,
+    /// `true` if the input is exhausted or an error occured.
+    done: bool,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Creates a new parser detecting the format from the first bytes of the input.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut lexer = Lexer::new(reader);
+        let format = Format::detect(lexer.peek_buf()?);
+        Ok(Self::from_lexer(lexer, format))
+    }
+
+    /// Creates a new parser for input of the given format.
+    pub fn with_format(reader: R, format: Format) -> Self {
+        Self::from_lexer(Lexer::new(reader), format)
+    }
+
+    /// Creates a new parser reading the given format from the lexer.
+    fn from_lexer(lexer: Lexer<R>, format: Format) -> Self {
+        let source = match format {
+            Format::Text => Source::Text(lexer),
+            Format::Binary => Source::Binary(BinaryReader::new(lexer.into_inner())),
+        };
+        Self {
+            source,
+            done: false,
+        }
+    }
+
+    /// Returns the format of the input.
+    pub fn format(&self) -> Format {
+        match self.source {
+            Source::Text(_) => Format::Text,
+            Source::Binary(_) => Format::Binary,
+        }
+    }
+
+    /// Reads whitespace separated non-zero integers up to the terminating `0`.
+    fn read_ints(lexer: &mut Lexer<R>) -> Result<Vec<(i128, (usize, usize))>> {
+        let mut ints = Vec::new();
+        loop {
+            lexer.skip_whitespace()?;
+            let start = lexer.position();
+            match lexer.read_int()? {
+                Some(0) => return Ok(ints),
+                Some(value) => ints.push((value, start)),
+                None => return lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+    }
+
+    /// Reads whitespace separated clause identifiers up to the terminating `0`.
+    fn read_ids(lexer: &mut Lexer<R>) -> Result<Vec<u64>> {
+        let mut ids = Vec::new();
+        for (value, start) in Self::read_ints(lexer)? {
+            if !(1..=i128::from(u64::MAX)).contains(&value) {
+                return lexer.error_at(start, LratErrorKind::InvalidValue(value))
+            }
+            ids.push(value as u64);
+        }
+        Ok(ids)
+    }
+
+    /// Parses the next step of text input.
+    fn parse_text(lexer: &mut Lexer<R>) -> Result<Option<Step>> {
+        let id = loop {
+            lexer.skip_whitespace()?;
+            let start = lexer.position();
+            let byte = match lexer.peek()? {
+                Some(byte) => byte,
+                None => return Ok(None),
+            };
+            if lexer.take_line_start() && byte == b'c' {
+                lexer.skip_line()?;
+                continue
+            }
+            match lexer.read_int()? {
+                Some(id) if (1..=i128::from(u64::MAX)).contains(&id) => break id as u64,
+                Some(id) => return lexer.error_at(start, LratErrorKind::InvalidValue(id)),
+                None => return lexer.unexpected(ParseErrorKind::UnexpectedEof),
+            }
+        };
+        lexer.skip_whitespace()?;
+        if lexer.peek()? == Some(b'd') {
+            lexer.bump(b'd');
+            let clauses = Self::read_ids(lexer)?;
+            return Ok(Some(Step::Delete { id, clauses }))
+        }
+        let mut lits = Vec::new();
+        for (value, start) in Self::read_ints(lexer)? {
+            match Lit::try_from_wide(value) {
+                Ok(lit) => lits.push(lit),
+                Err(err) => return lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+            }
+        }
+        let mut hints = Vec::new();
+        for (value, start) in Self::read_ints(lexer)? {
+            if !(i128::from(-i64::MAX)..=i128::from(i64::MAX)).contains(&value) {
+                return lexer.error_at(start, LratErrorKind::InvalidValue(value))
+            }
+            hints.push(value as i64);
+        }
+        Ok(Some(Step::Add { id, clause: OwnedClause::from(lits), hints }))
+    }
+
+    /// Reads binary identifiers or hints up to the terminating `0`.
+    fn read_binary_ints(reader: &mut BinaryReader<R>, signed: bool) -> Result<Vec<i64>> {
+        let mut ints = Vec::new();
+        while let Some((value, start)) = reader.read_signed()? {
+            if value < 0 && !signed || value > i128::from(i64::MAX) {
+                return reader.error_at(start, LratErrorKind::InvalidValue(value))
+            }
+            ints.push(value as i64);
+        }
+        Ok(ints)
+    }
+
+    /// Parses the next step of binary input.
+    fn parse_binary(reader: &mut BinaryReader<R>) -> Result<Option<Step>> {
+        let deletion = match reader.read_marker()? {
+            Some(deletion) => deletion,
+            None => return Ok(None),
+        };
+        if deletion {
+            let clauses = Self::read_binary_ints(reader, false)?;
+            let clauses = clauses.into_iter().map(|id| id as u64).collect();
+            return Ok(Some(Step::Delete { id: 0, clauses }))
+        }
+        let (encoded, start) = reader.read_encoded()?;
+        if encoded == 0 || encoded & 1 == 1 {
+            return reader.error_at(start, LratErrorKind::InvalidValue(i128::from(encoded)))
+        }
+        let id = encoded >> 1;
+        let clause = OwnedClause::from(reader.read_lits()?);
+        let hints = Self::read_binary_ints(reader, true)?;
+        Ok(Some(Step::Add { id, clause, hints }))
+    }
+}
+
+impl<R> Iterator for Parser<R>
+where
+    R: Read,
+{
+    type Item = Result<Step>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let step = match &mut self.source {
+            Source::Text(lexer) => Self::parse_text(lexer),
+            Source::Binary(reader) => Self::parse_binary(reader),
+        };
+        match step {
+            Ok(Some(step)) => Some(Ok(step)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
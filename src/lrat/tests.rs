@@ -0,0 +1,82 @@
+use super::*;
+use crate::{
+    dimacs::read_cnf,
+    drat,
+    tests::lit,
+};
+use std::{
+    string::ToString,
+    vec::Vec,
+};
+
+/// An unsatisfiable formula without conflict under unit propagation.
+const FORMULA: &str = "p cnf 4 5\n-1 2 0\n2 3 0\n2 -3 0\n-2 4 0\n-2 -4 0\n";
+
+/// Checks the LRAT proof against the formula.
+fn check(proof: &str) -> Result<(), CheckError> {
+    check_lrat(FORMULA.as_bytes(), proof.as_bytes())
+}
+
+#[test]
+fn parse_and_write_both_formats() {
+    let input = "c rat lemma\n6 1 0 -1 2 3 0\n6 d 2 3 0\n7 0 6 1 4 5 0\n";
+    let steps: Vec<Step> = Parser::new(input.as_bytes()).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(steps[0], Step::Add { id: 6, clause: vec![lit(1)].into(), hints: vec![-1, 2, 3] });
+    assert_eq!(steps[1], Step::Delete { id: 6, clauses: vec![2, 3] });
+    let mut text = Vec::new();
+    let mut binary = Vec::new();
+    for step in &steps {
+        write_step(&mut text, step, Format::Text).unwrap();
+        write_step(&mut binary, step, Format::Binary).unwrap();
+    }
+    assert_eq!(text, &input.as_bytes()[12..]);
+    assert_eq!(&binary[..9], b"a\x0c\x02\0\x03\x04\x06\0d");
+    let parsed: Vec<Step> = Parser::new(binary.as_slice()).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(parsed[1], Step::Delete { id: 0, clauses: vec![2, 3] });
+    assert_eq!(parsed[2], steps[2]);
+    let err = Parser::new("6 d -2 0\n".as_bytes()).unwrap().next().unwrap().unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 5));
+    assert_eq!(err.kind().format_kind(), Some(&LratErrorKind::InvalidValue(-2)));
+}
+
+#[test]
+fn check_rup_and_rat_hints() {
+    check("6 2 0 2 3 0\n7 0 6 4 5 0\n").unwrap();
+    check("6 1 0 -1 2 3 0\n7 0 6 1 4 5 0\n").unwrap();
+    match check("6 2 0 2 0\n") {
+        Err(CheckError::Rejected { id, lemma }) => assert_eq!((id, lemma.as_slice()), (6, &[lit(2)][..])),
+        other => panic!("unexpected check result: {:?}", other),
+    }
+    // The RAT candidate 1 is missing.
+    assert!(matches!(check("6 1 0 2 3 0\n"), Err(CheckError::Rejected { id: 6, .. })));
+    // Without the deleted candidate 1 the lemma is RAT without any hints.
+    assert!(matches!(check("1 d 1 0\n6 1 0 0\n"), Err(CheckError::NoEmptyClause)));
+    assert_eq!(check("6 2 0 2 8 0\n").unwrap_err().to_string(), "unknown clause 8");
+    assert_eq!(check("5 2 0 2 3 0\n").unwrap_err().to_string(), "clause 5 already exists");
+    assert_eq!(check("6 2 0 2 3 0\n").unwrap_err().to_string(), "proof does not derive the empty clause");
+}
+
+#[test]
+fn elaborate_drat_proofs() {
+    let cnf = read_cnf(FORMULA.as_bytes()).unwrap();
+    for proof in &["1 0\n0\n", "3 0\n1 0\nd 2 3 0\n0\n", "1 0\n2 0\n"] {
+        let mut checker = drat::Checker::new(&cnf);
+        for step in drat::Parser::new(proof.as_bytes()).unwrap() {
+            checker.add_step(step.unwrap());
+        }
+        let steps = checker.elaborate().unwrap();
+        let mut lrat = Checker::new(&cnf);
+        for step in &steps {
+            lrat.add_step(step).unwrap();
+        }
+        assert!(lrat.is_refuted(), "{:?}", steps);
+    }
+    let mut checker = drat::Checker::new(&cnf);
+    checker.add_step(drat::Step::Add(vec![lit(1)].into()));
+    checker.add_step(drat::Step::Add(vec![lit(1), lit(3)].into()));
+    checker.add_step(drat::Step::Add(Vec::<crate::Lit>::new().into()));
+    assert_eq!(checker.elaborate().unwrap(), vec![
+        Step::Add { id: 6, clause: vec![lit(1)].into(), hints: vec![-1, 2, 3] },
+        Step::Add { id: 8, clause: Vec::<crate::Lit>::new().into(), hints: vec![6, 1, 4, 5] },
+    ]);
+}
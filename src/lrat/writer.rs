@@ -0,0 +1,59 @@
+use super::{
+    Format,
+    Step,
+};
+use crate::drat::write_encoded;
+use std::io::{
+    self,
+    Write,
+};
+
+/// Writes the proof step in the given format.
+///
+/// # Note
+///
+/// Identifiers must not exceed `i64::MAX` to be written in binary.
+pub fn write_step<W>(writer: &mut W, step: &Step, format: Format) -> io::Result<()>
+where
+    W: Write,
+{
+    match (step, format) {
+        (Step::Add { id, clause, hints }, Format::Text) => {
+            write!(writer, "{} ", id)?;
+            for lit in clause {
+                write!(writer, "{} ", lit.to_raw())?;
+            }
+            write!(writer, "0")?;
+            for hint in hints {
+                write!(writer, " {}", hint)?;
+            }
+            writeln!(writer, " 0")
+        }
+        (Step::Delete { id, clauses }, Format::Text) => {
+            write!(writer, "{} d", id)?;
+            for clause in clauses {
+                write!(writer, " {}", clause)?;
+            }
+            writeln!(writer, " 0")
+        }
+        (Step::Add { id, clause, hints }, Format::Binary) => {
+            writer.write_all(b"a")?;
+            write_encoded(writer, *id as i64)?;
+            for lit in clause {
+                write_encoded(writer, i64::from(lit.to_raw()))?;
+            }
+            writer.write_all(&[0])?;
+            for &hint in hints {
+                write_encoded(writer, hint)?;
+            }
+            writer.write_all(&[0])
+        }
+        (Step::Delete { clauses, .. }, Format::Binary) => {
+            writer.write_all(b"d")?;
+            for &clause in clauses {
+                write_encoded(writer, clause as i64)?;
+            }
+            writer.write_all(&[0])
+        }
+    }
+}
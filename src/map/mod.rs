@@ -0,0 +1,415 @@
+//! Dense maps and sets indexed by variables and literals.
+//!
+//! These are backed by vectors and grow on demand, so they are best used
+//! with the densely allocated variables that IPASIR solvers expect.
+
+use crate::{
+    Var,
+    Lit,
+    Sign,
+};
+use alloc::vec::Vec;
+use core::{
+    iter,
+    ops,
+    slice,
+};
+
+#[cfg(test)]
+mod tests;
+
+macro_rules! dense_map {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident<T> for $key:ident, covered by $cover:path;
+        $(#[$iter_attr:meta])*
+        pub struct $iter:ident;
+        $(#[$iter_mut_attr:meta])*
+        pub struct $iter_mut:ident;
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name<T> {
+            /// The values indexed by the key indices.
+            values: Vec<T>,
+        }
+
+        impl<T> Default for $name<T> {
+            fn default() -> Self {
+                Self { values: Vec::new() }
+            }
+        }
+
+        impl<T> $name<T> {
+            /// Creates a new empty map.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Creates a new empty map with space for the given number of keys.
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self { values: Vec::with_capacity(capacity) }
+            }
+
+            /// Returns the number of keys covered by the map.
+            pub fn len(&self) -> usize {
+                self.values.len()
+            }
+
+            /// Returns `true` if the map covers no keys.
+            pub fn is_empty(&self) -> bool {
+                self.values.is_empty()
+            }
+
+            /// Returns `true` if the map covers the given key.
+            pub fn contains_key(&self, key: $key) -> bool {
+                key.index() < self.values.len()
+            }
+
+            /// Returns a shared reference to the value of the key if covered.
+            pub fn get(&self, key: $key) -> Option<&T> {
+                self.values.get(key.index())
+            }
+
+            /// Returns an exclusive reference to the value of the key if covered.
+            pub fn get_mut(&mut self, key: $key) -> Option<&mut T> {
+                self.values.get_mut(key.index())
+            }
+
+            /// Grows the map to cover the given key filling new entries with `value`.
+            pub fn grow_to(&mut self, key: $key, value: T)
+            where
+                T: Clone,
+            {
+                let len = $cover(key);
+                if self.values.len() < len {
+                    self.values.resize(len, value);
+                }
+            }
+
+            /// Removes all keys from the map.
+            pub fn clear(&mut self) {
+                self.values.clear()
+            }
+
+            /// Returns an iterator over all keys covered by the map.
+            pub fn keys(&self) -> impl Iterator<Item = $key> + '_ {
+                (0..self.values.len()).map($key::from_index)
+            }
+
+            /// Returns an iterator over all values of the map.
+            pub fn values(&self) -> slice::Iter<'_, T> {
+                self.values.iter()
+            }
+
+            /// Returns an iterator over all values of the map allowing to modify them.
+            pub fn values_mut(&mut self) -> slice::IterMut<'_, T> {
+                self.values.iter_mut()
+            }
+
+            /// Returns an iterator over all keys and their values.
+            pub fn iter(&self) -> $iter<'_, T> {
+                $iter { iter: self.values.iter().enumerate() }
+            }
+
+            /// Returns an iterator over all keys and their values allowing to modify them.
+            pub fn iter_mut(&mut self) -> $iter_mut<'_, T> {
+                $iter_mut { iter: self.values.iter_mut().enumerate() }
+            }
+        }
+
+        impl<T> ops::Index<$key> for $name<T> {
+            type Output = T;
+
+            /// Returns the value of the key.
+            ///
+            /// # Panics
+            ///
+            /// If the key is not covered by the map.
+            fn index(&self, key: $key) -> &T {
+                &self.values[key.index()]
+            }
+        }
+
+        impl<T> ops::IndexMut<$key> for $name<T>
+        where
+            T: Default,
+        {
+            /// Returns the value of the key growing the map with
+            /// default values to cover the key if necessary.
+            fn index_mut(&mut self, key: $key) -> &mut T {
+                let len = $cover(key);
+                if self.values.len() < len {
+                    self.values.resize_with(len, T::default);
+                }
+                &mut self.values[key.index()]
+            }
+        }
+
+        impl<T> From<Vec<T>> for $name<T> {
+            /// Creates a map whose values are indexed by their key indices.
+            fn from(values: Vec<T>) -> Self {
+                Self { values }
+            }
+        }
+
+        impl<T> iter::FromIterator<T> for $name<T> {
+            /// Creates a map whose values are indexed by their key indices.
+            fn from_iter<I>(values: I) -> Self
+            where
+                I: IntoIterator<Item = T>,
+            {
+                Self { values: values.into_iter().collect() }
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a $name<T> {
+            type Item = ($key, &'a T);
+            type IntoIter = $iter<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a mut $name<T> {
+            type Item = ($key, &'a mut T);
+            type IntoIter = $iter_mut<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter_mut()
+            }
+        }
+
+        $(#[$iter_attr])*
+        #[derive(Debug, Clone)]
+        pub struct $iter<'a, T> {
+            /// The underlying iterator.
+            iter: iter::Enumerate<slice::Iter<'a, T>>,
+        }
+
+        impl<'a, T> Iterator for $iter<'a, T> {
+            type Item = ($key, &'a T);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.iter.next().map(|(index, value)| ($key::from_index(index), value))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<'a, T> ExactSizeIterator for $iter<'a, T> {}
+
+        $(#[$iter_mut_attr])*
+        #[derive(Debug)]
+        pub struct $iter_mut<'a, T> {
+            /// The underlying iterator.
+            iter: iter::Enumerate<slice::IterMut<'a, T>>,
+        }
+
+        impl<'a, T> Iterator for $iter_mut<'a, T> {
+            type Item = ($key, &'a mut T);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.iter.next().map(|(index, value)| ($key::from_index(index), value))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<'a, T> ExactSizeIterator for $iter_mut<'a, T> {}
+    };
+}
+
+/// Returns the length of a `VarMap` covering the variable.
+fn var_map_len(var: Var) -> usize {
+    var.index() + 1
+}
+
+/// Returns the length of a `LitMap` covering both literals of the variable of the literal.
+fn lit_map_len(lit: Lit) -> usize {
+    lit.var().lit(Sign::Neg).index() + 1
+}
+
+dense_map! {
+    /// A dense map from variables to values.
+    pub struct VarMap<T> for Var, covered by var_map_len;
+    /// Iterator over the variables and values of a `VarMap`.
+    pub struct VarMapIter;
+    /// Iterator over the variables and mutable values of a `VarMap`.
+    pub struct VarMapIterMut;
+}
+
+dense_map! {
+    /// A dense map from literals to values.
+    ///
+    /// # Note
+    ///
+    /// Growing the map always covers both literals of a variable together.
+    /// Maps created from vectors or iterators cover exactly the given values.
+    pub struct LitMap<T> for Lit, covered by lit_map_len;
+    /// Iterator over the literals and values of a `LitMap`.
+    pub struct LitMapIter;
+    /// Iterator over the literals and mutable values of a `LitMap`.
+    pub struct LitMapIterMut;
+}
+
+impl<T> VarMap<T> {
+    /// Grows the map to cover all variables up to the given one
+    /// filling new entries with default values.
+    pub fn reserve_var(&mut self, var: Var)
+    where
+        T: Default,
+    {
+        let len = var_map_len(var);
+        if self.values.len() < len {
+            self.values.resize_with(len, T::default);
+        }
+    }
+}
+
+impl<T> LitMap<T> {
+    /// Grows the map to cover both literals of all variables up to
+    /// the given one filling new entries with default values.
+    pub fn reserve_var(&mut self, var: Var)
+    where
+        T: Default,
+    {
+        let len = lit_map_len(var.lit(Sign::Pos));
+        if self.values.len() < len {
+            self.values.resize_with(len, T::default);
+        }
+    }
+}
+
+/// The number of bits per word of a `LitSet`.
+const WORD_BITS: usize = 64;
+
+/// A compact set of literals backed by a bitset.
+///
+/// # Note
+///
+/// Insertion and membership tests take constant time.
+/// The set also remembers its members so that iteration and `clear`
+/// only take time proportional to the number of contained literals
+/// instead of the number of literals covered by the bitset.
+#[derive(Debug, Clone, Default)]
+pub struct LitSet {
+    /// One bit per literal index.
+    bits: Vec<u64>,
+    /// The contained literals in insertion order.
+    members: Vec<Lit>,
+}
+
+impl LitSet {
+    /// Creates a new empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of literals in the set.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the set contains no literals.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns `true` if the set contains the literal.
+    pub fn contains(&self, lit: Lit) -> bool {
+        let index = lit.index();
+        self.bits
+            .get(index / WORD_BITS)
+            .map(|word| word & (1 << (index % WORD_BITS)) != 0)
+            .unwrap_or(false)
+    }
+
+    /// Inserts the literal into the set.
+    ///
+    /// Returns `true` if the literal was not yet contained.
+    pub fn insert(&mut self, lit: Lit) -> bool {
+        let index = lit.index();
+        let word = index / WORD_BITS;
+        if self.bits.len() <= word {
+            self.bits.resize(word + 1, 0);
+        }
+        let mask = 1 << (index % WORD_BITS);
+        if self.bits[word] & mask != 0 {
+            return false
+        }
+        self.bits[word] |= mask;
+        self.members.push(lit);
+        true
+    }
+
+    /// Removes the literal from the set.
+    ///
+    /// Returns `true` if the literal was contained.
+    ///
+    /// # Note
+    ///
+    /// This takes time proportional to the number of contained literals.
+    pub fn remove(&mut self, lit: Lit) -> bool {
+        if !self.contains(lit) {
+            return false
+        }
+        let index = lit.index();
+        self.bits[index / WORD_BITS] &= !(1 << (index % WORD_BITS));
+        let position = self.members
+            .iter()
+            .position(|&member| member == lit)
+            .expect("contained literals are members");
+        self.members.swap_remove(position);
+        true
+    }
+
+    /// Removes all literals from the set.
+    pub fn clear(&mut self) {
+        for lit in self.members.drain(..) {
+            let index = lit.index();
+            self.bits[index / WORD_BITS] = 0;
+        }
+    }
+
+    /// Returns an iterator over the contained literals in unspecified order.
+    pub fn iter(&self) -> crate::LitIter<'_> {
+        crate::Clause::from(self.members.as_slice()).iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a LitSet {
+    type Item = Lit;
+    type IntoIter = crate::LitIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl iter::Extend<Lit> for LitSet {
+    fn extend<I>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        for lit in lits {
+            self.insert(lit);
+        }
+    }
+}
+
+impl iter::FromIterator<Lit> for LitSet {
+    fn from_iter<I>(lits: I) -> Self
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        let mut set = Self::new();
+        set.extend(lits);
+        set
+    }
+}
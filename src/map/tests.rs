@@ -0,0 +1,64 @@
+use super::*;
+use crate::{
+    Sign,
+    tests::lit,
+};
+
+#[test]
+fn lit_and_var_indices() {
+    assert_eq!(lit(1).index(), 0);
+    assert_eq!(lit(-1).index(), 1);
+    assert_eq!(lit(-3).index(), 5);
+    assert_eq!(Lit::from_index(5), lit(-3));
+    assert_eq!(Var::from_index(2), lit(3).var());
+    assert_eq!(lit(3).var().lit(Sign::Neg), lit(-3));
+}
+
+#[test]
+fn var_map_grows_on_demand() {
+    let mut activity = VarMap::<u32>::new();
+    assert_eq!(activity.get(lit(2).var()), None);
+    activity[lit(3).var()] += 5;
+    assert_eq!(activity.len(), 3);
+    assert_eq!(activity[lit(1).var()], 0);
+    assert_eq!(
+        activity.iter().map(|(var, &value)| (var.to_raw(), value)).collect::<Vec<_>>(),
+        vec![(1, 0), (2, 0), (3, 5)]
+    );
+}
+
+#[test]
+fn lit_map_covers_both_polarities() {
+    let mut watches = LitMap::<Vec<usize>>::new();
+    watches.reserve_var(lit(2).var());
+    assert_eq!(watches.len(), 4);
+    watches[lit(-2)].push(7);
+    assert_eq!(watches.keys().collect::<Vec<_>>(), vec![lit(1), lit(-1), lit(2), lit(-2)]);
+    assert_eq!(watches.get(lit(-2)), Some(&vec![7]));
+}
+
+#[test]
+fn lit_map_grows_to_both_polarities() {
+    let mut marks = LitMap::<bool>::new();
+    marks[lit(3)] = true;
+    assert!(!marks[lit(-3)]);
+    assert_eq!(marks.len(), 6);
+    let mut counts = LitMap::new();
+    counts.grow_to(lit(2), 0u32);
+    assert_eq!(counts.get(lit(-2)), Some(&0));
+}
+
+#[test]
+fn lit_set_insert_remove_clear() {
+    let mut seen = LitSet::new();
+    assert!(seen.insert(lit(-70)));
+    assert!(!seen.insert(lit(-70)));
+    assert!(seen.insert(lit(2)));
+    assert!(seen.contains(lit(-70)));
+    assert!(!seen.contains(lit(70)));
+    assert!(seen.remove(lit(2)));
+    assert_eq!(seen.iter().collect::<Vec<_>>(), vec![lit(-70)]);
+    seen.clear();
+    assert!(seen.is_empty());
+    assert!(!seen.contains(lit(-70)));
+}
@@ -0,0 +1,114 @@
+use crate::{
+    Var,
+    Lit,
+    Sign,
+    Clause,
+    Cnf,
+    VarMap,
+    LitValue,
+    IpasirSolver,
+    Result,
+};
+use core::iter;
+
+/// A partial assignment of variables, e.g. the model found by a solver.
+///
+/// # Note
+///
+/// Variables that are not assigned are `LitValue::DontCare`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Model {
+    /// The values of the positive literals of all variables.
+    values: VarMap<LitValue>,
+}
+
+impl Model {
+    /// Creates a new empty model.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queries the values of all variables up to `max_var` from the solver.
+    ///
+    /// # States
+    ///
+    /// - **Required:** SAT
+    /// - **After:** SAT
+    pub fn from_solver<S>(solver: &mut S, max_var: Var) -> Result<Self>
+    where
+        S: IpasirSolver,
+    {
+        let mut values = VarMap::with_capacity(max_var.index() + 1);
+        values.reserve_var(max_var);
+        for (var, value) in &mut values {
+            *value = solver.val(var.lit(Sign::Pos))?;
+        }
+        Ok(Self { values })
+    }
+
+    /// Returns the number of variables covered by the model.
+    pub fn num_vars(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Assigns the literal to be `true`.
+    pub fn set(&mut self, lit: Lit) {
+        self.values[lit.var()] = lit.eval(LitValue::True);
+    }
+
+    /// Unassigns the variable.
+    pub fn unset(&mut self, var: Var) {
+        if let Some(value) = self.values.get_mut(var) {
+            *value = LitValue::DontCare;
+        }
+    }
+
+    /// Returns the value of the literal.
+    pub fn value(&self, lit: Lit) -> LitValue {
+        let value = self.values.get(lit.var()).cloned().unwrap_or_default();
+        lit.eval(value)
+    }
+
+    /// Returns an iterator over all literals that are `true` in the model.
+    pub fn lits(&self) -> impl Iterator<Item = Lit> + '_ {
+        self.values
+            .iter()
+            .filter_map(|(var, value)| match value {
+                LitValue::True => Some(var.lit(Sign::Pos)),
+                LitValue::False => Some(var.lit(Sign::Neg)),
+                LitValue::DontCare => None,
+            })
+    }
+
+    /// Evaluates the clause under the model.
+    pub fn eval(&self, clause: Clause) -> LitValue {
+        clause.eval(|lit| self.value(lit))
+    }
+
+    /// Returns the first clause of the formula that is not `true` under the model.
+    pub fn first_unsatisfied<'a>(&self, cnf: &'a Cnf) -> Option<Clause<'a>> {
+        cnf.clauses().find(|&clause| self.eval(clause) != LitValue::True)
+    }
+}
+
+impl iter::Extend<Lit> for Model {
+    fn extend<I>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        for lit in lits {
+            self.set(lit);
+        }
+    }
+}
+
+impl iter::FromIterator<Lit> for Model {
+    fn from_iter<I>(lits: I) -> Self
+    where
+        I: IntoIterator<Item = Lit>,
+    {
+        let mut model = Self::new();
+        model.extend(lits);
+        model
+    }
+}
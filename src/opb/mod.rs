@@ -0,0 +1,159 @@
+//! Reading pseudo-Boolean problems in the OPB format.
+//!
+//! Comment lines start with `*`. An optional objective `min: <terms> ;`
+//! is followed by constraints `<terms> >= <rhs> ;` or `<terms> = <rhs> ;`
+//! where every term is an integer coefficient followed by a literal `x<n>`
+//! or its negation `~x<n>`. The variable `x<n>` is mapped to `Var` `n`.
+
+mod parser;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::parser::{
+    OpbErrorKind,
+    read_opb,
+};
+use crate::{
+    encodings::pb::{
+        PbConstraint,
+        PbEncoding,
+        Relation,
+    },
+    Var,
+    Lit,
+    Model,
+    LitValue,
+    IpasirSolver,
+    SolveResponse,
+    VarManager,
+    Result,
+};
+use std::vec::Vec;
+
+/// A pseudo-Boolean problem of linear constraints and an optional objective to minimize.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PbProblem {
+    /// The weighted literals of the objective if any.
+    objective: Option<Vec<(i64, Lit)>>,
+    /// The constraints.
+    constraints: Vec<PbConstraint>,
+    /// The greatest variable of the objective and all constraints.
+    max_var: Option<Var>,
+}
+
+impl PbProblem {
+    /// Creates a new problem without constraints and objective.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the weighted literals of the objective to minimize.
+    pub fn set_objective<I, L>(&mut self, terms: I)
+    where
+        I: IntoIterator<Item = (i64, L)>,
+        L: Into<Lit>,
+    {
+        let terms: Vec<(i64, Lit)> = terms.into_iter().map(|(coef, lit)| (coef, lit.into())).collect();
+        for (_, lit) in &terms {
+            self.max_var = self.max_var.max(Some(lit.var()));
+        }
+        self.objective = Some(terms);
+    }
+
+    /// Returns the weighted literals of the objective if any.
+    pub fn objective(&self) -> Option<&[(i64, Lit)]> {
+        self.objective.as_deref()
+    }
+
+    /// Adds a constraint.
+    pub fn add_constraint(&mut self, constraint: PbConstraint) {
+        for (_, lit) in &constraint.terms {
+            self.max_var = self.max_var.max(Some(lit.var()));
+        }
+        self.constraints.push(constraint);
+    }
+
+    /// Returns the constraints.
+    pub fn constraints(&self) -> &[PbConstraint] {
+        &self.constraints
+    }
+
+    /// Returns the greatest variable of the objective and all constraints if any.
+    pub fn max_var(&self) -> Option<Var> {
+        self.max_var
+    }
+
+    /// Returns `true` if the model satisfies all constraints.
+    ///
+    /// Literals that are not `true` in the model count as `false`.
+    pub fn is_satisfied(&self, model: &Model) -> bool {
+        self.constraints.iter().all(|constraint| constraint.is_satisfied(model))
+    }
+
+    /// Returns the value of the objective under the model.
+    ///
+    /// The value is `0` without objective, literals that are not `true`
+    /// in the model count as `false` and the sum saturates.
+    pub fn cost(&self, model: &Model) -> i64 {
+        self.objective()
+            .unwrap_or(&[])
+            .iter()
+            .filter(|(_, lit)| model.value(*lit) == LitValue::True)
+            .fold(0i64, |sum, (coef, _)| sum.saturating_add(*coef))
+    }
+
+    /// Adds clauses enforcing all constraints to the solver.
+    pub fn load_into<S>(&self, solver: &mut S, vars: &mut VarManager, encoding: PbEncoding)
+    where
+        S: IpasirSolver,
+    {
+        if let Some(max_var) = self.max_var {
+            vars.reserve(max_var);
+        }
+        for constraint in &self.constraints {
+            constraint.encode(solver, vars, encoding);
+        }
+    }
+
+    /// Loads the problem into the solver and minimizes the objective.
+    ///
+    /// Returns a model of the problem with the least objective value found
+    /// or `None` if the problem is unsatisfiable.
+    ///
+    /// # Note
+    ///
+    /// Every improved model permanently adds the constraint that the objective
+    /// is less than its value until the solver answers unsatisfiable, so the
+    /// solver should not be used for anything else afterwards. If the solver is
+    /// interrupted the best model so far is returned which may not be optimal.
+    /// Objective variables that are don't-care in a model are fixed to the cheaper value.
+    pub fn minimize<S>(&self, solver: &mut S, encoding: PbEncoding) -> Result<Option<(Model, i64)>>
+    where
+        S: IpasirSolver,
+    {
+        let mut vars = VarManager::new();
+        self.load_into(solver, &mut vars, encoding);
+        let mut best = None;
+        while solver.solve()? == SolveResponse::Sat {
+            let mut model = match self.max_var {
+                Some(max_var) => Model::from_solver(solver, max_var)?,
+                None => Model::new(),
+            };
+            let objective = match self.objective() {
+                Some(objective) => objective,
+                None => return Ok(Some((model, 0))),
+            };
+            for &(coef, lit) in objective {
+                if model.value(lit) == LitValue::DontCare {
+                    model.set(if coef < 0 { lit } else { !lit });
+                }
+            }
+            let cost = self.cost(&model);
+            let bound = PbConstraint::new(objective.iter().cloned(), Relation::AtMost, cost.saturating_sub(1));
+            bound.encode(solver, &mut vars, encoding);
+            best = Some((model, cost));
+        }
+        Ok(best)
+    }
+}
@@ -0,0 +1,229 @@
+use super::PbProblem;
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    encodings::pb::{
+        PbConstraint,
+        Relation,
+    },
+    Lit,
+    Sign,
+    Var,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::Read,
+    string::String,
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to OPB instances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OpbErrorKind {
+    /// The objective starts with a keyword other than `min`.
+    UnknownKeyword(String),
+    /// A coefficient or degree exceeds the range of `i64`.
+    InvalidValue(i128),
+}
+
+impl fmt::Display for OpbErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OpbErrorKind::UnknownKeyword(keyword) => write!(f, "unknown keyword {:?}", keyword),
+            OpbErrorKind::InvalidValue(value) => write!(f, "invalid value {}", value),
+        }
+    }
+}
+
+impl Error for OpbErrorKind {}
+
+impl From<OpbErrorKind> for ParseErrorKind {
+    fn from(kind: OpbErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// Parses OPB input.
+struct Parser<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Consumes the next byte if it is the expected one.
+    fn expect(&mut self, expected: u8) -> Result<()> {
+        self.lexer.skip_whitespace()?;
+        match self.lexer.peek()? {
+            Some(byte) if byte == expected => {
+                self.lexer.bump(byte);
+                Ok(())
+            }
+            _ => self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Reads decimal digits.
+    ///
+    /// Returns `None` if the next character is not a digit.
+    /// Values exceeding the range of `i128` saturate.
+    fn read_digits(&mut self) -> Result<Option<i128>> {
+        let mut value: Option<i128> = None;
+        while let Some(byte) = self.lexer.peek()? {
+            if !byte.is_ascii_digit() {
+                break
+            }
+            self.lexer.bump(byte);
+            let digit = i128::from(byte - b'0');
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+        Ok(value)
+    }
+
+    /// Reads an optionally signed integer that fits into `i64`.
+    fn read_number(&mut self) -> Result<i64> {
+        self.lexer.skip_whitespace()?;
+        let start = self.lexer.position();
+        let negative = match self.lexer.peek()? {
+            Some(sign @ b'+') | Some(sign @ b'-') => {
+                self.lexer.bump(sign);
+                self.lexer.skip_blanks()?;
+                sign == b'-'
+            }
+            _ => false,
+        };
+        let value = match self.read_digits()? {
+            Some(value) if negative => -value,
+            Some(value) => value,
+            None => return self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        };
+        if !(i128::from(i64::MIN)..=i128::from(i64::MAX)).contains(&value) {
+            return self.lexer.error_at(start, OpbErrorKind::InvalidValue(value))
+        }
+        Ok(value as i64)
+    }
+
+    /// Reads a literal `x<n>` or `~x<n>`.
+    fn read_lit(&mut self) -> Result<Lit> {
+        self.lexer.skip_whitespace()?;
+        let start = self.lexer.position();
+        let sign = match self.lexer.peek()? {
+            Some(b'~') => {
+                self.lexer.bump(b'~');
+                Sign::Neg
+            }
+            _ => Sign::Pos,
+        };
+        if self.lexer.peek()? != Some(b'x') {
+            return self.lexer.unexpected(ParseErrorKind::UnexpectedEof)
+        }
+        self.lexer.bump(b'x');
+        let value = match self.read_digits()? {
+            Some(value) => value,
+            None => return self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        };
+        match Var::try_from_wide(value) {
+            Ok(var) => Ok(var.lit(sign)),
+            Err(err) => self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+        }
+    }
+
+    /// Reads terms up to a relation or the terminating `;`.
+    fn read_terms(&mut self) -> Result<Vec<(i64, Lit)>> {
+        let mut terms = Vec::new();
+        loop {
+            self.lexer.skip_whitespace()?;
+            match self.lexer.peek()? {
+                Some(b';') | Some(b'>') | Some(b'=') => return Ok(terms),
+                _ => (),
+            }
+            let coef = self.read_number()?;
+            let lit = self.read_lit()?;
+            terms.push((coef, lit));
+        }
+    }
+
+    /// Parses the objective after its leading `min`.
+    fn parse_objective(&mut self, problem: &mut PbProblem) -> Result<()> {
+        self.expect(b':')?;
+        let terms = self.read_terms()?;
+        self.expect(b';')?;
+        problem.set_objective(terms);
+        Ok(())
+    }
+
+    /// Parses a constraint.
+    fn parse_constraint(&mut self, problem: &mut PbProblem) -> Result<()> {
+        let terms = self.read_terms()?;
+        let relation = match self.lexer.peek()? {
+            Some(b'>') => {
+                self.lexer.bump(b'>');
+                if self.lexer.peek()? != Some(b'=') {
+                    return self.lexer.unexpected(ParseErrorKind::UnexpectedEof)
+                }
+                self.lexer.bump(b'=');
+                Relation::AtLeast
+            }
+            Some(b'=') => {
+                self.lexer.bump(b'=');
+                Relation::Equal
+            }
+            _ => return self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        };
+        let rhs = self.read_number()?;
+        self.expect(b';')?;
+        problem.add_constraint(PbConstraint { terms, relation, rhs });
+        Ok(())
+    }
+}
+
+/// Parses OPB input into a pseudo-Boolean problem.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::opb::read_opb;
+///
+/// let input = "* #variable= 2 #constraint= 1\nmin: +1 x1 +2 x2 ;\n+1 x1 +1 ~x2 >= 1 ;\n";
+/// let problem = read_opb(input.as_bytes()).unwrap();
+/// assert_eq!(problem.objective().unwrap().len(), 2);
+/// assert_eq!(problem.constraints().len(), 1);
+/// ```
+pub fn read_opb<R>(reader: R) -> Result<PbProblem>
+where
+    R: Read,
+{
+    let mut parser = Parser { lexer: Lexer::new(reader) };
+    let mut problem = PbProblem::new();
+    loop {
+        parser.lexer.skip_whitespace()?;
+        let start = parser.lexer.position();
+        match parser.lexer.peek()? {
+            None => return Ok(problem),
+            Some(b'*') => parser.lexer.skip_line()?,
+            Some(byte) if byte.is_ascii_alphabetic() => {
+                let keyword = parser.lexer.read_word()?;
+                if keyword != b"min" {
+                    let keyword = String::from_utf8_lossy(&keyword).into_owned();
+                    return parser.lexer.error_at(start, OpbErrorKind::UnknownKeyword(keyword))
+                }
+                if problem.objective().is_some() || !problem.constraints().is_empty() {
+                    return parser.lexer.error_at(start, ParseErrorKind::UnexpectedChar('m'))
+                }
+                parser.parse_objective(&mut problem)?;
+            }
+            Some(_) => parser.parse_constraint(&mut problem)?,
+        }
+    }
+}
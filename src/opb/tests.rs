@@ -0,0 +1,47 @@
+use super::*;
+use crate::{
+    encodings::pb::PbEncoding,
+    tests::{TestSolver, lit},
+};
+use std::string::{String, ToString};
+
+/// Returns the position and message of the parse error of the input.
+fn error(input: &str) -> (usize, usize, String) {
+    let err = read_opb(input.as_bytes()).unwrap_err();
+    (err.line(), err.column(), err.kind().to_string())
+}
+
+#[test]
+fn parse_problem() {
+    let input = "* #variable= 3 #constraint= 2\nmin: +2 x1 -1 x3;\n+1 x1 +1 ~x2\n>= 1 ;\n3 x2 - 2 x3 = -2;\n";
+    let problem = read_opb(input.as_bytes()).unwrap();
+    assert_eq!(problem.objective(), Some(&[(2, lit(1)), (-1, lit(3))][..]));
+    assert_eq!(problem.constraints(), &[
+        PbConstraint::new(vec![(1, lit(1)), (1, lit(-2))], Relation::AtLeast, 1),
+        PbConstraint::new(vec![(3, lit(2)), (-2, lit(3))], Relation::Equal, -2),
+    ][..]);
+    assert_eq!(problem.max_var(), Some(lit(3).var()));
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(error("max: +1 x1 ;\n"), (1, 1, "unknown keyword \"max\"".to_string()));
+    assert_eq!(error("+1 x1 <= 1 ;\n"), (1, 7, "unexpected character '<'".to_string()));
+    assert_eq!(error("+1 x1 x2 >= 1 ;\n"), (1, 7, "unexpected character 'x'".to_string()));
+    assert_eq!(error("+1 y1 >= 1 ;\n"), (1, 4, "unexpected character 'y'".to_string()));
+    assert_eq!(error("+1 x0 >= 1 ;\n"), (1, 4, "invalid literal value 0".to_string()));
+    let err = read_opb("+1 x1 >= 9223372036854775808 ;\n".as_bytes()).unwrap_err();
+    assert_eq!(err.kind().format_kind(), Some(&OpbErrorKind::InvalidValue(9223372036854775808)));
+    assert_eq!(error("+1 x1 >= 1\n"), (2, 1, "unexpected end of input".to_string()));
+}
+
+#[test]
+fn minimize_objective() {
+    let input = "min: +3 x1 +2 x2 +4 x3 -1 x4 ;\n+1 x1 +1 x2 +1 x3 >= 2 ;\n+1 x1 +1 ~x4 >= 1 ;\n";
+    let problem = read_opb(input.as_bytes()).unwrap();
+    let (model, cost) = problem.minimize(&mut TestSolver::init(), PbEncoding::Bdd).unwrap().unwrap();
+    assert!(problem.is_satisfied(&model));
+    assert_eq!((cost, problem.cost(&model)), (4, 4));
+    let infeasible = read_opb("+1 x1 = 2 ;\n".as_bytes()).unwrap();
+    assert_eq!(infeasible.minimize(&mut TestSolver::init(), PbEncoding::Bdd).unwrap(), None);
+}
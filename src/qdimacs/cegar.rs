@@ -0,0 +1,159 @@
+use super::{
+    Qbf,
+    Quantifier,
+};
+use crate::{
+    Var,
+    Sign,
+    Model,
+    LitValue,
+    IpasirSolver,
+    SolveResponse,
+    SolverError,
+    VarManager,
+};
+use std::{
+    error::Error,
+    fmt,
+    vec::Vec,
+};
+
+/// The answer of the 2QBF solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QbfAnswer {
+    /// The formula is true with the given values of the outer existential variables.
+    True(Model),
+    /// The formula is false.
+    False,
+    /// One of the solvers was interrupted.
+    Unknown,
+}
+
+/// An error of the 2QBF solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CegarError {
+    /// The prefix has more than one alternation from existential to universal.
+    UnsupportedPrefix,
+    /// One of the solvers failed.
+    Solver(SolverError),
+}
+
+impl fmt::Display for CegarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CegarError::UnsupportedPrefix => write!(f, "prefix is not of the form exists-forall"),
+            CegarError::Solver(err) => write!(f, "solver failed: {}", err),
+        }
+    }
+}
+
+impl Error for CegarError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CegarError::Solver(err) => Some(err),
+            CegarError::UnsupportedPrefix => None,
+        }
+    }
+}
+
+impl From<SolverError> for CegarError {
+    fn from(err: SolverError) -> Self {
+        CegarError::Solver(err)
+    }
+}
+
+impl Qbf {
+    /// Returns whether each variable is universal if the prefix is of the form
+    /// exists-forall after dropping empty blocks and merging adjacent ones.
+    fn universal_vars(&self) -> Option<Vec<bool>> {
+        let mut universal = vec![false; self.num_vars()];
+        let mut seen_forall = false;
+        for block in self.prefix.iter().filter(|block| !block.vars.is_empty()) {
+            match block.quantifier {
+                Quantifier::Exists if seen_forall => return None,
+                Quantifier::Exists => (),
+                Quantifier::Forall => {
+                    seen_forall = true;
+                    for var in &block.vars {
+                        universal[var.index()] = true;
+                    }
+                }
+            }
+        }
+        Some(universal)
+    }
+
+    /// Decides a formula `exists X forall Y. matrix` by counterexample-guided
+    /// abstraction refinement on two solvers.
+    ///
+    /// The candidate solver proposes values of the existential variables `X`
+    /// which includes all free variables. The counterexample solver encodes the
+    /// negated matrix and searches values of the universal variables `Y` that
+    /// falsify it under the candidate. Every counterexample refines the candidate
+    /// solver by the matrix with `Y` fixed to the counterexample.
+    ///
+    /// Both solvers must be fresh since clauses are added permanently.
+    /// The witness of a true formula assigns all existential variables where
+    /// unconstrained ones are `false`.
+    pub fn solve_2qbf<C, V>(&self, candidates: &mut C, counterexamples: &mut V) -> Result<QbfAnswer, CegarError>
+    where
+        C: IpasirSolver,
+        V: IpasirSolver,
+    {
+        let universal = self.universal_vars().ok_or(CegarError::UnsupportedPrefix)?;
+        let existential: Vec<Var> = (0..universal.len())
+            .filter(|&index| !universal[index])
+            .map(Var::from_index)
+            .collect();
+        // The counterexample solver selects one clause whose literals are all false.
+        let mut vars = VarManager::with_max_var(universal.len().checked_sub(1).map(Var::from_index));
+        let mut selectors = Vec::with_capacity(self.matrix.len());
+        for clause in self.matrix.clauses() {
+            let selector = vars.fresh_lit();
+            for lit in clause.iter() {
+                counterexamples.add_clause(vec![!selector, !lit]);
+            }
+            selectors.push(selector);
+        }
+        counterexamples.add_clause(selectors);
+        // Existential variables not in any clause of the candidate solver are unconstrained.
+        let mut constrained = vec![false; universal.len()];
+        loop {
+            match candidates.solve()? {
+                SolveResponse::Sat => (),
+                SolveResponse::Unsat => return Ok(QbfAnswer::False),
+                SolveResponse::Interrupted => return Ok(QbfAnswer::Unknown),
+            }
+            let mut candidate = Model::new();
+            for &var in &existential {
+                let lit = var.lit(Sign::Pos);
+                let value = if constrained[var.index()] { candidates.val(lit)? } else { LitValue::DontCare };
+                candidate.set(if value == LitValue::True { lit } else { !lit });
+            }
+            for lit in candidate.lits() {
+                counterexamples.assume(lit);
+            }
+            match counterexamples.solve()? {
+                SolveResponse::Sat => (),
+                SolveResponse::Unsat => return Ok(QbfAnswer::True(candidate)),
+                SolveResponse::Interrupted => return Ok(QbfAnswer::Unknown),
+            }
+            let mut counterexample = Model::new();
+            for index in (0..universal.len()).filter(|&index| universal[index]) {
+                let lit = Var::from_index(index).lit(Sign::Pos);
+                let value = counterexamples.val(lit)?;
+                counterexample.set(if value == LitValue::True { lit } else { !lit });
+            }
+            for clause in self.matrix.clauses() {
+                if clause.iter().any(|lit| counterexample.value(lit) == LitValue::True) {
+                    continue
+                }
+                let refined: Vec<_> = clause.iter().filter(|lit| !universal[lit.var().index()]).collect();
+                for lit in &refined {
+                    constrained[lit.var().index()] = true;
+                }
+                candidates.add_clause(refined);
+            }
+        }
+    }
+}
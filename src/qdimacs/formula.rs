@@ -0,0 +1,56 @@
+use crate::{
+    Var,
+    Cnf,
+};
+use std::vec::Vec;
+
+/// The quantifier of a block of variables.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Quantifier {
+    /// The existential quantifier written as `e`.
+    Exists,
+    /// The universal quantifier written as `a`.
+    Forall,
+}
+
+/// A block of variables with the same quantifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantifierBlock {
+    /// The quantifier of all variables of the block.
+    pub quantifier: Quantifier,
+    /// The variables of the block.
+    pub vars: Vec<Var>,
+}
+
+/// A quantified Boolean formula in prenex conjunctive normal form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Qbf {
+    /// The quantifier blocks from the outermost to the innermost.
+    pub prefix: Vec<QuantifierBlock>,
+    /// The clauses.
+    pub matrix: Cnf,
+}
+
+impl Qbf {
+    /// Creates a new formula with empty prefix and matrix.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of variables which is the greatest variable of prefix and matrix.
+    pub fn num_vars(&self) -> usize {
+        self.prefix
+            .iter()
+            .flat_map(|block| block.vars.iter())
+            .map(|var| var.index() + 1)
+            .fold(self.matrix.num_vars(), usize::max)
+    }
+
+    /// Returns the quantifier of the variable if it is bound by the prefix.
+    pub fn quantifier(&self, var: Var) -> Option<Quantifier> {
+        self.prefix
+            .iter()
+            .find(|block| block.vars.contains(&var))
+            .map(|block| block.quantifier)
+    }
+}
@@ -0,0 +1,33 @@
+//! Reading and writing quantified Boolean formulas in the QDIMACS format
+//! and solving 2QBF instances by counterexample-guided abstraction refinement.
+//!
+//! The format extends DIMACS CNF by a quantifier prefix between the header
+//! and the clauses. Every prefix line starts with `e` for an existential or
+//! `a` for a universal block and lists the positive variables of the block
+//! terminated by `0`. Variables of the matrix that are not quantified are
+//! existential in the outermost block.
+
+mod formula;
+mod parser;
+mod writer;
+mod cegar;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    formula::{
+        Qbf,
+        Quantifier,
+        QuantifierBlock,
+    },
+    parser::{
+        QdimacsErrorKind,
+        read_qdimacs,
+    },
+    writer::write_qdimacs,
+    cegar::{
+        CegarError,
+        QbfAnswer,
+    },
+};
@@ -0,0 +1,253 @@
+use super::{
+    Qbf,
+    Quantifier,
+    QuantifierBlock,
+};
+use crate::{
+    Var,
+    Lit,
+    OwnedClause,
+    dimacs::{
+        Header,
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::Read,
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to QDIMACS formulas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QdimacsErrorKind {
+    /// A quantifier block is not terminated by `0`.
+    UnterminatedBlock,
+    /// A variable is quantified more than once.
+    DuplicateQuantifier(Var),
+}
+
+impl fmt::Display for QdimacsErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QdimacsErrorKind::UnterminatedBlock => write!(f, "quantifier block is not terminated by 0"),
+            QdimacsErrorKind::DuplicateQuantifier(var) => write!(f, "variable {} is quantified twice", var.to_raw()),
+        }
+    }
+}
+
+impl Error for QdimacsErrorKind {}
+
+impl From<QdimacsErrorKind> for ParseErrorKind {
+    fn from(kind: QdimacsErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// Parses QDIMACS input.
+struct Parser<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+    /// The header if already parsed.
+    header: Option<Header>,
+    /// The formula parsed so far.
+    qbf: Qbf,
+    /// Whether each variable is quantified.
+    quantified: Vec<bool>,
+    /// The literals of the current clause.
+    lits: Vec<Lit>,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Parses the header line after its leading `p`.
+    fn parse_header(&mut self, start: (usize, usize)) -> Result<()> {
+        if self.header.is_some() || !self.qbf.prefix.is_empty() || !self.qbf.matrix.is_empty() || !self.lits.is_empty() {
+            return self.lexer.error_at(start, ParseErrorKind::UnexpectedHeader)
+        }
+        self.lexer.skip_blanks()?;
+        if self.lexer.read_word()? != b"cnf" {
+            return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+        }
+        let mut counts = [0; 2];
+        for count in &mut counts {
+            self.lexer.skip_blanks()?;
+            match self.lexer.read_usize()? {
+                Some(value) => *count = value,
+                None => return self.lexer.error_at(start, ParseErrorKind::InvalidHeader),
+            }
+        }
+        self.lexer.expect_line_end(start, ParseErrorKind::InvalidHeader)?;
+        self.header = Some(Header {
+            num_vars: counts[0],
+            num_clauses: counts[1],
+        });
+        Ok(())
+    }
+
+    /// Returns the header or an error at the given position if it is missing.
+    fn header(&self, start: (usize, usize)) -> Result<Header> {
+        match self.header {
+            Some(header) => Ok(header),
+            None => self.lexer.error_at(start, ParseErrorKind::MissingHeader),
+        }
+    }
+
+    /// Checks that the variable read at the given position is declared by the header.
+    fn check_var(&self, var: Var, start: (usize, usize)) -> Result<()> {
+        let header = self.header(start)?;
+        if var.index() >= header.num_vars {
+            let kind = ParseErrorKind::VarOutOfRange {
+                var,
+                num_vars: header.num_vars,
+            };
+            return self.lexer.error_at(start, kind)
+        }
+        Ok(())
+    }
+
+    /// Parses a quantifier block after its leading `e` or `a`.
+    fn parse_block(&mut self, quantifier: Quantifier, start: (usize, usize)) -> Result<()> {
+        self.header(start)?;
+        let mut vars = Vec::new();
+        loop {
+            self.lexer.skip_blanks()?;
+            let var_start = self.lexer.position();
+            let var = match self.lexer.read_int()? {
+                Some(0) => break,
+                Some(value) => match Var::try_from_wide(value) {
+                    Ok(var) => var,
+                    Err(err) => return self.lexer.error_at(var_start, ParseErrorKind::InvalidLit(err)),
+                },
+                None => return self.lexer.unexpected(QdimacsErrorKind::UnterminatedBlock),
+            };
+            self.check_var(var, var_start)?;
+            if self.quantified.len() <= var.index() {
+                self.quantified.resize(var.index() + 1, false);
+            }
+            if self.quantified[var.index()] {
+                let kind = QdimacsErrorKind::DuplicateQuantifier(var);
+                return self.lexer.error_at(var_start, kind)
+            }
+            self.quantified[var.index()] = true;
+            vars.push(var);
+        }
+        self.lexer.finish_line()?;
+        self.qbf.prefix.push(QuantifierBlock { quantifier, vars });
+        Ok(())
+    }
+
+    /// Checks the end of the input.
+    fn finish(self) -> Result<Qbf> {
+        if !self.lits.is_empty() {
+            return self.lexer.error(ParseErrorKind::UnterminatedClause)
+        }
+        let header = self.header(self.lexer.position())?;
+        if header.num_clauses != self.qbf.matrix.len() {
+            let kind = ParseErrorKind::ClauseCountMismatch {
+                expected: header.num_clauses,
+                found: self.qbf.matrix.len(),
+            };
+            return self.lexer.error(kind)
+        }
+        Ok(self.qbf)
+    }
+
+    /// Parses the whole input.
+    fn parse(mut self) -> Result<Qbf> {
+        let mut clause_start = self.lexer.position();
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            let byte = match self.lexer.peek()? {
+                Some(byte) => byte,
+                None => return self.finish(),
+            };
+            if self.lexer.take_line_start() {
+                let quantifier = match byte {
+                    b'c' => {
+                        self.lexer.skip_line()?;
+                        continue
+                    }
+                    b'p' => {
+                        self.lexer.bump(byte);
+                        self.parse_header(start)?;
+                        continue
+                    }
+                    b'e' => Some(Quantifier::Exists),
+                    b'a' => Some(Quantifier::Forall),
+                    _ => None,
+                };
+                if let Some(quantifier) = quantifier {
+                    if !self.qbf.matrix.is_empty() || !self.lits.is_empty() {
+                        return self.lexer.unexpected(ParseErrorKind::UnexpectedEof)
+                    }
+                    self.lexer.bump(byte);
+                    self.parse_block(quantifier, start)?;
+                    continue
+                }
+            }
+            if self.lits.is_empty() {
+                clause_start = start;
+            }
+            match self.lexer.read_int()? {
+                Some(0) => {
+                    let clause: OwnedClause = self.lits.drain(..).collect();
+                    self.qbf.matrix.push(clause);
+                    let header = self.header(clause_start)?;
+                    if self.qbf.matrix.len() > header.num_clauses {
+                        let kind = ParseErrorKind::ClauseCountMismatch {
+                            expected: header.num_clauses,
+                            found: self.qbf.matrix.len(),
+                        };
+                        return self.lexer.error_at(clause_start, kind)
+                    }
+                }
+                Some(value) => {
+                    let lit = match Lit::try_from_wide(value) {
+                        Ok(lit) => lit,
+                        Err(err) => return self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+                    };
+                    self.check_var(lit.var(), start)?;
+                    self.lits.push(lit);
+                }
+                None => return self.lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+    }
+}
+
+/// Strictly parses QDIMACS input into a quantified Boolean formula.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::qdimacs::{read_qdimacs, Quantifier};
+///
+/// let input = "p cnf 2 2\ne 1 0\na 2 0\n1 2 0\n1 -2 0\n";
+/// let qbf = read_qdimacs(input.as_bytes()).unwrap();
+/// assert_eq!(qbf.prefix[1].quantifier, Quantifier::Forall);
+/// assert_eq!(qbf.matrix.len(), 2);
+/// ```
+pub fn read_qdimacs<R>(reader: R) -> Result<Qbf>
+where
+    R: Read,
+{
+    Parser {
+        lexer: Lexer::new(reader),
+        header: None,
+        qbf: Qbf::new(),
+        quantified: Vec::new(),
+        lits: Vec::new(),
+    }.parse()
+}
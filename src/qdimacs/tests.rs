@@ -0,0 +1,73 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    LitValue,
+    tests::{TestSolver, lit},
+};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Returns the position and message of the parse error of the input.
+fn error(input: &str) -> (usize, usize, String) {
+    let err = read_qdimacs(input.as_bytes()).unwrap_err();
+    (err.line(), err.column(), err.kind().to_string())
+}
+
+/// Solves the QDIMACS input with fresh test solvers.
+fn solve(input: &str) -> Result<QbfAnswer, CegarError> {
+    let qbf = read_qdimacs(input.as_bytes()).unwrap();
+    qbf.solve_2qbf(&mut TestSolver::init(), &mut TestSolver::init())
+}
+
+#[test]
+fn write_and_read_back() {
+    let input = "c synthesis\np cnf 4 2\ne 1 2 0\na 3 0\n1 -3 4 0\n2 3 0\n";
+    let qbf = read_qdimacs(input.as_bytes()).unwrap();
+    assert_eq!(qbf.prefix, vec![
+        QuantifierBlock { quantifier: Quantifier::Exists, vars: vec![lit(1).var(), lit(2).var()] },
+        QuantifierBlock { quantifier: Quantifier::Forall, vars: vec![lit(3).var()] },
+    ]);
+    assert_eq!(qbf.quantifier(lit(4).var()), None);
+    let mut output = Vec::new();
+    write_qdimacs(&mut output, &qbf).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "p cnf 4 2\ne 1 2 0\na 3 0\n1 -3 4 0\n2 3 0\n");
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(error("e 1 0\n"), (1, 1, "missing header".to_string()));
+    assert_eq!(error("p cnf 2 0\ne 1 0\na 2 1 0\n"), (3, 5, "variable 1 is quantified twice".to_string()));
+    assert_eq!(error("p cnf 2 0\ne 1"), (2, 4, "quantifier block is not terminated by 0".to_string()));
+    assert_eq!(error("p cnf 2 0\ne 3 0\n"), (2, 3, "variable 3 exceeds the declared 2 variables".to_string()));
+    assert_eq!(error("p cnf 2 1\n1 0\na 2 0\n"), (3, 1, "unexpected character 'a'".to_string()));
+    assert_eq!(error("p cnf 2 2\n1 0\n"), (3, 1, "found 1 clauses but 2 are declared".to_string()));
+    let err = read_qdimacs("p cnf 2 0\ne 1 0\na 2 1 0\n".as_bytes()).unwrap_err();
+    assert_eq!(err.kind().format_kind(), Some(&QdimacsErrorKind::DuplicateQuantifier(lit(1).var())));
+}
+
+#[test]
+fn solve_true_formula_with_witness() {
+    // Setting x1 satisfies every clause for both values of y3.
+    let input = "p cnf 3 3\ne 1 2 0\na 3 0\n1 3 0\n1 -3 0\n-2 -3 1 0\n";
+    let witness = match solve(input).unwrap() {
+        QbfAnswer::True(witness) => witness,
+        answer => panic!("unexpected answer {:?}", answer),
+    };
+    assert_eq!(witness.value(lit(1)), LitValue::True);
+    let qbf = read_qdimacs(input.as_bytes()).unwrap();
+    for &y in &[lit(3), lit(-3)] {
+        let mut assignment = witness.clone();
+        assignment.set(y);
+        assert!(assignment.first_unsatisfied(&qbf.matrix).is_none());
+    }
+}
+
+#[test]
+fn solve_false_formula() {
+    // The clauses require x1 == y2 which fails for one of the values of y2.
+    assert_eq!(solve("p cnf 2 2\ne 1 0\na 2 0\n1 -2 0\n-1 2 0\n"), Ok(QbfAnswer::False));
+    let inner_exists = "p cnf 3 1\ne 1 0\na 2 0\ne 3 0\n1 2 3 0\n";
+    assert_eq!(solve(inner_exists), Err(CegarError::UnsupportedPrefix));
+}
@@ -0,0 +1,42 @@
+use super::{
+    Qbf,
+    Quantifier,
+};
+use crate::dimacs::{
+    Header,
+    write_clause,
+    write_header,
+};
+use std::io::{
+    self,
+    Write,
+};
+
+/// Writes the formula in QDIMACS format.
+///
+/// The number of variables of the header is the greatest variable of the formula.
+/// Empty quantifier blocks are omitted.
+pub fn write_qdimacs<W>(writer: &mut W, qbf: &Qbf) -> io::Result<()>
+where
+    W: Write,
+{
+    write_header(writer, Header {
+        num_vars: qbf.num_vars(),
+        num_clauses: qbf.matrix.len(),
+    })?;
+    for block in qbf.prefix.iter().filter(|block| !block.vars.is_empty()) {
+        let quantifier = match block.quantifier {
+            Quantifier::Exists => 'e',
+            Quantifier::Forall => 'a',
+        };
+        write!(writer, "{} ", quantifier)?;
+        for var in &block.vars {
+            write!(writer, "{} ", var.to_raw())?;
+        }
+        writeln!(writer, "0")?;
+    }
+    for clause in qbf.matrix.clauses() {
+        write_clause(writer, clause)?;
+    }
+    Ok(())
+}
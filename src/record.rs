@@ -0,0 +1,161 @@
+//! Solver wrappers that record the input of an inner solver.
+
+use crate::{
+    Lit,
+    Clause,
+    OwnedClause,
+    Cnf,
+    IpasirSolver,
+    SolveResponse,
+    Result,
+    LitValue,
+    SolveControl,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Wraps an IPASIR solver and records all clauses and assumptions passed to it.
+///
+/// The recorded formula can be written in DIMACS CNF format to reproduce
+/// the input of an application offline.
+pub struct Recording<S> {
+    /// The inner solver.
+    solver: S,
+    /// All clauses added so far.
+    clauses: Cnf,
+    /// The assumptions of the pending or last solve.
+    assumptions: Vec<Lit>,
+    /// `true` if the assumptions belong to an already finished solve.
+    solved: bool,
+}
+
+impl<S> Recording<S> {
+    /// Wraps the given solver.
+    ///
+    /// # Note
+    ///
+    /// Clauses that have been added to the solver before
+    /// wrapping it are not known and thus not recorded.
+    pub fn new(solver: S) -> Self {
+        Self {
+            solver,
+            clauses: Cnf::new(),
+            assumptions: Vec::new(),
+            solved: false,
+        }
+    }
+
+    /// Returns a shared reference to the inner solver.
+    pub fn get_ref(&self) -> &S {
+        &self.solver
+    }
+
+    /// Returns the inner solver.
+    pub fn into_inner(self) -> S {
+        self.solver
+    }
+
+    /// Returns all recorded clauses.
+    pub fn cnf(&self) -> &Cnf {
+        &self.clauses
+    }
+
+    /// Returns the current assumptions.
+    ///
+    /// # Note
+    ///
+    /// These are the assumptions for the next solve if any have been added
+    /// since the last solve or otherwise the assumptions of the last solve.
+    pub fn assumptions(&self) -> &[Lit] {
+        &self.assumptions
+    }
+
+    /// Writes all recorded clauses in DIMACS CNF format.
+    ///
+    /// Appends the current assumptions as unit clauses if `with_assumptions` is `true`.
+    /// The number of variables of the header is the greatest variable written.
+    #[cfg(feature = "std")]
+    pub fn write_dimacs<W>(&self, writer: &mut W, with_assumptions: bool) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        use crate::dimacs::{write_clause, write_header, Header};
+
+        let assumptions: &[Lit] = if with_assumptions { &self.assumptions } else { &[] };
+        let num_vars = assumptions
+            .iter()
+            .map(|lit| lit.var().index() + 1)
+            .fold(self.clauses.num_vars(), usize::max);
+        write_header(writer, Header {
+            num_vars,
+            num_clauses: self.clauses.len() + assumptions.len(),
+        })?;
+        for clause in self.clauses.clauses() {
+            write_clause(writer, clause)?;
+        }
+        for lit in assumptions {
+            write_clause(writer, Clause::from(core::slice::from_ref(lit)))?;
+        }
+        Ok(())
+    }
+}
+
+impl<S> IpasirSolver for Recording<S>
+where
+    S: IpasirSolver,
+{
+    fn signature(&self) -> &'static str {
+        self.solver.signature()
+    }
+
+    fn init() -> Self {
+        Self::new(S::init())
+    }
+
+    fn add_clause<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        let clause: OwnedClause = lits.into_iter().collect();
+        self.solver.add_clause(&clause);
+        self.clauses.push(clause);
+    }
+
+    fn assume(&mut self, lit: Lit) {
+        if self.solved {
+            self.assumptions.clear();
+            self.solved = false;
+        }
+        self.solver.assume(lit);
+        self.assumptions.push(lit);
+    }
+
+    fn solve(&mut self) -> Result<SolveResponse> {
+        self.solved = true;
+        self.solver.solve()
+    }
+
+    fn val(&mut self, lit: Lit) -> Result<LitValue> {
+        self.solver.val(lit)
+    }
+
+    fn failed(&mut self, lit: Lit) -> Result<bool> {
+        self.solver.failed(lit)
+    }
+
+    fn set_terminate<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> SolveControl + 'static,
+    {
+        self.solver.set_terminate(callback)
+    }
+
+    fn set_learn<F>(&mut self, max_len: usize, callback: F)
+    where
+        F: FnMut(Clause) + 'static,
+    {
+        self.solver.set_learn(max_len, callback)
+    }
+}
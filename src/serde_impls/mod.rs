@@ -0,0 +1,127 @@
+//! Implementations of `serde` traits that cannot be derived.
+//!
+//! Literals and variables are represented by their DIMACS integers,
+//! clauses, formulas and models by sequences thereof.
+
+use crate::{
+    Var,
+    Lit,
+    Clause,
+    OwnedClause,
+    Cnf,
+    Model,
+};
+use serde::{
+    de::Error as _,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use alloc::vec::Vec;
+use core::{
+    convert::TryFrom,
+    ffi::c_int,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl Serialize for Var {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_raw().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Var {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let val = c_int::deserialize(deserializer)?;
+        Var::try_from(val).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Lit {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_raw().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Lit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let val = c_int::deserialize(deserializer)?;
+        Lit::try_from(val).map_err(D::Error::custom)
+    }
+}
+
+impl<'a> Serialize for Clause<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl Serialize for Cnf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Cnf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<OwnedClause>::deserialize(deserializer).map(|clauses| clauses.into_iter().collect())
+    }
+}
+
+impl Serialize for Model {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.lits())
+    }
+}
+
+impl<'de> Deserialize<'de> for Model {
+    /// Deserializes a model from its literals.
+    ///
+    /// The literals are checked before the model is built, so it is allocated
+    /// once covering exactly the variables up to the greatest one.
+    ///
+    /// # Errors
+    ///
+    /// If a variable is assigned both values.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut lits = Vec::<Lit>::deserialize(deserializer)?;
+        lits.sort_unstable_by(|a, b| b.var().cmp(&a.var()).then(a.cmp(b)));
+        lits.dedup();
+        if let Some(pair) = lits.windows(2).find(|pair| pair[0].var() == pair[1].var()) {
+            return Err(D::Error::custom(format_args!(
+                "conflicting assignment of variable {}", pair[0].var().to_raw()
+            )))
+        }
+        Ok(lits.into_iter().collect())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    Cnf,
+    Model,
+    Lit,
+    LitValue,
+    SolveResponse,
+    tests::lit,
+};
+use serde_test::{
+    assert_de_tokens,
+    assert_de_tokens_error,
+    assert_tokens,
+    Token,
+};
+
+#[test]
+fn lit_as_dimacs_integer() {
+    assert_tokens(&lit(-7), &[Token::I32(-7)]);
+    assert_de_tokens_error::<Lit>(&[Token::I32(0)], "invalid literal value 0");
+}
+
+#[test]
+fn cnf_as_nested_sequences() {
+    let mut cnf = Cnf::new();
+    cnf.add_clause(vec![lit(1), lit(-2)]);
+    assert_tokens(&cnf, &[
+        Token::Seq { len: Some(1) },
+        Token::Seq { len: Some(2) },
+        Token::I32(1),
+        Token::I32(-2),
+        Token::SeqEnd,
+        Token::SeqEnd,
+    ]);
+}
+
+#[test]
+fn model_as_true_literals() {
+    let model: Model = vec![lit(-1), lit(3)].into_iter().collect();
+    assert_eq!(model.value(lit(2)), LitValue::DontCare);
+    assert_tokens(&model, &[
+        Token::Seq { len: None },
+        Token::I32(-1),
+        Token::I32(3),
+        Token::SeqEnd,
+    ]);
+}
+
+#[test]
+fn sparse_model_round_trip() {
+    let model: Model = Some(lit(100)).into_iter().collect();
+    assert_tokens(&model, &[Token::Seq { len: None }, Token::I32(100), Token::SeqEnd]);
+    let tokens = [Token::Seq { len: Some(3) }, Token::I32(-4), Token::I32(100), Token::I32(-4), Token::SeqEnd];
+    assert_de_tokens(&vec![lit(100), lit(-4)].into_iter().collect::<Model>(), &tokens);
+    assert_de_tokens_error::<Model>(
+        &[Token::Seq { len: Some(3) }, Token::I32(2), Token::I32(1), Token::I32(-2), Token::SeqEnd],
+        "conflicting assignment of variable 2",
+    );
+}
+
+#[test]
+fn enums_as_variant_names() {
+    assert_tokens(&SolveResponse::Unsat, &[
+        Token::UnitVariant { name: "SolveResponse", variant: "Unsat" },
+    ]);
+}
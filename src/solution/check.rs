@@ -0,0 +1,78 @@
+use super::{
+    read_solution,
+    Solution,
+};
+use crate::{
+    dimacs::{
+        Parser,
+        ParseError,
+    },
+    Cnf,
+    VerificationError,
+};
+use std::{
+    error::Error,
+    fmt,
+    io::Read,
+};
+
+/// An error encountered while checking a solution against a formula.
+#[derive(Debug)]
+pub enum CheckError {
+    /// The DIMACS CNF formula is malformed.
+    Cnf(ParseError),
+    /// The solution is malformed.
+    Solution(ParseError),
+    /// The model of the solution does not satisfy the formula.
+    Verification(VerificationError),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::Cnf(err) => write!(f, "formula: {}", err),
+            CheckError::Solution(err) => write!(f, "solution: {}", err),
+            CheckError::Verification(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for CheckError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckError::Cnf(err) | CheckError::Solution(err) => Some(err),
+            CheckError::Verification(err) => Some(err),
+        }
+    }
+}
+
+impl From<VerificationError> for CheckError {
+    fn from(err: VerificationError) -> Self {
+        CheckError::Verification(err)
+    }
+}
+
+/// Checks a solution against a strictly parsed DIMACS CNF formula.
+///
+/// Returns the solution if it is well-formed, assigns no variables beyond
+/// those declared by the formula and its model satisfies the formula.
+///
+/// # Note
+///
+/// Unsatisfiable and unknown solutions cannot be checked without a proof
+/// and always pass.
+pub fn check_solution<C, R>(cnf: C, solution: R) -> Result<Solution, CheckError>
+where
+    C: Read,
+    R: Read,
+{
+    let mut parser = Parser::new(cnf);
+    let mut formula = Cnf::new();
+    while let Some(clause) = parser.next_clause() {
+        formula.push(clause.map_err(CheckError::Cnf)?.into());
+    }
+    let num_vars = parser.header().map(|header| header.num_vars).unwrap_or(0);
+    let solution = read_solution(solution, num_vars).map_err(CheckError::Solution)?;
+    solution.verify(&formula)?;
+    Ok(solution)
+}
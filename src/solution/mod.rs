@@ -0,0 +1,95 @@
+//! Reading, writing and checking solutions in the SAT competition output format.
+//!
+//! A solution consists of comment lines starting with `c`, exactly one status line
+//! `s SATISFIABLE`, `s UNSATISFIABLE` or `s UNKNOWN` and, for satisfiable
+//! instances, value lines starting with `v` that list the literals of the model
+//! and are terminated by `0` on the last value line.
+
+mod reader;
+mod writer;
+mod check;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    reader::{
+        read_solution,
+        SolutionErrorKind,
+    },
+    writer::{
+        write_model,
+        write_solution,
+        write_status,
+    },
+    check::{
+        check_solution,
+        CheckError,
+    },
+};
+use crate::{
+    Var,
+    Cnf,
+    Model,
+    IpasirSolver,
+    SolveResponse,
+    Result,
+    VerificationError,
+};
+
+/// The answer of a solver to an instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// The status of the answer.
+    ///
+    /// `SolveResponse::Interrupted` is written as `UNKNOWN`.
+    pub response: SolveResponse,
+    /// The model of a satisfiable answer and empty otherwise.
+    pub model: Model,
+}
+
+impl Solution {
+    /// Creates a new solution with the given status and an empty model.
+    pub fn new(response: SolveResponse) -> Self {
+        Self {
+            response,
+            model: Model::new(),
+        }
+    }
+
+    /// Creates a solution for the last response of the solver.
+    ///
+    /// For satisfiable responses the values of all variables up to `max_var`
+    /// are queried from the solver.
+    ///
+    /// # States
+    ///
+    /// - **Required:** SAT if the response is `SolveResponse::Sat`
+    /// - **After:** unchanged
+    pub fn from_solver<S>(solver: &mut S, response: SolveResponse, max_var: Option<Var>) -> Result<Self>
+    where
+        S: IpasirSolver,
+    {
+        let model = match (response, max_var) {
+            (SolveResponse::Sat, Some(max_var)) => Model::from_solver(solver, max_var)?,
+            _ => Model::new(),
+        };
+        Ok(Self { response, model })
+    }
+
+    /// Checks that the model of a satisfiable solution satisfies all clauses of the formula.
+    ///
+    /// # Note
+    ///
+    /// Unsatisfiable and unknown solutions cannot be checked without a proof
+    /// and always pass.
+    pub fn verify(&self, cnf: &Cnf) -> core::result::Result<(), VerificationError> {
+        if self.response != SolveResponse::Sat {
+            return Ok(())
+        }
+        match self.model.first_unsatisfied(cnf) {
+            Some(clause) => Err(VerificationError::ViolatedClause(clause.into())),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::Solution;
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    Lit,
+    LitValue,
+    Model,
+    SolveResponse,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::Read,
+    string::String,
+};
+
+/// A kind of a parse error specific to solutions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SolutionErrorKind {
+    /// A line starts with an unknown keyword or the status is unknown.
+    UnknownKeyword(String),
+    /// The solution has no status line.
+    MissingStatus,
+    /// The solution has more than one status line.
+    DuplicateStatus,
+    /// Values without a satisfiable status or after the terminating `0`.
+    UnexpectedValues,
+    /// The solution assigns both polarities of the literal's variable.
+    ConflictingLit(Lit),
+    /// The values are not terminated by `0`.
+    UnterminatedValues,
+}
+
+impl fmt::Display for SolutionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolutionErrorKind::UnknownKeyword(keyword) => write!(f, "unknown keyword {:?}", keyword),
+            SolutionErrorKind::MissingStatus => write!(f, "missing status line"),
+            SolutionErrorKind::DuplicateStatus => write!(f, "duplicate status line"),
+            SolutionErrorKind::UnexpectedValues => write!(f, "unexpected values line"),
+            SolutionErrorKind::ConflictingLit(lit) => write!(
+                f, "conflicting values for variable {}", lit.var().to_raw()
+            ),
+            SolutionErrorKind::UnterminatedValues => write!(f, "values are not terminated by 0"),
+        }
+    }
+}
+
+impl Error for SolutionErrorKind {}
+
+impl From<SolutionErrorKind> for ParseErrorKind {
+    fn from(kind: SolutionErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Parses a solution in the SAT competition output format.
+///
+/// Solutions usually come from other programs, so values of variables
+/// greater than `num_vars` are rejected before the model grows to them.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::{solution::read_solution, SolveResponse};
+///
+/// let solution = read_solution("c found it\ns SATISFIABLE\nv 1 -2\nv 0\n".as_bytes(), 2).unwrap();
+/// assert_eq!(solution.response, SolveResponse::Sat);
+/// assert_eq!(solution.model.lits().count(), 2);
+/// ```
+pub fn read_solution<R>(reader: R, num_vars: usize) -> Result<Solution, ParseError>
+where
+    R: Read,
+{
+    let mut lexer = Lexer::new(reader);
+    let mut response = None;
+    let mut model = Model::new();
+    let mut terminated = false;
+    loop {
+        lexer.skip_whitespace()?;
+        let start = lexer.position();
+        if lexer.peek()?.is_none() {
+            break
+        }
+        lexer.take_line_start();
+        match lexer.read_word()?.as_slice() {
+            b"c" => lexer.skip_line()?,
+            b"s" => {
+                if response.is_some() {
+                    return lexer.error_at(start, SolutionErrorKind::DuplicateStatus)
+                }
+                lexer.skip_blanks()?;
+                let status_start = lexer.position();
+                response = Some(match lexer.read_word()?.as_slice() {
+                    b"SATISFIABLE" => SolveResponse::Sat,
+                    b"UNSATISFIABLE" => SolveResponse::Unsat,
+                    b"UNKNOWN" => SolveResponse::Interrupted,
+                    status => {
+                        let status = String::from_utf8_lossy(status).into_owned();
+                        return lexer.error_at(status_start, SolutionErrorKind::UnknownKeyword(status))
+                    }
+                });
+                lexer.finish_line()?;
+            }
+            b"v" => {
+                if response != Some(SolveResponse::Sat) || terminated {
+                    return lexer.error_at(start, SolutionErrorKind::UnexpectedValues)
+                }
+                loop {
+                    lexer.skip_blanks()?;
+                    let lit_start = lexer.position();
+                    match lexer.read_int()? {
+                        Some(0) => {
+                            terminated = true;
+                            lexer.finish_line()?;
+                            break
+                        }
+                        Some(value) => {
+                            let lit = match Lit::try_from_wide(value) {
+                                Ok(lit) => lit,
+                                Err(err) => return lexer.error_at(lit_start, ParseErrorKind::InvalidLit(err)),
+                            };
+                            if lit.var().to_raw() as usize > num_vars {
+                                let kind = ParseErrorKind::VarOutOfRange { var: lit.var(), num_vars };
+                                return lexer.error_at(lit_start, kind)
+                            }
+                            if model.value(lit) == LitValue::False {
+                                return lexer.error_at(lit_start, SolutionErrorKind::ConflictingLit(lit))
+                            }
+                            model.set(lit);
+                        }
+                        None => {
+                            lexer.finish_line()?;
+                            break
+                        }
+                    }
+                }
+            }
+            b"" => return lexer.unexpected(ParseErrorKind::UnexpectedEof),
+            keyword => {
+                let keyword = String::from_utf8_lossy(keyword).into_owned();
+                return lexer.error_at(start, SolutionErrorKind::UnknownKeyword(keyword))
+            }
+        }
+    }
+    match response {
+        None => lexer.error(SolutionErrorKind::MissingStatus),
+        Some(SolveResponse::Sat) if !terminated => lexer.error(SolutionErrorKind::UnterminatedValues),
+        Some(response) => Ok(Solution { response, model }),
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    Model,
+    SolveResponse,
+    VerificationError,
+    tests::{TestSolver, lit},
+};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Returns the position and message of the parse error of the solution.
+fn error(input: &str) -> (usize, usize, String) {
+    let err = read_solution(input.as_bytes(), 2).unwrap_err();
+    (err.line(), err.column(), err.kind().to_string())
+}
+
+#[test]
+fn write_and_read_solver_answer() {
+    let mut solver = TestSolver::init();
+    solver.add_clause(vec![lit(-1)]);
+    solver.add_clause(vec![lit(1), lit(2)]);
+    let response = solver.solve().unwrap();
+    let solution = Solution::from_solver(&mut solver, response, Some(lit(2).var())).unwrap();
+    let mut output = Vec::new();
+    write_solution(&mut output, &solution).unwrap();
+    assert_eq!(String::from_utf8(output.clone()).unwrap(), "s SATISFIABLE\nv -1 2 0\n");
+    assert_eq!(read_solution(output.as_slice(), 2).unwrap(), solution);
+    let mut output = Vec::new();
+    write_solution(&mut output, &Solution::new(SolveResponse::Interrupted)).unwrap();
+    assert_eq!(output, b"s UNKNOWN\n");
+}
+
+#[test]
+fn long_models_are_wrapped() {
+    let model: Model = (1..=40).map(lit).collect();
+    let mut output = Vec::new();
+    write_model(&mut output, &model).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.lines().all(|line| line.len() <= 78 && line.starts_with("v ")));
+    assert!(output.lines().count() > 1 && output.ends_with(" 40 0\n"));
+    let solution = read_solution(format!("s SATISFIABLE\n{}", output).as_bytes(), 40).unwrap();
+    assert_eq!(solution.model, model);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(error("c nothing\n"), (2, 1, "missing status line".to_string()));
+    assert_eq!(error("s SAT\n"), (1, 3, "unknown keyword \"SAT\"".to_string()));
+    assert_eq!(error("s UNSATISFIABLE\nv 1 0\n"), (2, 1, "unexpected values line".to_string()));
+    assert_eq!(error("s SATISFIABLE\nv 1 0\nv 2 0\n"), (3, 1, "unexpected values line".to_string()));
+    assert_eq!(error("s SATISFIABLE\ns UNKNOWN\n"), (2, 1, "duplicate status line".to_string()));
+    assert_eq!(error("s SATISFIABLE\nv 1 -1 0\n"), (2, 5, "conflicting values for variable 1".to_string()));
+    assert_eq!(error("s SATISFIABLE\nv 1\n"), (3, 1, "values are not terminated by 0".to_string()));
+    assert_eq!(
+        error("s SATISFIABLE\nv 1 2147483647 0\n"),
+        (2, 5, "variable 2147483647 exceeds the declared 2 variables".to_string())
+    );
+    let err = read_solution("s UNKNOWN\ns UNKNOWN\n".as_bytes(), 0).unwrap_err();
+    assert_eq!(err.kind().format_kind(), Some(&SolutionErrorKind::DuplicateStatus));
+}
+
+#[test]
+fn check_against_formula() {
+    let cnf = "p cnf 2 2\n1 2 0\n-1 0\n";
+    let solution = check_solution(cnf.as_bytes(), "s SATISFIABLE\nv -1 2 0\n".as_bytes()).unwrap();
+    assert_eq!(solution.response, SolveResponse::Sat);
+    match check_solution(cnf.as_bytes(), "s SATISFIABLE\nv 1 2 0\n".as_bytes()) {
+        Err(CheckError::Verification(VerificationError::ViolatedClause(clause))) => {
+            assert_eq!(clause.as_slice(), &[lit(-1)])
+        }
+        other => panic!("unexpected check result: {:?}", other),
+    }
+    assert!(check_solution(cnf.as_bytes(), "s UNSATISFIABLE\n".as_bytes()).is_ok());
+    // Declared variables may be assigned even if no clause contains them.
+    assert!(check_solution("p cnf 3 1\n1 0\n".as_bytes(), "s SATISFIABLE\nv 1 -3 0\n".as_bytes()).is_ok());
+    assert!(matches!(
+        check_solution(cnf.as_bytes(), "s SATISFIABLE\nv -1 2 3 0\n".as_bytes()),
+        Err(CheckError::Solution(_))
+    ));
+    assert!(matches!(
+        check_solution("p cnf 1 1\n".as_bytes(), "s UNKNOWN\n".as_bytes()),
+        Err(CheckError::Cnf(_))
+    ));
+}
@@ -0,0 +1,62 @@
+use super::Solution;
+use crate::{
+    Model,
+    SolveResponse,
+};
+use std::io::{
+    self,
+    Write,
+};
+
+/// The maximal length of a value line without the line break.
+const MAX_LINE_LEN: usize = 78;
+
+/// Writes the status line of the response.
+pub fn write_status<W>(writer: &mut W, response: SolveResponse) -> io::Result<()>
+where
+    W: Write,
+{
+    let status = match response {
+        SolveResponse::Sat => "SATISFIABLE",
+        SolveResponse::Unsat => "UNSATISFIABLE",
+        SolveResponse::Interrupted => "UNKNOWN",
+    };
+    writeln!(writer, "s {}", status)
+}
+
+/// Writes the assigned literals of the model as value lines terminated by `0`.
+///
+/// Lines are wrapped to at most 78 characters.
+pub fn write_model<W>(writer: &mut W, model: &Model) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut line_len = 1;
+    write!(writer, "v")?;
+    for lit in model.lits() {
+        let raw = lit.to_raw();
+        let len = 1 + (raw < 0) as usize + (raw.unsigned_abs().ilog10() as usize + 1);
+        if line_len + len > MAX_LINE_LEN {
+            write!(writer, "\nv")?;
+            line_len = 1;
+        }
+        write!(writer, " {}", raw)?;
+        line_len += len;
+    }
+    if line_len + 2 > MAX_LINE_LEN {
+        write!(writer, "\nv")?;
+    }
+    writeln!(writer, " 0")
+}
+
+/// Writes the status line and for satisfiable solutions the value lines.
+pub fn write_solution<W>(writer: &mut W, solution: &Solution) -> io::Result<()>
+where
+    W: Write,
+{
+    write_status(writer, solution.response)?;
+    if solution.response == SolveResponse::Sat {
+        write_model(writer, &solution.model)?;
+    }
+    Ok(())
+}
@@ -1,19 +1,23 @@
 use crate::{
     Lit,
+    Sign,
     Clause,
+    OwnedClause,
     IpasirSolver,
     SolveResponse,
+    SolverErrorKind,
     Result,
     LitValue,
     SolveControl,
 };
-use std::convert::{
+use alloc::vec::Vec;
+use core::convert::{
     TryFrom,
 };
 
 /// The solver state of the test solver.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum SolverState {
+pub(crate) enum SolverState {
     /// The input phase.
     Input,
     /// After evaluating to SAT.
@@ -23,13 +27,22 @@ enum SolverState {
 }
 
 /// The test solver implementing the IPASIR interface.
-struct TestSolver {
+///
+/// # Note
+///
+/// This is a naive DPLL solver without any clause learning
+/// and is only meant to be used on tiny formulas in tests.
+pub(crate) struct TestSolver {
     /// The current solver state.
     state: SolverState,
     /// All clauses.
     clauses: Vec<OwnedClause>,
     /// All assumption literals.
     assumptions: Vec<Lit>,
+    /// The partial assignment found by the last satisfiable solve.
+    model: Vec<Option<bool>>,
+    /// The failed assumptions of the last unsatisfiable solve.
+    failed: Vec<Lit>,
 }
 
 impl TestSolver {
@@ -39,34 +52,82 @@ impl TestSolver {
     }
 
     /// Returns an iterator over all clauses.
-    pub fn clauses(&self) -> impl Iterator<Item = &OwnedClause> {
+    pub fn clauses(&self) -> impl Iterator<Item = &OwnedClause> + '_ {
         self.clauses.iter()
     }
 }
 
-/// A clause that owns its literals.
-struct OwnedClause {
-    /// The literals of the clause.
-    lits: Vec<Lit>,
+/// Returns `lit` for the given raw DIMACS value.
+pub(crate) fn lit(val: i32) -> Lit {
+    Lit::try_from(val).unwrap()
 }
 
-impl<'a> From<Clause<'a>> for OwnedClause {
-    fn from(clause: Clause<'a>) -> Self {
-        Self {
-            lits: clause.iter().collect(),
+/// Returns the value of `lit` under the given partial assignment.
+fn lit_value(assignment: &[Option<bool>], lit: Lit) -> Option<bool> {
+    let index = lit.var().to_raw() as usize - 1;
+    assignment
+        .get(index)
+        .cloned()
+        .unwrap_or(None)
+        .map(|value| value == (lit.sign() == Sign::Pos))
+}
+
+/// Assigns `lit` to be `true` in the given partial assignment.
+fn assign(assignment: &mut Vec<Option<bool>>, lit: Lit) {
+    let index = lit.var().to_raw() as usize - 1;
+    if assignment.len() <= index {
+        assignment.resize(index + 1, None);
+    }
+    assignment[index] = Some(lit.sign() == Sign::Pos);
+}
+
+/// Extends `assignment` by unit propagation until a fixpoint.
+///
+/// Returns `false` if a clause is falsified.
+fn propagate(clauses: &[OwnedClause], assignment: &mut Vec<Option<bool>>) -> bool {
+    loop {
+        let mut propagated = false;
+        for clause in clauses {
+            if clause.iter().any(|lit| lit_value(assignment, lit) == Some(true)) {
+                continue
+            }
+            let mut unassigned = clause.iter().filter(|&lit| lit_value(assignment, lit).is_none());
+            match (unassigned.next(), unassigned.next()) {
+                (None, _) => return false,
+                (Some(unit), None) => {
+                    assign(assignment, unit);
+                    propagated = true;
+                }
+                (Some(_), Some(_)) => (),
+            }
+        }
+        if !propagated {
+            return true
         }
     }
 }
 
-impl<I, L> From<I> for OwnedClause
-where
-    I: IntoIterator<Item = L>,
-    L: Into<Lit>,
-{
-    fn from(lits: I) -> Self {
-        Self {
-            lits: lits.into_iter().map(Into::into).collect()
+/// Searches for an extension of `assignment` satisfying all clauses.
+fn dpll(clauses: &[OwnedClause], mut assignment: Vec<Option<bool>>) -> Option<Vec<Option<bool>>> {
+    loop {
+        if !propagate(clauses, &mut assignment) {
+            return None
+        }
+        let branch = clauses
+            .iter()
+            .filter(|clause| clause.iter().all(|lit| lit_value(&assignment, lit) != Some(true)))
+            .flat_map(|clause| clause.iter())
+            .find(|&lit| lit_value(&assignment, lit).is_none());
+        let lit = match branch {
+            Some(lit) => lit,
+            None => return Some(assignment),
+        };
+        let mut positive = assignment.clone();
+        assign(&mut positive, lit);
+        if let Some(model) = dpll(clauses, positive) {
+            return Some(model)
         }
+        assign(&mut assignment, !lit);
     }
 }
 
@@ -76,7 +137,43 @@ impl Default for TestSolver {
             state: SolverState::Input,
             clauses: Vec::new(),
             assumptions: Vec::new(),
+            model: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl TestSolver {
+    /// Returns the literals that unit propagation of all clauses
+    /// under the given assumptions makes `true` or `None` on a conflict.
+    pub fn propagate(&self, assumptions: &[Lit]) -> Option<Vec<Lit>> {
+        let mut assignment = Vec::new();
+        for &lit in assumptions {
+            match lit_value(&assignment, lit) {
+                Some(false) => return None,
+                _ => assign(&mut assignment, lit),
+            }
+        }
+        if !propagate(&self.clauses, &mut assignment) {
+            return None
+        }
+        let lits = assignment.iter().enumerate().filter_map(|(index, value)| {
+            let var = Lit::try_from(index as i32 + 1).unwrap();
+            value.map(|value| if value { var } else { !var })
+        });
+        Some(lits.collect())
+    }
+
+    /// Solves the clauses of `self` under the given assumptions.
+    fn solve_under(&self, assumptions: &[Lit]) -> Option<Vec<Option<bool>>> {
+        let mut assignment = Vec::new();
+        for &lit in assumptions {
+            match lit_value(&assignment, lit) {
+                Some(false) => return None,
+                _ => assign(&mut assignment, lit),
+            }
         }
+        dpll(&self.clauses, assignment)
     }
 }
 
@@ -94,24 +191,56 @@ impl IpasirSolver for TestSolver {
         I: IntoIterator<Item = L>,
         L: Into<Lit>,
     {
-        self.clauses.push(OwnedClause::from(lits))
+        self.state = SolverState::Input;
+        self.clauses.push(lits.into_iter().collect())
     }
 
     fn assume(&mut self, lit: Lit) {
+        self.state = SolverState::Input;
         self.assumptions.push(lit)
     }
 
     fn solve(&mut self) -> Result<SolveResponse> {
-        self.state = SolverState::Sat;
-        Ok(SolveResponse::Sat)
+        let mut assumptions = core::mem::take(&mut self.assumptions);
+        match self.solve_under(&assumptions) {
+            Some(model) => {
+                self.state = SolverState::Sat;
+                self.model = model;
+                Ok(SolveResponse::Sat)
+            }
+            None => {
+                // Shrink the failed assumptions by deletion.
+                let mut n = 0;
+                while n < assumptions.len() {
+                    let removed = assumptions.remove(n);
+                    if self.solve_under(&assumptions).is_some() {
+                        assumptions.insert(n, removed);
+                        n += 1;
+                    }
+                }
+                self.state = SolverState::Unsat;
+                self.failed = assumptions;
+                Ok(SolveResponse::Unsat)
+            }
+        }
     }
 
-    fn val(&mut self, _lit: Lit) -> Result<LitValue> {
-        Ok(LitValue::DontCare)
+    fn val(&mut self, lit: Lit) -> Result<LitValue> {
+        if self.state != SolverState::Sat {
+            return Err(SolverErrorKind::InvalidSolverState.into())
+        }
+        Ok(match lit_value(&self.model, lit) {
+            Some(true) => LitValue::True,
+            Some(false) => LitValue::False,
+            None => LitValue::DontCare,
+        })
     }
 
-    fn failed(&mut self, _lit: Lit) -> Result<bool> {
-        Ok(false)
+    fn failed(&mut self, lit: Lit) -> Result<bool> {
+        if self.state != SolverState::Unsat {
+            return Err(SolverErrorKind::InvalidSolverState.into())
+        }
+        Ok(self.failed.contains(&lit))
     }
 
     fn set_terminate<F>(&mut self, _callback: F)
@@ -143,3 +272,59 @@ fn add_clause() {
         [1, 2, 3].iter().map(|val| Lit::try_from(*val).unwrap()));
     assert_eq!(solver.clauses().count(), 1);
 }
+
+#[test]
+fn solve_under_assumptions() {
+    let mut solver = TestSolver::init();
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.add_clause(vec![lit(-1), lit(2)]);
+    solver.assume(lit(-2));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Unsat));
+    assert_eq!(solver.failed(lit(-2)), Ok(true));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    assert_eq!(solver.val(lit(2)), Ok(LitValue::True));
+}
+
+#[test]
+fn kleene_logic() {
+    use crate::LitValue::{True, False, DontCare};
+    assert_eq!(!DontCare, DontCare);
+    assert_eq!(True & DontCare, DontCare);
+    assert_eq!(False & DontCare, False);
+    assert_eq!(True | DontCare, True);
+    assert_eq!(False | DontCare, DontCare);
+    assert_eq!(False.implies(DontCare), True);
+    assert_eq!(True.implies(DontCare), DontCare);
+    assert_eq!(True ^ True, False);
+    assert_eq!(True ^ DontCare, DontCare);
+    assert_eq!(LitValue::from(None), DontCare);
+    assert_eq!(Option::<bool>::from(False), Some(false));
+}
+
+#[test]
+fn eval_clause_and_lits() {
+    let clause: OwnedClause = vec![lit(1), lit(-2)].into();
+    let under = |values: [LitValue; 2]| {
+        clause.eval(|lit| lit.eval(values[lit.var().to_raw() as usize - 1]))
+    };
+    assert_eq!(under([LitValue::False, LitValue::True]), LitValue::False);
+    assert_eq!(under([LitValue::False, LitValue::DontCare]), LitValue::DontCare);
+    assert_eq!(under([LitValue::DontCare, LitValue::False]), LitValue::True);
+    assert_eq!(lit(3).lift(LitValue::True, !lit(3)), Some(LitValue::False));
+    assert_eq!(lit(3).lift(LitValue::True, lit(4)), None);
+}
+
+#[test]
+fn checked_conversions() {
+    use crate::{InvalidLitVal, Var, MAX_VAR};
+    assert_eq!(Lit::try_from(-5i64), Ok(lit(-5)));
+    assert_eq!(Lit::try_from(0usize), Err(InvalidLitVal::Zero));
+    assert_eq!(Lit::try_from(i32::MIN), Err(InvalidLitVal::IntMin));
+    assert_eq!(Lit::try_from(i64::from(i32::MIN)), Err(InvalidLitVal::IntMin));
+    assert_eq!(Lit::try_from(1i64 << 40), Err(InvalidLitVal::Overflow(1 << 40)));
+    assert_eq!(Lit::try_from(u64::MAX), Err(InvalidLitVal::Overflow(u64::MAX.into())));
+    assert_eq!(Var::try_from(7u32), Ok(lit(7).var()));
+    assert_eq!(Var::try_from(-7i64), Err(InvalidLitVal::Negative(-7)));
+    assert_eq!(Var::try_from(MAX_VAR as usize), Ok(Var::MAX));
+    assert_eq!(Var::try_from(MAX_VAR as usize + 1), Err(InvalidLitVal::Overflow(1 << 31)));
+}
@@ -0,0 +1,82 @@
+use crate::{
+    Lit,
+    OwnedClause,
+    SolveResponse,
+    LitValue,
+};
+use std::{
+    fmt,
+    string::String,
+};
+
+/// A single recorded call of the `IpasirSolver` interface.
+///
+/// Results are `None` if the call returned an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A call to `signature` with its result.
+    Signature(String),
+    /// A call to `add_clause`.
+    AddClause(OwnedClause),
+    /// A call to `assume`.
+    Assume(Lit),
+    /// A call to `solve` with its response.
+    Solve(Option<SolveResponse>),
+    /// A call to `val` with its result.
+    Val(Lit, Option<LitValue>),
+    /// A call to `failed` with its result.
+    Failed(Lit, Option<bool>),
+    /// A registration of a terminate callback.
+    SetTerminate,
+    /// A registration of a learn callback with the maximum clause length.
+    SetLearn(usize),
+}
+
+/// Formats an optional result with the given function or as `error`.
+fn fmt_result<T, F>(f: &mut fmt::Formatter, result: Option<T>, fmt_ok: F) -> fmt::Result
+where
+    F: FnOnce(&mut fmt::Formatter, T) -> fmt::Result,
+{
+    match result {
+        Some(ok) => fmt_ok(f, ok),
+        None => write!(f, "error"),
+    }
+}
+
+impl fmt::Display for Event {
+    /// Formats the event as line of the trace format without line break.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Signature(signature) => write!(f, "signature {}", signature),
+            Event::AddClause(clause) => {
+                write!(f, "add")?;
+                for lit in clause {
+                    write!(f, " {}", lit.to_raw())?;
+                }
+                write!(f, " 0")
+            }
+            Event::Assume(lit) => write!(f, "assume {}", lit.to_raw()),
+            Event::Solve(response) => {
+                write!(f, "solve ")?;
+                fmt_result(f, *response, |f, response| write!(f, "{}", response as i32))
+            }
+            Event::Val(lit, value) => {
+                write!(f, "val {} ", lit.to_raw())?;
+                fmt_result(f, *value, |f, value| {
+                    let raw = match value {
+                        LitValue::True => lit.to_raw(),
+                        LitValue::False => -lit.to_raw(),
+                        LitValue::DontCare => 0,
+                    };
+                    write!(f, "{}", raw)
+                })
+            }
+            Event::Failed(lit, failed) => {
+                write!(f, "failed {} ", lit.to_raw())?;
+                fmt_result(f, *failed, |f, failed| write!(f, "{}", failed as i32))
+            }
+            Event::SetTerminate => write!(f, "terminate"),
+            Event::SetLearn(max_len) => write!(f, "learn {}", max_len),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Recording and replaying full traces of IPASIR solver calls.
+//!
+//! Unlike iCNF a trace records every call of the `IpasirSolver` interface
+//! including its result, one call per line:
+//!
+//! - `p ipasirtrace`: The header line.
+//! - `signature <text>`: A call to `signature` returning `<text>`.
+//! - `add <lits> 0`: A call to `add_clause`.
+//! - `assume <lit>`: A call to `assume`.
+//! - `solve <10|20|0>`: A call to `solve` with its response.
+//! - `val <lit> <lit|-lit|0>`: A call to `val` with the result as returned by `ipasir_val`.
+//! - `failed <lit> <1|0>`: A call to `failed` with its result.
+//! - `terminate`: A registration of a terminate callback.
+//! - `learn <max_len>`: A registration of a learn callback.
+//!
+//! Calls that returned an error have `error` in place of their result.
+//! Comment lines start with `c`.
+
+mod event;
+mod reader;
+mod tracing;
+mod replay;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    event::Event,
+    reader::{
+        Reader,
+        TraceErrorKind,
+    },
+    tracing::Tracing,
+    replay::{
+        replay,
+        Divergence,
+        ReplayError,
+    },
+};
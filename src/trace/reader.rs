@@ -0,0 +1,250 @@
+use super::Event;
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    Lit,
+    OwnedClause,
+    SolveResponse,
+    LitValue,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::Read,
+    string::String,
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to traces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceErrorKind {
+    /// A line starts with an unknown keyword.
+    UnknownKeyword(String),
+    /// A value is out of the range of its argument.
+    InvalidValue(i128),
+}
+
+impl fmt::Display for TraceErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceErrorKind::UnknownKeyword(keyword) => write!(f, "unknown keyword {:?}", keyword),
+            TraceErrorKind::InvalidValue(value) => write!(f, "invalid value {}", value),
+        }
+    }
+}
+
+impl Error for TraceErrorKind {}
+
+impl From<TraceErrorKind> for ParseErrorKind {
+    fn from(kind: TraceErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// A streaming reader of traces.
+///
+/// Yields the recorded events in order.
+pub struct Reader<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+    /// `true` if the header has been parsed.
+    header: bool,
+    /// The line of the last event.
+    line: usize,
+    /// `true` if the input is exhausted or an error occured.
+    done: bool,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a new trace reader over the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lexer: Lexer::new(reader),
+            header: false,
+            line: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the one-based line of the last event.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Reads a blank separated literal.
+    fn read_lit(&mut self) -> Result<Lit> {
+        self.lexer.skip_blanks()?;
+        let start = self.lexer.position();
+        match self.lexer.read_int()? {
+            Some(value) => match Lit::try_from_wide(value) {
+                Ok(lit) => Ok(lit),
+                Err(err) => self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+            },
+            None => self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Reads a blank separated result which is either `error` or an integer.
+    ///
+    /// The integer is converted by the given function which
+    /// returns `None` for values out of range.
+    fn read_result<T, F>(&mut self, convert: F) -> Result<Option<T>>
+    where
+        F: FnOnce(i128) -> Option<T>,
+    {
+        self.lexer.skip_blanks()?;
+        let start = self.lexer.position();
+        if self.lexer.peek()?.map(|byte| byte.is_ascii_alphabetic()).unwrap_or(false) {
+            return match self.lexer.read_word()?.as_slice() {
+                b"error" => Ok(None),
+                word => {
+                    let word = String::from_utf8_lossy(word).into_owned();
+                    self.lexer.error_at(start, TraceErrorKind::UnknownKeyword(word))
+                }
+            }
+        }
+        match self.lexer.read_int()? {
+            Some(value) => match convert(value) {
+                Some(result) => Ok(Some(result)),
+                None => self.lexer.error_at(start, TraceErrorKind::InvalidValue(value)),
+            },
+            None => self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Parses the event of the given keyword.
+    fn parse_event(&mut self, keyword: &[u8], start: (usize, usize)) -> Result<Event> {
+        let event = match keyword {
+            b"signature" => {
+                self.lexer.skip_blanks()?;
+                let text = self.lexer.read_rest_of_line()?;
+                Event::Signature(String::from_utf8_lossy(&text).into_owned())
+            }
+            b"add" => {
+                let mut lits = Vec::new();
+                loop {
+                    self.lexer.skip_whitespace()?;
+                    let start = self.lexer.position();
+                    match self.lexer.read_int()? {
+                        Some(0) => break,
+                        Some(value) => match Lit::try_from_wide(value) {
+                            Ok(lit) => lits.push(lit),
+                            Err(err) => return self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+                        },
+                        None => return self.lexer.unexpected(ParseErrorKind::UnterminatedClause),
+                    }
+                }
+                Event::AddClause(OwnedClause::from(lits))
+            }
+            b"assume" => Event::Assume(self.read_lit()?),
+            b"solve" => Event::Solve(self.read_result(|value| match value {
+                10 => Some(SolveResponse::Sat),
+                20 => Some(SolveResponse::Unsat),
+                0 => Some(SolveResponse::Interrupted),
+                _ => None,
+            })?),
+            b"val" => {
+                let lit = self.read_lit()?;
+                Event::Val(lit, self.read_result(|value| match value {
+                    0 => Some(LitValue::DontCare),
+                    v if v == i128::from(lit.to_raw()) => Some(LitValue::True),
+                    v if v == -i128::from(lit.to_raw()) => Some(LitValue::False),
+                    _ => None,
+                })?)
+            }
+            b"failed" => {
+                let lit = self.read_lit()?;
+                Event::Failed(lit, self.read_result(|value| match value {
+                    0 => Some(false),
+                    1 => Some(true),
+                    _ => None,
+                })?)
+            }
+            b"terminate" => Event::SetTerminate,
+            b"learn" => {
+                self.lexer.skip_blanks()?;
+                let start = self.lexer.position();
+                match self.lexer.read_usize()? {
+                    Some(max_len) => Event::SetLearn(max_len),
+                    None => return self.lexer.error_at(start, ParseErrorKind::UnexpectedEof),
+                }
+            }
+            _ => {
+                let keyword = String::from_utf8_lossy(keyword).into_owned();
+                return self.lexer.error_at(start, TraceErrorKind::UnknownKeyword(keyword))
+            }
+        };
+        self.lexer.finish_line()?;
+        Ok(event)
+    }
+
+    /// Parses the next event.
+    fn parse_next(&mut self) -> Result<Option<Event>> {
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            if self.lexer.peek()?.is_none() {
+                if self.header {
+                    return Ok(None)
+                }
+                return self.lexer.error(ParseErrorKind::MissingHeader)
+            }
+            self.lexer.take_line_start();
+            let keyword = self.lexer.read_word()?;
+            match keyword.as_slice() {
+                b"c" => self.lexer.skip_line()?,
+                b"p" => {
+                    if self.header {
+                        return self.lexer.error_at(start, ParseErrorKind::UnexpectedHeader)
+                    }
+                    self.lexer.skip_blanks()?;
+                    if self.lexer.read_word()? != b"ipasirtrace" {
+                        return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+                    }
+                    self.lexer.expect_line_end(start, ParseErrorKind::InvalidHeader)?;
+                    self.header = true;
+                }
+                _ if !self.header => return self.lexer.error_at(start, ParseErrorKind::MissingHeader),
+                keyword => {
+                    self.line = start.0;
+                    return self.parse_event(keyword, start).map(Some)
+                }
+            }
+        }
+    }
+}
+
+impl<R> Iterator for Reader<R>
+where
+    R: Read,
+{
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        match self.parse_next() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use super::{
+    Event,
+    Reader,
+};
+use crate::{
+    dimacs::ParseError,
+    IpasirSolver,
+    SolveControl,
+};
+use std::{
+    error::Error,
+    fmt,
+    io::Read,
+};
+
+/// The first call whose result differs between a trace and its replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// The one-based line of the call in the trace.
+    pub line: usize,
+    /// The call as recorded in the trace.
+    pub expected: Event,
+    /// The call as replayed.
+    pub actual: Event,
+}
+
+/// An error encountered while replaying a trace.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The trace is malformed.
+    Parse(ParseError),
+    /// The replayed solver diverged from the trace.
+    Diverged(Divergence),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Parse(err) => err.fmt(f),
+            ReplayError::Diverged(divergence) => write!(
+                f,
+                "line {}: expected `{}` but found `{}`",
+                divergence.line, divergence.expected, divergence.actual,
+            ),
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReplayError::Parse(err) => Some(err),
+            ReplayError::Diverged(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for ReplayError {
+    fn from(err: ParseError) -> Self {
+        ReplayError::Parse(err)
+    }
+}
+
+/// Re-executes all calls of the trace against the solver.
+///
+/// Returns the number of replayed calls or the first call whose result
+/// differs from the trace.
+///
+/// # Note
+///
+/// - Signatures are not compared since they differ between solvers.
+/// - Registered callbacks never stop the solver and ignore learnt clauses.
+/// - Different solvers may legitimately find different models and failed
+///   assumptions, so divergences in `val` and `failed` calls are most
+///   meaningful when replaying against another build of the same solver.
+pub fn replay<S, R>(solver: &mut S, reader: R) -> Result<usize, ReplayError>
+where
+    S: IpasirSolver,
+    R: Read,
+{
+    let mut reader = Reader::new(reader);
+    let mut count = 0;
+    while let Some(event) = reader.next() {
+        let expected = event?;
+        let actual = match &expected {
+            Event::Signature(_) => None,
+            Event::AddClause(clause) => {
+                solver.add_clause(clause);
+                None
+            }
+            Event::Assume(lit) => {
+                solver.assume(*lit);
+                None
+            }
+            Event::Solve(_) => Some(Event::Solve(solver.solve().ok())),
+            Event::Val(lit, _) => Some(Event::Val(*lit, solver.val(*lit).ok())),
+            Event::Failed(lit, _) => Some(Event::Failed(*lit, solver.failed(*lit).ok())),
+            Event::SetTerminate => {
+                solver.set_terminate(|| SolveControl::Continue);
+                None
+            }
+            Event::SetLearn(max_len) => {
+                solver.set_learn(*max_len, |_| ());
+                None
+            }
+        };
+        count += 1;
+        if let Some(actual) = actual {
+            if actual != expected {
+                return Err(ReplayError::Diverged(Divergence {
+                    line: reader.line(),
+                    expected,
+                    actual,
+                }))
+            }
+        }
+    }
+    Ok(count)
+}
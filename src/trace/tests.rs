@@ -0,0 +1,80 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    LitValue,
+    SolveControl,
+    SolveResponse,
+    tests::{TestSolver, lit},
+};
+use std::{
+    string::ToString,
+    vec::Vec,
+};
+
+/// Records a small session with the test solver.
+fn record_session() -> Vec<u8> {
+    let mut solver = Tracing::new(TestSolver::init(), Vec::new()).unwrap();
+    solver.signature();
+    solver.set_terminate(|| SolveControl::Continue);
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.add_clause(vec![lit(-1)]);
+    solver.assume(lit(-2));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Unsat));
+    assert_eq!(solver.failed(lit(-2)), Ok(true));
+    assert!(solver.val(lit(2)).is_err());
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    assert_eq!(solver.val(lit(2)), Ok(LitValue::True));
+    solver.finish().unwrap().1.unwrap()
+}
+
+#[test]
+fn trace_format() {
+    let trace = record_session();
+    assert_eq!(std::str::from_utf8(&trace).unwrap(), "\
+p ipasirtrace
+signature TestSolver
+terminate
+add 1 2 0
+add -1 0
+assume -2
+solve 20
+failed -2 1
+val 2 error
+solve 10
+val 2 2
+");
+    let events = Reader::new(trace.as_slice()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(events.len(), 10);
+    assert_eq!(events[7], Event::Val(lit(2), None));
+}
+
+#[test]
+fn replay_matches_same_solver() {
+    let trace = record_session();
+    assert_eq!(replay(&mut TestSolver::init(), trace.as_slice()).unwrap(), 10);
+}
+
+#[test]
+fn replay_flags_first_divergence() {
+    let trace = "p ipasirtrace\nadd 1 0\nsolve 10\nval 1 -1\nsolve 20\n";
+    match replay(&mut TestSolver::init(), trace.as_bytes()) {
+        Err(ReplayError::Diverged(divergence)) => {
+            assert_eq!(divergence.line, 4);
+            assert_eq!(divergence.actual, Event::Val(lit(1), Some(LitValue::True)));
+            assert_eq!(
+                ReplayError::Diverged(divergence).to_string(),
+                "line 4: expected `val 1 -1` but found `val 1 1`"
+            );
+        }
+        other => panic!("unexpected replay result: {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_keyword() {
+    let err = Reader::new("p ipasirtrace\nfrobnicate 1\n".as_bytes()).next().unwrap().unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 1));
+    assert_eq!(err.kind().to_string(), "unknown keyword \"frobnicate\"");
+    let kind = TraceErrorKind::UnknownKeyword("frobnicate".to_string());
+    assert_eq!(err.kind().format_kind(), Some(&kind));
+}
@@ -0,0 +1,162 @@
+use super::Event;
+use crate::{
+    Lit,
+    Clause,
+    OwnedClause,
+    IpasirSolver,
+    SolveResponse,
+    Result,
+    LitValue,
+    SolveControl,
+};
+use core::cell::RefCell;
+use std::io::{
+    self,
+    Write,
+};
+
+/// The log target of a tracing solver.
+struct Target<W> {
+    /// The underlying writer if any.
+    writer: Option<W>,
+    /// The first error encountered while writing.
+    error: Option<io::Error>,
+}
+
+/// Wraps an IPASIR solver and writes a trace of all calls to it.
+///
+/// # Note
+///
+/// Invocations of the terminate and learn callbacks by the solver are not traced.
+/// Since the solver interface cannot report I/O errors the first
+/// error is stored, tracing stops and the error is returned by `finish`.
+/// A solver created through `IpasirSolver::init` has no trace target
+/// and does not trace anything.
+pub struct Tracing<S, W> {
+    /// The inner solver.
+    solver: S,
+    /// The trace target.
+    ///
+    /// This is a `RefCell` since `signature` is traced as well.
+    target: RefCell<Target<W>>,
+}
+
+impl<S, W> Tracing<S, W>
+where
+    W: Write,
+{
+    /// Wraps the given solver tracing to the given writer.
+    pub fn new(solver: S, mut writer: W) -> io::Result<Self> {
+        writeln!(writer, "p ipasirtrace")?;
+        Ok(Self {
+            solver,
+            target: RefCell::new(Target {
+                writer: Some(writer),
+                error: None,
+            }),
+        })
+    }
+
+    /// Returns a shared reference to the inner solver.
+    pub fn get_ref(&self) -> &S {
+        &self.solver
+    }
+
+    /// Flushes the trace and returns the inner solver and the trace target.
+    pub fn finish(self) -> io::Result<(S, Option<W>)> {
+        let target = self.target.into_inner();
+        if let Some(error) = target.error {
+            return Err(error)
+        }
+        let writer = match target.writer {
+            Some(mut writer) => {
+                writer.flush()?;
+                Some(writer)
+            }
+            None => None,
+        };
+        Ok((self.solver, writer))
+    }
+
+    /// Writes the event unless tracing has failed before.
+    fn trace(&self, event: &Event) {
+        let target = &mut *self.target.borrow_mut();
+        if target.error.is_some() {
+            return
+        }
+        if let Some(writer) = target.writer.as_mut() {
+            target.error = writeln!(writer, "{}", event).err();
+        }
+    }
+}
+
+impl<S, W> IpasirSolver for Tracing<S, W>
+where
+    S: IpasirSolver,
+    W: Write,
+{
+    fn signature(&self) -> &'static str {
+        let signature = self.solver.signature();
+        self.trace(&Event::Signature(signature.into()));
+        signature
+    }
+
+    fn init() -> Self {
+        Self {
+            solver: S::init(),
+            target: RefCell::new(Target {
+                writer: None,
+                error: None,
+            }),
+        }
+    }
+
+    fn add_clause<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        let clause: OwnedClause = lits.into_iter().collect();
+        self.solver.add_clause(&clause);
+        self.trace(&Event::AddClause(clause));
+    }
+
+    fn assume(&mut self, lit: Lit) {
+        self.solver.assume(lit);
+        self.trace(&Event::Assume(lit));
+    }
+
+    fn solve(&mut self) -> Result<SolveResponse> {
+        let response = self.solver.solve();
+        self.trace(&Event::Solve(response.as_ref().ok().cloned()));
+        response
+    }
+
+    fn val(&mut self, lit: Lit) -> Result<LitValue> {
+        let value = self.solver.val(lit);
+        self.trace(&Event::Val(lit, value.as_ref().ok().cloned()));
+        value
+    }
+
+    fn failed(&mut self, lit: Lit) -> Result<bool> {
+        let failed = self.solver.failed(lit);
+        self.trace(&Event::Failed(lit, failed.as_ref().ok().cloned()));
+        failed
+    }
+
+    fn set_terminate<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> SolveControl + 'static,
+    {
+        self.solver.set_terminate(callback);
+        self.trace(&Event::SetTerminate);
+    }
+
+    fn set_learn<F>(&mut self, max_len: usize, callback: F)
+    where
+        F: FnMut(Clause) + 'static,
+    {
+        self.solver.set_learn(max_len, callback);
+        self.trace(&Event::SetLearn(max_len));
+    }
+}
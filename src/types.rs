@@ -1,13 +1,18 @@
-use std::{
-    os::raw::c_int,
+use crate::LitValue;
+use alloc::vec::Vec;
+use core::{
     convert::TryFrom,
-    error::Error,
+    ffi::c_int,
     fmt,
     result::Result as StdResult,
 };
 
 /// A variable of the IPASIR implementing solver.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// # Note
+///
+/// With the `serde` feature this is serialized as its DIMACS integer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Var(c_int);
 
@@ -16,47 +21,151 @@ impl Var {
     pub fn to_raw(self) -> c_int {
         self.0
     }
+
+    /// Returns the variable for the given zero-based index.
+    ///
+    /// # Panics
+    ///
+    /// If the index is not representable as variable.
+    pub fn from_index(index: usize) -> Self {
+        assert!(index < MAX_VAR as usize, "variable index {} out of bounds", index);
+        Var(index as c_int + 1)
+    }
+
+    /// Returns the zero-based index of `self`.
+    ///
+    /// # Note
+    ///
+    /// Variable `1` has index `0`.
+    pub fn index(self) -> usize {
+        self.0 as usize - 1
+    }
+
+    /// Returns the literal of `self` with the given sign.
+    pub fn lit(self, sign: Sign) -> Lit {
+        match sign {
+            Sign::Pos => Lit(self.0),
+            Sign::Neg => Lit(-self.0),
+        }
+    }
+}
+
+impl From<Var> for Lit {
+    fn from(var: Var) -> Self {
+        var.lit(Sign::Pos)
+    }
 }
 
 /// A literal of the IPASIR implementing solver.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// # Note
+///
+/// With the `serde` feature this is serialized as its DIMACS integer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Lit(c_int);
 
-/// Encountered when trying to create a literal with an invalid value.
+/// The greatest variable supported by IPASIR solvers.
+///
+/// # Note
+///
+/// IPASIR requires literals to be smaller or equal to `INT_MAX` and
+/// strictly larger than `INT_MIN`, therefore the greatest variable is `INT_MAX`.
+pub const MAX_VAR: c_int = c_int::MAX;
+
+/// Encountered when trying to create a literal or variable with an invalid value.
 ///
 /// # Note
 ///
 /// Invalid values are the following
-/// 
+///
 /// - `0`: Because it cannot have positive or negative polarity
-///        and because it is explicitely forbidden by the IPASIR specification
+///   and because it is explicitely forbidden by the IPASIR specification
 /// - `INT_MIN`: Because it cannot have positive polarity because
-///              `-INT_MIN == INT_MIN`.
+///   `-INT_MIN == INT_MIN`.
+/// - Values whose absolute value is greater than [`MAX_VAR`](constant.MAX_VAR.html).
+/// - Negative values for variables.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct InvalidLitVal(pub c_int);
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum InvalidLitVal {
+    /// The value is zero.
+    Zero,
+    /// The value is `INT_MIN`.
+    IntMin,
+    /// The absolute value is greater than `MAX_VAR`.
+    Overflow(i128),
+    /// The value is negative but denotes a variable.
+    Negative(i128),
+}
 
 impl fmt::Display for InvalidLitVal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "invalid literal value {}", self.0)
+        match self {
+            InvalidLitVal::Zero => write!(f, "invalid literal value 0"),
+            InvalidLitVal::IntMin => write!(f, "invalid literal value {}", c_int::MIN),
+            InvalidLitVal::Overflow(val) => write!(f, "literal value {} out of range", val),
+            InvalidLitVal::Negative(val) => write!(f, "invalid negative variable value {}", val),
+        }
     }
 }
 
-impl Error for InvalidLitVal {}
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidLitVal {}
 
-impl TryFrom<c_int> for Lit {
-    type Error = InvalidLitVal;
+impl Var {
+    /// The greatest variable supported by IPASIR solvers.
+    pub const MAX: Var = Var(MAX_VAR);
+
+    /// Checks the given value to be a valid variable.
+    pub(crate) fn try_from_wide(val: i128) -> StdResult<Self, InvalidLitVal> {
+        match val {
+            0 => Err(InvalidLitVal::Zero),
+            val if val < 0 => Err(InvalidLitVal::Negative(val)),
+            val if val > i128::from(MAX_VAR) => Err(InvalidLitVal::Overflow(val)),
+            val => Ok(Var(val as c_int)),
+        }
+    }
+}
 
-    fn try_from(val: c_int) -> StdResult<Self, Self::Error> {
-        if val == 0 || val == c_int::min_value() {
-            return Err(InvalidLitVal(val))
+impl Lit {
+    /// Checks the given value to be a valid literal.
+    pub(crate) fn try_from_wide(val: i128) -> StdResult<Self, InvalidLitVal> {
+        match val {
+            0 => Err(InvalidLitVal::Zero),
+            val if val == i128::from(c_int::MIN) => Err(InvalidLitVal::IntMin),
+            val if val.abs() > i128::from(MAX_VAR) => Err(InvalidLitVal::Overflow(val)),
+            val => Ok(Lit(val as c_int)),
         }
-        Ok(Self(val))
     }
 }
 
+macro_rules! impl_try_from_int {
+    ( $( $int:ty ),* ) => {
+        $(
+            impl TryFrom<$int> for Var {
+                type Error = InvalidLitVal;
+
+                fn try_from(val: $int) -> StdResult<Self, Self::Error> {
+                    Var::try_from_wide(val as i128)
+                }
+            }
+
+            impl TryFrom<$int> for Lit {
+                type Error = InvalidLitVal;
+
+                fn try_from(val: $int) -> StdResult<Self, Self::Error> {
+                    Lit::try_from_wide(val as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_int!(c_int, i8, i16, i64, isize, u8, u16, u32, u64, usize);
+
 /// The polarity of a literal.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Sign {
     /// Positive polarity.
     Pos,
@@ -80,6 +189,26 @@ impl Lit {
         self.0
     }
 
+    /// Returns the literal for the given zero-based index.
+    ///
+    /// # Panics
+    ///
+    /// If the index is not representable as literal.
+    pub fn from_index(index: usize) -> Self {
+        let sign = if index & 1 == 0 { Sign::Pos } else { Sign::Neg };
+        Var::from_index(index / 2).lit(sign)
+    }
+
+    /// Returns the zero-based index of `self`.
+    ///
+    /// # Note
+    ///
+    /// The positive and negative literals of a variable
+    /// have adjacent indices, positive first.
+    pub fn index(self) -> usize {
+        2 * self.var().index() + (self.0 < 0) as usize
+    }
+
     /// Returns the variable of `self`.
     pub fn var(self) -> Var {
         Var(self.to_raw().abs())
@@ -93,9 +222,40 @@ impl Lit {
             Sign::Neg
         }
     }
+
+    /// Returns the value of `self` given the value of its variable.
+    pub fn eval(self, var_value: LitValue) -> LitValue {
+        match self.sign() {
+            Sign::Pos => var_value,
+            Sign::Neg => !var_value,
+        }
+    }
+
+    /// Returns the value of `other` given the value of `self`.
+    ///
+    /// Returns `None` if `other` refers to another variable than `self`.
+    pub fn lift(self, value: LitValue, other: Lit) -> Option<LitValue> {
+        if self.var() != other.var() {
+            return None
+        }
+        if self == other {
+            Some(value)
+        } else {
+            Some(!value)
+        }
+    }
+}
+
+impl core::ops::Not for Lit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(-self.0)
+    }
 }
 
 /// A clause from the IPASIR solver.
+#[derive(Debug, Copy, Clone)]
 pub struct Clause<'a> {
     /// The zero-ended literals.
     lits: &'a [Lit],
@@ -123,16 +283,142 @@ impl<'a> Clause<'a> {
     }
 
     /// Returns an iterator over the literals of the clause.
-    pub fn iter(&self) -> LitIter {
+    pub fn iter(&self) -> LitIter<'a> {
         LitIter { iter: self.lits.iter() }
     }
+
+    /// Evaluates the clause under the given partial assignment of literals.
+    ///
+    /// # Note
+    ///
+    /// The clause is `LitValue::True` if any of its literals is `LitValue::True`,
+    /// `LitValue::False` if all of its literals are `LitValue::False`
+    /// and `LitValue::DontCare` otherwise.
+    pub fn eval<F>(&self, mut value: F) -> LitValue
+    where
+        F: FnMut(Lit) -> LitValue,
+    {
+        let mut result = LitValue::False;
+        for lit in self.iter() {
+            result = result.or(value(lit));
+            if result == LitValue::True {
+                break
+            }
+        }
+        result
+    }
+}
+
+impl<'a> IntoIterator for &'a Clause<'a> {
+    type Item = Lit;
+    type IntoIter = LitIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, Idx> core::ops::Index<Idx> for Clause<'a>
+where
+    Idx: core::slice::SliceIndex<[Lit]>,
+{
+    type Output = <[Lit] as core::ops::Index<Idx>>::Output;
+
+    fn index(&self, index: Idx) -> &Self::Output {
+        &self.lits[index]
+    }
+}
+
+/// A clause that owns its literals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct OwnedClause {
+    /// The literals of the clause.
+    lits: Vec<Lit>,
+}
+
+impl OwnedClause {
+    /// Returns the length of the clause.
+    pub fn len(&self) -> usize {
+        self.lits.len()
+    }
+
+    /// Returns `true` if the clause is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lits.is_empty()
+    }
+
+    /// Returns an iterator over the literals of the clause.
+    pub fn iter(&self) -> LitIter<'_> {
+        LitIter { iter: self.lits.iter() }
+    }
+
+    /// Evaluates the clause under the given partial assignment of literals.
+    ///
+    /// See [`Clause::eval`](struct.Clause.html#method.eval) for details.
+    pub fn eval<F>(&self, value: F) -> LitValue
+    where
+        F: FnMut(Lit) -> LitValue,
+    {
+        self.as_clause().eval(value)
+    }
+
+    /// Returns a borrowed view of the clause.
+    pub fn as_clause(&self) -> Clause<'_> {
+        Clause::from(self.lits.as_slice())
+    }
+
+    /// Returns the literals of the clause as slice.
+    pub fn as_slice(&self) -> &[Lit] {
+        &self.lits
+    }
+}
+
+impl<'a> From<Clause<'a>> for OwnedClause {
+    fn from(clause: Clause<'a>) -> Self {
+        Self { lits: clause.lits.to_vec() }
+    }
+}
+
+impl From<Vec<Lit>> for OwnedClause {
+    fn from(lits: Vec<Lit>) -> Self {
+        Self { lits }
+    }
+}
+
+impl From<OwnedClause> for Vec<Lit> {
+    fn from(clause: OwnedClause) -> Self {
+        clause.lits
+    }
+}
+
+impl<L> core::iter::FromIterator<L> for OwnedClause
+where
+    L: Into<Lit>,
+{
+    fn from_iter<I>(lits: I) -> Self
+    where
+        I: IntoIterator<Item = L>,
+    {
+        Self { lits: lits.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl<'a> IntoIterator for &'a OwnedClause {
+    type Item = Lit;
+    type IntoIter = LitIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
-impl<'a, Idx> std::ops::Index<Idx> for Clause<'a>
+impl<Idx> core::ops::Index<Idx> for OwnedClause
 where
-    Idx: std::slice::SliceIndex<[Lit]>,
+    Idx: core::slice::SliceIndex<[Lit]>,
 {
-    type Output = <[Lit] as std::ops::Index<Idx>>::Output;
+    type Output = <[Lit] as core::ops::Index<Idx>>::Output;
 
     fn index(&self, index: Idx) -> &Self::Output {
         &self.lits[index]
@@ -143,7 +429,7 @@ where
 #[derive(Debug, Clone)]
 pub struct LitIter<'a> {
     /// The underlying iterator.
-    iter: std::slice::Iter<'a, Lit>,
+    iter: core::slice::Iter<'a, Lit>,
 }
 
 impl<'a> ExactSizeIterator for LitIter<'a> {
@@ -0,0 +1,72 @@
+use crate::{
+    Var,
+    Lit,
+    Sign,
+};
+
+/// Hands out fresh variables that do not clash with variables in use.
+///
+/// # Note
+///
+/// Encodings take a variable manager to introduce auxiliary variables,
+/// so all variables of the input must be reserved before encoding.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct VarManager {
+    /// The number of variables in use.
+    num_vars: usize,
+}
+
+impl VarManager {
+    /// Creates a new variable manager without variables in use.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new variable manager with all variables up to `max_var` in use.
+    pub fn with_max_var(max_var: Option<Var>) -> Self {
+        let mut vars = Self::new();
+        if let Some(max_var) = max_var {
+            vars.reserve(max_var);
+        }
+        vars
+    }
+
+    /// Returns the number of variables in use.
+    ///
+    /// # Note
+    ///
+    /// This is the number of the greatest variable in use.
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Returns the greatest variable in use if any.
+    pub fn max_var(&self) -> Option<Var> {
+        self.num_vars.checked_sub(1).map(Var::from_index)
+    }
+
+    /// Marks all variables up to the given variable as in use.
+    pub fn reserve(&mut self, var: Var) {
+        self.num_vars = self.num_vars.max(var.index() + 1);
+    }
+
+    /// Returns a fresh variable.
+    ///
+    /// # Panics
+    ///
+    /// If all variables are in use.
+    pub fn fresh(&mut self) -> Var {
+        let var = Var::from_index(self.num_vars);
+        self.num_vars += 1;
+        var
+    }
+
+    /// Returns the positive literal of a fresh variable.
+    ///
+    /// # Panics
+    ///
+    /// If all variables are in use.
+    pub fn fresh_lit(&mut self) -> Lit {
+        self.fresh().lit(Sign::Pos)
+    }
+}
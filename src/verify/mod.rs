@@ -0,0 +1,228 @@
+//! Solver wrappers that verify the answers of an inner solver.
+
+use crate::{
+    Lit,
+    Clause,
+    OwnedClause,
+    Cnf,
+    IpasirSolver,
+    SolveResponse,
+    Result,
+    LitValue,
+    SolveControl,
+    VerificationError,
+};
+use alloc::vec::Vec;
+
+#[cfg(test)]
+mod tests;
+
+/// Configures the verification of failed assumptions after unsatisfiable solves.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CoreCheck {
+    /// Failed assumptions are taken on faith.
+    #[default]
+    Disabled,
+    /// Re-solves the recorded clauses under only the failed assumptions
+    /// using a fresh solver instance and confirms unsatisfiability.
+    ///
+    /// # Note
+    ///
+    /// An empty set of failed assumptions is taken on faith since
+    /// verifying it requires solving the formula without assumptions.
+    FailedAssumptions,
+    /// Like `FailedAssumptions` but also re-solves the formula without
+    /// any assumptions if no assumption failed.
+    Formula,
+}
+
+/// Wraps an IPASIR solver and verifies its answers.
+///
+/// Records all clauses and assumptions passed to the inner solver
+/// and checks the model of the inner solver against them after
+/// every satisfiable solve.
+/// Failed assumptions of unsatisfiable solves are checked depending
+/// on the configured [`CoreCheck`](enum.CoreCheck.html).
+///
+/// # Note
+///
+/// A literal that is reported as `LitValue::DontCare` may take
+/// any value, therefore it never satisfies a clause on its own.
+/// Assumptions on the other hand are only violated by literals
+/// that are reported as `LitValue::False`.
+pub struct Verified<S> {
+    /// The inner solver.
+    solver: S,
+    /// All clauses added so far.
+    clauses: Cnf,
+    /// The assumptions for the next solve.
+    assumptions: Vec<Lit>,
+    /// How failed assumptions are verified.
+    core_check: CoreCheck,
+}
+
+impl<S> Verified<S> {
+    /// Wraps the given solver.
+    ///
+    /// # Note
+    ///
+    /// Clauses that have been added to the solver before
+    /// wrapping it are not known and thus not verified.
+    pub fn new(solver: S) -> Self {
+        Self {
+            solver,
+            clauses: Cnf::new(),
+            assumptions: Vec::new(),
+            core_check: CoreCheck::default(),
+        }
+    }
+
+    /// Wraps the given solver and verifies failed assumptions as configured.
+    pub fn with_core_check(solver: S, core_check: CoreCheck) -> Self {
+        Self {
+            core_check,
+            ..Self::new(solver)
+        }
+    }
+
+    /// Sets how failed assumptions are verified.
+    pub fn set_core_check(&mut self, core_check: CoreCheck) {
+        self.core_check = core_check;
+    }
+
+    /// Returns how failed assumptions are verified.
+    pub fn core_check(&self) -> CoreCheck {
+        self.core_check
+    }
+
+    /// Returns a shared reference to the inner solver.
+    pub fn get_ref(&self) -> &S {
+        &self.solver
+    }
+
+    /// Returns the inner solver.
+    pub fn into_inner(self) -> S {
+        self.solver
+    }
+
+    /// Returns an iterator over all recorded clauses.
+    pub fn clauses(&self) -> impl Iterator<Item = Clause<'_>> {
+        self.clauses.clauses()
+    }
+}
+
+impl<S> Verified<S>
+where
+    S: IpasirSolver,
+{
+    /// Checks the current model of the inner solver against
+    /// all recorded clauses and the given assumptions.
+    fn verify_model(&mut self, assumptions: &[Lit]) -> Result<()> {
+        for &lit in assumptions {
+            if self.solver.val(lit)? == LitValue::False {
+                return Err(VerificationError::ViolatedAssumption(lit).into())
+            }
+        }
+        for clause in &self.clauses {
+            let mut satisfied = false;
+            for lit in clause {
+                if self.solver.val(lit)? == LitValue::True {
+                    satisfied = true;
+                    break
+                }
+            }
+            if !satisfied {
+                return Err(VerificationError::ViolatedClause(clause.clone()).into())
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the failed assumptions of the inner solver after an unsatisfiable
+    /// solve under the given assumptions using a fresh solver instance.
+    fn verify_core(&mut self, assumptions: &[Lit]) -> Result<()> {
+        if self.core_check == CoreCheck::Disabled {
+            return Ok(())
+        }
+        let mut core = Vec::new();
+        for &lit in assumptions {
+            if self.solver.failed(lit)? {
+                core.push(lit);
+            }
+        }
+        if core.is_empty() && self.core_check != CoreCheck::Formula {
+            return Ok(())
+        }
+        let mut checker = S::init();
+        self.clauses.add_to(&mut checker);
+        for &lit in &core {
+            checker.assume(lit);
+        }
+        match checker.solve()? {
+            SolveResponse::Unsat => Ok(()),
+            SolveResponse::Sat => Err(VerificationError::SatisfiableCore(core).into()),
+            SolveResponse::Interrupted => Err(VerificationError::Inconclusive.into()),
+        }
+    }
+}
+
+impl<S> IpasirSolver for Verified<S>
+where
+    S: IpasirSolver,
+{
+    fn signature(&self) -> &'static str {
+        self.solver.signature()
+    }
+
+    fn init() -> Self {
+        Self::new(S::init())
+    }
+
+    fn add_clause<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        let clause: OwnedClause = lits.into_iter().collect();
+        self.solver.add_clause(&clause);
+        self.clauses.push(clause);
+    }
+
+    fn assume(&mut self, lit: Lit) {
+        self.solver.assume(lit);
+        self.assumptions.push(lit);
+    }
+
+    fn solve(&mut self) -> Result<SolveResponse> {
+        let assumptions = core::mem::take(&mut self.assumptions);
+        let response = self.solver.solve()?;
+        match response {
+            SolveResponse::Sat => self.verify_model(&assumptions)?,
+            SolveResponse::Unsat => self.verify_core(&assumptions)?,
+            SolveResponse::Interrupted => (),
+        }
+        Ok(response)
+    }
+
+    fn val(&mut self, lit: Lit) -> Result<LitValue> {
+        self.solver.val(lit)
+    }
+
+    fn failed(&mut self, lit: Lit) -> Result<bool> {
+        self.solver.failed(lit)
+    }
+
+    fn set_terminate<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> SolveControl + 'static,
+    {
+        self.solver.set_terminate(callback)
+    }
+
+    fn set_learn<F>(&mut self, max_len: usize, callback: F)
+    where
+        F: FnMut(Clause) + 'static,
+    {
+        self.solver.set_learn(max_len, callback)
+    }
+}
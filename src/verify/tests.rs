@@ -0,0 +1,143 @@
+use super::*;
+use crate::{
+    SolverErrorKind,
+    tests::{TestSolver, lit},
+};
+
+/// A solver that lies about the value and failure of a single variable.
+struct Lying {
+    /// The underlying correct solver.
+    solver: TestSolver,
+    /// The variable whose value is reported negated.
+    flipped: i32,
+}
+
+impl IpasirSolver for Lying {
+    fn signature(&self) -> &'static str {
+        "Lying"
+    }
+
+    fn init() -> Self {
+        Self { solver: TestSolver::init(), flipped: 1 }
+    }
+
+    fn add_clause<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        self.solver.add_clause(lits)
+    }
+
+    fn assume(&mut self, lit: Lit) {
+        self.solver.assume(lit)
+    }
+
+    fn solve(&mut self) -> Result<SolveResponse> {
+        self.solver.solve()
+    }
+
+    fn val(&mut self, lit: Lit) -> Result<LitValue> {
+        let value = self.solver.val(lit)?;
+        if lit.var().to_raw() != self.flipped {
+            return Ok(value)
+        }
+        Ok(match value {
+            LitValue::True => LitValue::False,
+            LitValue::False => LitValue::True,
+            LitValue::DontCare => LitValue::DontCare,
+        })
+    }
+
+    fn failed(&mut self, lit: Lit) -> Result<bool> {
+        let failed = self.solver.failed(lit)?;
+        Ok(failed != (lit.var().to_raw() == self.flipped))
+    }
+
+    fn set_terminate<F>(&mut self, _callback: F)
+    where
+        F: FnMut() -> SolveControl + 'static,
+    {}
+
+    fn set_learn<F>(&mut self, _max_len: usize, _callback: F)
+    where
+        F: FnMut(Clause) + 'static,
+    {}
+}
+
+fn verification_error(result: Result<SolveResponse>) -> VerificationError {
+    match result.unwrap_err().kind() {
+        SolverErrorKind::Verification(err) => err.clone(),
+        other => panic!("unexpected error kind: {:?}", other),
+    }
+}
+
+#[test]
+fn correct_model_passes() {
+    let mut solver = Verified::<TestSolver>::init();
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.add_clause(vec![lit(-1), lit(3)]);
+    solver.assume(lit(1));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    assert_eq!(solver.clauses().count(), 2);
+}
+
+#[test]
+fn violated_clause_is_reported() {
+    let mut solver = Verified::<Lying>::init();
+    solver.add_clause(vec![lit(1)]);
+    solver.add_clause(vec![lit(2), lit(3)]);
+    assert_eq!(
+        verification_error(solver.solve()),
+        VerificationError::ViolatedClause(vec![lit(1)].into())
+    );
+}
+
+#[test]
+fn violated_assumption_is_reported() {
+    let mut solver = Verified::<Lying>::init();
+    solver.add_clause(vec![lit(2), lit(3)]);
+    solver.assume(lit(-1));
+    assert_eq!(
+        verification_error(solver.solve()),
+        VerificationError::ViolatedAssumption(lit(-1))
+    );
+}
+
+#[test]
+fn correct_core_passes() {
+    let mut solver = Verified::with_core_check(TestSolver::init(), CoreCheck::Formula);
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.assume(lit(-1));
+    solver.assume(lit(-2));
+    solver.assume(lit(3));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Unsat));
+    assert_eq!(solver.failed(lit(3)), Ok(false));
+}
+
+#[test]
+fn satisfiable_core_is_reported() {
+    let mut solver = Verified::<Lying>::init();
+    solver.set_core_check(CoreCheck::FailedAssumptions);
+    solver.add_clause(vec![lit(1), lit(2)]);
+    solver.assume(lit(-1));
+    solver.assume(lit(-2));
+    assert_eq!(
+        verification_error(solver.solve()),
+        VerificationError::SatisfiableCore(vec![lit(-2)])
+    );
+}
+
+#[test]
+fn empty_core_is_checked_against_formula() {
+    let mut solver = Verified::with_core_check(Lying::init(), CoreCheck::FailedAssumptions);
+    solver.add_clause(vec![lit(1)]);
+    solver.assume(lit(-1));
+    assert_eq!(solver.solve(), Ok(SolveResponse::Unsat));
+    solver.set_core_check(CoreCheck::Formula);
+    solver.assume(lit(-1));
+    assert_eq!(
+        verification_error(solver.solve()),
+        VerificationError::SatisfiableCore(vec![])
+    );
+}
@@ -0,0 +1,160 @@
+use crate::{
+    Var,
+    Lit,
+    Clause,
+    OwnedClause,
+    Cnf,
+    Model,
+    LitValue,
+    IpasirSolver,
+    VarManager,
+};
+use std::vec::Vec;
+
+/// The weight of a clause of a MaxSAT instance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Weight {
+    /// The clause must be satisfied.
+    Hard,
+    /// Violating the clause costs the given positive weight.
+    Soft(u64),
+}
+
+/// A weighted partial MaxSAT instance of hard and weighted soft clauses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeightedCnf {
+    /// The hard clauses.
+    hard: Cnf,
+    /// The soft clauses with their weights.
+    soft: Vec<(u64, OwnedClause)>,
+    /// The greatest variable of all clauses.
+    max_var: Option<Var>,
+}
+
+impl WeightedCnf {
+    /// Creates a new empty instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a hard clause.
+    pub fn add_hard<I, L>(&mut self, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        self.push(Weight::Hard, lits.into_iter().collect())
+    }
+
+    /// Adds a soft clause with the given weight.
+    ///
+    /// # Panics
+    ///
+    /// If the weight is zero.
+    pub fn add_soft<I, L>(&mut self, weight: u64, lits: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Lit>,
+    {
+        self.push(Weight::Soft(weight), lits.into_iter().collect())
+    }
+
+    /// Adds an owned clause with the given weight.
+    ///
+    /// # Panics
+    ///
+    /// If the weight is `Weight::Soft(0)`.
+    pub fn push(&mut self, weight: Weight, clause: OwnedClause) {
+        for lit in &clause {
+            self.max_var = self.max_var.max(Some(lit.var()));
+        }
+        match weight {
+            Weight::Hard => self.hard.push(clause),
+            Weight::Soft(weight) => {
+                assert!(weight > 0, "soft clauses must have a positive weight");
+                self.soft.push((weight, clause))
+            }
+        }
+    }
+
+    /// Returns the hard clauses.
+    pub fn hard(&self) -> &Cnf {
+        &self.hard
+    }
+
+    /// Returns an iterator over the soft clauses and their weights.
+    pub fn soft(&self) -> impl Iterator<Item = (u64, Clause<'_>)> {
+        self.soft.iter().map(|(weight, clause)| (*weight, clause.as_clause()))
+    }
+
+    /// Returns the number of soft clauses.
+    pub fn num_soft(&self) -> usize {
+        self.soft.len()
+    }
+
+    /// Returns the greatest variable of all clauses if any.
+    pub fn max_var(&self) -> Option<Var> {
+        self.max_var
+    }
+
+    /// Returns the number of variables of the instance.
+    ///
+    /// # Note
+    ///
+    /// This is the number of the greatest variable
+    /// as is the convention for the DIMACS format.
+    pub fn num_vars(&self) -> usize {
+        self.max_var.map(|var| var.index() + 1).unwrap_or(0)
+    }
+
+    /// Returns the sum of all soft weights or `None` if it overflows.
+    pub fn total_weight(&self) -> Option<u64> {
+        self.soft.iter().try_fold(0u64, |sum, (weight, _)| sum.checked_add(*weight))
+    }
+
+    /// Returns the summed weight of the soft clauses that the model does not satisfy.
+    ///
+    /// Returns `None` if the model does not satisfy all hard clauses.
+    /// A clause is only satisfied if one of its literals is `true` in the model.
+    pub fn cost(&self, model: &Model) -> Option<u64> {
+        if model.first_unsatisfied(&self.hard).is_some() {
+            return None
+        }
+        Some(self.soft
+            .iter()
+            .filter(|(_, clause)| model.eval(clause.as_clause()) != LitValue::True)
+            .fold(0u64, |sum, (weight, _)| sum.saturating_add(*weight)))
+    }
+
+    /// Adds the instance to the solver relaxing every soft clause.
+    ///
+    /// Returns a relaxation literal and the weight for every soft clause in order.
+    /// A soft clause may only be violated if its relaxation literal is `true`,
+    /// so assuming the negations of relaxation literals enforces their soft clauses.
+    ///
+    /// # Note
+    ///
+    /// The relaxation literal of a unit soft clause is the negation of its literal.
+    /// All other soft clauses are relaxed by fresh variables of the variable manager
+    /// after reserving the variables of the instance.
+    pub fn load_into<S>(&self, solver: &mut S, vars: &mut VarManager) -> Vec<(Lit, u64)>
+    where
+        S: IpasirSolver,
+    {
+        if let Some(max_var) = self.max_var {
+            vars.reserve(max_var);
+        }
+        self.hard.add_to(solver);
+        let mut relaxed = Vec::with_capacity(self.soft.len());
+        for (weight, clause) in &self.soft {
+            if clause.len() == 1 {
+                relaxed.push((!clause[0], *weight));
+                continue
+            }
+            let relax = vars.fresh_lit();
+            solver.add_clause(clause.iter().chain(Some(relax)));
+            relaxed.push((relax, *weight));
+        }
+        relaxed
+    }
+}
@@ -0,0 +1,34 @@
+//! Reading and writing the WCNF format of weighted partial MaxSAT instances.
+//!
+//! Two variants of the format are supported:
+//!
+//! - The classic format starts with the header `p wcnf <num_vars> <num_clauses> <top>`
+//!   and prefixes every clause with its weight. Clauses with a weight of at least
+//!   `top` are hard. Without `top` all clauses are soft.
+//! - The format used since the MaxSAT Evaluation 2022 has no header and
+//!   prefixes hard clauses with `h` and soft clauses with their weight.
+//!
+//! In both variants weights are positive, clauses are terminated by `0`
+//! and comment lines start with `c`.
+
+mod formula;
+mod parser;
+mod writer;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    formula::{
+        Weight,
+        WeightedCnf,
+    },
+    parser::{
+        Format,
+        Header,
+        Parser,
+        WcnfErrorKind,
+        read_wcnf,
+    },
+    writer::write_wcnf,
+};
@@ -0,0 +1,331 @@
+use super::{
+    Weight,
+    WeightedCnf,
+};
+use crate::{
+    dimacs::{
+        Lexer,
+        ParseError,
+        ParseErrorKind,
+    },
+    Lit,
+    Clause,
+};
+use std::{
+    boxed::Box,
+    error::Error,
+    fmt,
+    io::Read,
+    string::String,
+    vec::Vec,
+};
+
+/// A kind of a parse error specific to WCNF instances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WcnfErrorKind {
+    /// A clause of the headerless format starts with a word other than `h`.
+    UnknownKeyword(String),
+    /// A weight is zero or exceeds `u64::MAX`.
+    InvalidWeight(i128),
+}
+
+impl fmt::Display for WcnfErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WcnfErrorKind::UnknownKeyword(keyword) => write!(f, "unknown keyword {:?}", keyword),
+            WcnfErrorKind::InvalidWeight(weight) => write!(f, "invalid weight {}", weight),
+        }
+    }
+}
+
+impl Error for WcnfErrorKind {}
+
+impl From<WcnfErrorKind> for ParseErrorKind {
+    fn from(kind: WcnfErrorKind) -> Self {
+        ParseErrorKind::Format(Box::new(kind))
+    }
+}
+
+/// Type alias that has a `ParseError` as error variant.
+type Result<T> = core::result::Result<T, ParseError>;
+
+/// The variant of the WCNF format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The classic format with a `p wcnf` header and a top weight for hard clauses.
+    Classic,
+    /// The headerless format with `h` marking hard clauses.
+    Modern,
+}
+
+/// The header of a classic WCNF file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// The declared number of variables.
+    pub num_vars: usize,
+    /// The declared number of hard and soft clauses.
+    pub num_clauses: usize,
+    /// The weight from which on clauses are hard if any.
+    pub top: Option<u64>,
+}
+
+/// A streaming parser for WCNF input of either format.
+///
+/// The format is detected from the first line that is not a comment.
+/// Input of the classic format is checked against its header
+/// like strict DIMACS CNF input.
+///
+/// # Example
+///
+/// ```
+/// use ipasir::wcnf::{Format, Parser, Weight};
+///
+/// let input = "h 1 2 0\n3 -1 0\n";
+/// let mut parser = Parser::new(input.as_bytes());
+/// let (weight, clause) = parser.next_clause().unwrap().unwrap();
+/// assert_eq!((weight, clause.len()), (Weight::Hard, 2));
+/// assert_eq!(parser.format(), Some(Format::Modern));
+/// ```
+pub struct Parser<R> {
+    /// The lexer over the input.
+    lexer: Lexer<R>,
+    /// The detected format.
+    format: Option<Format>,
+    /// The header if already parsed.
+    header: Option<Header>,
+    /// The weight of the current clause.
+    weight: Weight,
+    /// The literals of the current clause.
+    lits: Vec<Lit>,
+    /// The number of clauses parsed so far.
+    num_clauses: usize,
+    /// `true` if the input is exhausted or an error occured.
+    done: bool,
+}
+
+impl<R> Parser<R>
+where
+    R: Read,
+{
+    /// Creates a new parser over the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lexer: Lexer::new(reader),
+            format: None,
+            header: None,
+            weight: Weight::Hard,
+            lits: Vec::new(),
+            num_clauses: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the format of the input if it has been detected already.
+    pub fn format(&self) -> Option<Format> {
+        self.format
+    }
+
+    /// Returns the header if it has been parsed already.
+    pub fn header(&self) -> Option<Header> {
+        self.header
+    }
+
+    /// Parses the header line after its leading `p`.
+    fn parse_header(&mut self, start: (usize, usize)) -> Result<()> {
+        if self.format.is_some() {
+            return self.lexer.error_at(start, ParseErrorKind::UnexpectedHeader)
+        }
+        self.lexer.skip_blanks()?;
+        if self.lexer.read_word()? != b"wcnf" {
+            return self.lexer.error_at(start, ParseErrorKind::InvalidHeader)
+        }
+        let mut counts = [0; 2];
+        for count in &mut counts {
+            self.lexer.skip_blanks()?;
+            match self.lexer.read_usize()? {
+                Some(value) => *count = value,
+                None => return self.lexer.error_at(start, ParseErrorKind::InvalidHeader),
+            }
+        }
+        self.lexer.skip_blanks()?;
+        let top = match self.lexer.peek()? {
+            Some(byte) if byte.is_ascii_digit() => match self.lexer.read_int()? {
+                Some(top) if (1..=i128::from(u64::MAX)).contains(&top) => Some(top as u64),
+                _ => return self.lexer.error_at(start, ParseErrorKind::InvalidHeader),
+            },
+            _ => None,
+        };
+        self.lexer.expect_line_end(start, ParseErrorKind::InvalidHeader)?;
+        self.format = Some(Format::Classic);
+        self.header = Some(Header {
+            num_vars: counts[0],
+            num_clauses: counts[1],
+            top,
+        });
+        Ok(())
+    }
+
+    /// Reads the weight prefix of a clause.
+    fn read_weight(&mut self) -> Result<Weight> {
+        let format = *self.format.get_or_insert(Format::Modern);
+        let start = self.lexer.position();
+        if format == Format::Modern && self.lexer.peek()? == Some(b'h') {
+            let word = self.lexer.read_word()?;
+            if word != b"h" {
+                let word = String::from_utf8_lossy(&word).into_owned();
+                return self.lexer.error_at(start, WcnfErrorKind::UnknownKeyword(word))
+            }
+            return Ok(Weight::Hard)
+        }
+        let weight = match self.lexer.read_int()? {
+            Some(weight) if (1..=i128::from(u64::MAX)).contains(&weight) => weight as u64,
+            Some(weight) => return self.lexer.error_at(start, WcnfErrorKind::InvalidWeight(weight)),
+            None => return self.lexer.unexpected(ParseErrorKind::UnexpectedEof),
+        };
+        match self.header.and_then(|header| header.top) {
+            Some(top) if weight >= top => Ok(Weight::Hard),
+            _ => Ok(Weight::Soft(weight)),
+        }
+    }
+
+    /// Checks the literal read at the given position against the header.
+    fn check_lit(&mut self, value: i128, start: (usize, usize)) -> Result<Lit> {
+        let lit = match Lit::try_from_wide(value) {
+            Ok(lit) => lit,
+            Err(err) => return self.lexer.error_at(start, ParseErrorKind::InvalidLit(err)),
+        };
+        if let Some(header) = self.header {
+            if lit.var().to_raw() as usize > header.num_vars {
+                let kind = ParseErrorKind::VarOutOfRange {
+                    var: lit.var(),
+                    num_vars: header.num_vars,
+                };
+                return self.lexer.error_at(start, kind)
+            }
+        }
+        Ok(lit)
+    }
+
+    /// Counts a completed clause starting at the given position.
+    fn finish_clause(&mut self, start: (usize, usize)) -> Result<()> {
+        self.num_clauses += 1;
+        if let Some(header) = self.header {
+            if self.num_clauses > header.num_clauses {
+                let kind = ParseErrorKind::ClauseCountMismatch {
+                    expected: header.num_clauses,
+                    found: self.num_clauses,
+                };
+                return self.lexer.error_at(start, kind)
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the end of the input.
+    fn finish_input(&mut self, in_clause: bool) -> Result<bool> {
+        self.done = true;
+        if in_clause {
+            return self.lexer.error(ParseErrorKind::UnterminatedClause)
+        }
+        if let Some(header) = self.header {
+            if header.num_clauses != self.num_clauses {
+                let kind = ParseErrorKind::ClauseCountMismatch {
+                    expected: header.num_clauses,
+                    found: self.num_clauses,
+                };
+                return self.lexer.error(kind)
+            }
+        }
+        Ok(false)
+    }
+
+    /// Parses the next clause and its weight into the buffers.
+    ///
+    /// Returns `false` if there are no more clauses.
+    fn parse_clause(&mut self) -> Result<bool> {
+        self.lits.clear();
+        let mut clause_start = None;
+        loop {
+            self.lexer.skip_whitespace()?;
+            let start = self.lexer.position();
+            let byte = match self.lexer.peek()? {
+                Some(byte) => byte,
+                None => return self.finish_input(clause_start.is_some()),
+            };
+            if self.lexer.take_line_start() {
+                match byte {
+                    b'c' => {
+                        self.lexer.skip_line()?;
+                        continue
+                    }
+                    b'p' => {
+                        self.lexer.bump(byte);
+                        self.parse_header(start)?;
+                        continue
+                    }
+                    _ => (),
+                }
+            }
+            let clause_start = match clause_start {
+                Some(clause_start) => clause_start,
+                None => {
+                    clause_start = Some(start);
+                    self.weight = self.read_weight()?;
+                    continue
+                }
+            };
+            match self.lexer.read_int()? {
+                Some(0) => {
+                    self.finish_clause(clause_start)?;
+                    return Ok(true)
+                }
+                Some(value) => {
+                    let lit = self.check_lit(value, start)?;
+                    self.lits.push(lit);
+                }
+                None => return self.lexer.unexpected(ParseErrorKind::UnterminatedClause),
+            }
+        }
+    }
+
+    /// Returns the next clause of the input and its weight.
+    ///
+    /// Returns `None` after the last clause or after an error.
+    ///
+    /// # Note
+    ///
+    /// The returned clause borrows the internal buffer of the parser
+    /// and is only valid until the next call.
+    pub fn next_clause(&mut self) -> Option<Result<(Weight, Clause<'_>)>> {
+        if self.done {
+            return None
+        }
+        match self.parse_clause() {
+            Ok(true) => Some(Ok((self.weight, Clause::from(self.lits.as_slice())))),
+            Ok(false) => None,
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+
+    /// Collects all remaining clauses of the input into an instance.
+    pub fn into_wcnf(mut self) -> Result<WeightedCnf> {
+        let mut wcnf = WeightedCnf::new();
+        while let Some(clause) = self.next_clause() {
+            let (weight, clause) = clause?;
+            wcnf.push(weight, clause.into());
+        }
+        Ok(wcnf)
+    }
+}
+
+/// Parses WCNF input of either format into an instance.
+pub fn read_wcnf<R>(reader: R) -> Result<WeightedCnf>
+where
+    R: Read,
+{
+    Parser::new(reader).into_wcnf()
+}
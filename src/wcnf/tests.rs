@@ -0,0 +1,103 @@
+use super::*;
+use crate::{
+    IpasirSolver,
+    Model,
+    SolveResponse,
+    VarManager,
+    tests::{TestSolver, lit},
+};
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Parses all clauses of the input with their weights.
+fn parse(input: &str) -> Result<Vec<(Weight, Vec<i32>)>, crate::dimacs::ParseError> {
+    let mut parser = Parser::new(input.as_bytes());
+    let mut clauses = Vec::new();
+    while let Some(clause) = parser.next_clause() {
+        let (weight, clause) = clause?;
+        clauses.push((weight, clause.iter().map(|lit| lit.to_raw()).collect()));
+    }
+    Ok(clauses)
+}
+
+/// Returns the position and message of the parse error of the input.
+fn error(input: &str) -> (usize, usize, String) {
+    let err = parse(input).unwrap_err();
+    (err.line(), err.column(), err.kind().to_string())
+}
+
+#[test]
+fn parse_both_formats() {
+    let expected = vec![
+        (Weight::Hard, vec![1, 2]),
+        (Weight::Soft(3), vec![-1]),
+        (Weight::Soft(5), vec![-2, 3]),
+    ];
+    let classic = "c classic\np wcnf 3 3 9\n9 1 2 0\n3 -1 0\n5 -2\n 3 0\n";
+    let modern = "c modern\nh 1 2 0\n3 -1 0\n5 -2 3 0\n";
+    assert_eq!(parse(classic).unwrap(), expected);
+    assert_eq!(parse(modern).unwrap(), expected);
+    let unweighted = parse("p wcnf 1 1\n7 1 0\n").unwrap();
+    assert_eq!(unweighted, vec![(Weight::Soft(7), vec![1])]);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(error("h 1 0\np wcnf 1 1 2\n"), (2, 1, "unexpected header".to_string()));
+    assert_eq!(error("0 1 0\n"), (1, 1, "invalid weight 0".to_string()));
+    assert_eq!(error("hard 1 0\n"), (1, 1, "unknown keyword \"hard\"".to_string()));
+    assert_eq!(error("p wcnf 1 1 5\nh 1 0\n"), (2, 1, "unexpected character 'h'".to_string()));
+    assert_eq!(error("p wcnf 1 1 5\n5 2 0\n"), (2, 3, "variable 2 exceeds the declared 1 variables".to_string()));
+    assert_eq!(error("p wcnf 1 2 5\n5 1 0\n"), (3, 1, "found 1 clauses but 2 are declared".to_string()));
+    assert_eq!(error("3 1"), (1, 4, "last clause is not terminated by 0".to_string()));
+    let err = parse("0 1 0\n").unwrap_err();
+    assert_eq!(err.kind().format_kind(), Some(&WcnfErrorKind::InvalidWeight(0)));
+}
+
+#[test]
+fn write_round_trip() {
+    let mut wcnf = WeightedCnf::new();
+    wcnf.add_hard(vec![lit(1), lit(2)]);
+    wcnf.add_soft(3, vec![lit(-1)]);
+    wcnf.add_soft(5, vec![lit(-2), lit(3)]);
+    let mut classic = Vec::new();
+    write_wcnf(&mut classic, &wcnf, Format::Classic).unwrap();
+    assert_eq!(
+        String::from_utf8(classic.clone()).unwrap(),
+        "p wcnf 3 3 9\n9 1 2 0\n3 -1 0\n5 -2 3 0\n"
+    );
+    let mut modern = Vec::new();
+    write_wcnf(&mut modern, &wcnf, Format::Modern).unwrap();
+    assert_eq!(String::from_utf8(modern.clone()).unwrap(), "h 1 2 0\n3 -1 0\n5 -2 3 0\n");
+    assert_eq!(read_wcnf(classic.as_slice()).unwrap(), wcnf);
+    assert_eq!(read_wcnf(modern.as_slice()).unwrap(), wcnf);
+}
+
+#[test]
+fn relaxation_and_cost() {
+    let mut wcnf = WeightedCnf::new();
+    wcnf.add_hard(vec![lit(1), lit(2)]);
+    wcnf.add_soft(3, vec![lit(-1)]);
+    wcnf.add_soft(5, vec![lit(-2), lit(3)]);
+    wcnf.add_soft(1, vec![lit(-3)]);
+    assert_eq!(wcnf.total_weight(), Some(9));
+    let mut solver = TestSolver::init();
+    // Variable 4 is already in use, e.g. by another encoding.
+    let mut vars = VarManager::with_max_var(Some(lit(4).var()));
+    let relaxed = wcnf.load_into(&mut solver, &mut vars);
+    assert_eq!(relaxed, vec![(lit(1), 3), (lit(5), 5), (lit(3), 1)]);
+    assert_eq!(vars.num_vars(), 5);
+    for &(relax, _) in &relaxed {
+        solver.assume(!relax);
+    }
+    assert_eq!(solver.solve(), Ok(SolveResponse::Unsat));
+    // Violating only the cheapest soft clause is optimal.
+    solver.assume(!relaxed[0].0);
+    solver.assume(!relaxed[1].0);
+    assert_eq!(solver.solve(), Ok(SolveResponse::Sat));
+    let model = Model::from_solver(&mut solver, lit(5).var()).unwrap();
+    assert_eq!(wcnf.cost(&model), Some(1));
+    assert_eq!(wcnf.cost(&Model::new()), None);
+}
@@ -0,0 +1,50 @@
+use super::{
+    Format,
+    WeightedCnf,
+};
+use crate::dimacs::write_clause;
+use std::{
+    io::{
+        self,
+        Write,
+    },
+    string::{
+        String,
+        ToString,
+    },
+};
+
+/// Writes the instance in the given WCNF format.
+///
+/// The classic format uses the total soft weight plus one as top weight.
+///
+/// # Errors
+///
+/// Returns an `InvalidInput` error for the classic format
+/// if the top weight overflows `u64`.
+pub fn write_wcnf<W>(writer: &mut W, wcnf: &WeightedCnf, format: Format) -> io::Result<()>
+where
+    W: Write,
+{
+    let hard: String = match format {
+        Format::Classic => {
+            let top = wcnf
+                .total_weight()
+                .and_then(|total| total.checked_add(1))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "top weight overflows"))?;
+            let num_clauses = wcnf.hard().len() + wcnf.num_soft();
+            writeln!(writer, "p wcnf {} {} {}", wcnf.num_vars(), num_clauses, top)?;
+            top.to_string()
+        }
+        Format::Modern => "h".into(),
+    };
+    for clause in wcnf.hard().clauses() {
+        write!(writer, "{} ", hard)?;
+        write_clause(writer, clause)?;
+    }
+    for (weight, clause) in wcnf.soft() {
+        write!(writer, "{} ", weight)?;
+        write_clause(writer, clause)?;
+    }
+    Ok(())
+}